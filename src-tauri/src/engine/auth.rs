@@ -0,0 +1,205 @@
+//! LAN paylaşımında proxy kimlik doğrulaması.
+//!
+//! Kimlik bilgileri ilk açılışta rastgele üretilir ve `settings.json`'da
+//! tutulur. HTTP istemcileri `Proxy-Authorization: Basic` başlığıyla, SOCKS5
+//! istemcileri kullanıcı adı/parola yöntemiyle (RFC 1929) doğrulanır. Bu
+//! makinenin kendi bağlantıları hiç incelenmeden geçirilir.
+
+use std::io::{self, Read};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+/// Doğrulanmamış HTTP istemcisine dönen yanıt.
+pub const PROXY_AUTH_REQUIRED: &[u8] = b"HTTP/1.1 407 Proxy Authentication Required\r\n\
+Proxy-Authenticate: Basic realm=\"Vexar\"\r\n\
+Content-Length: 0\r\n\
+Connection: close\r\n\r\n";
+/// İstek başlığı bundan uzunsa bağlantı kapatılır.
+const MAX_HEAD: usize = 16 * 1024;
+const HEADER: &str = "proxy-authorization";
+/// Karışabilecek karakterler (0/O, 1/l/I) çıkarıldı; bilgiler elle de girilebilsin.
+const ALPHABET: &[u8] = b"abcdefghjkmnpqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+const USERNAME_PREFIX: &str = "vexar-";
+const USERNAME_LEN: usize = 6;
+const PASSWORD_LEN: usize = 20;
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ProxyCredentials {
+    pub username: String,
+    pub password: String,
+}
+
+impl ProxyCredentials {
+    pub fn generate() -> Result<Self, String> {
+        Ok(Self {
+            username: format!("{USERNAME_PREFIX}{}", random_string(USERNAME_LEN)?),
+            password: random_string(PASSWORD_LEN)?,
+        })
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        // SOCKS5 alanları tek baytlık uzunlukla gönderilir
+        let fits = |s: &str| !s.is_empty() && s.len() <= 255;
+        if !fits(&self.username) || self.username.contains(':') || !fits(&self.password) {
+            return Err("Geçersiz proxy kimlik bilgileri.".to_string());
+        }
+        Ok(())
+    }
+
+    /// Karşılaştırma süresi eşleşen karakter sayısını ele vermez.
+    pub fn matches(&self, username: &[u8], password: &[u8]) -> bool {
+        constant_eq(self.username.as_bytes(), username)
+            & constant_eq(self.password.as_bytes(), password)
+    }
+
+    /// `Basic <base64(kullanıcı:parola)>` başlık değerini doğrular.
+    fn matches_basic(&self, value: &str) -> bool {
+        let Some((scheme, token)) = value.trim().split_once(' ') else {
+            return false;
+        };
+        if !scheme.eq_ignore_ascii_case("basic") {
+            return false;
+        }
+        let Ok(decoded) = STANDARD.decode(token.trim()) else {
+            return false;
+        };
+        match decoded.iter().position(|b| *b == b':') {
+            Some(colon) => self.matches(&decoded[..colon], &decoded[colon + 1..]),
+            None => false,
+        }
+    }
+}
+
+fn random_string(len: usize) -> Result<String, String> {
+    let mut out = String::with_capacity(len);
+    let mut bytes = [0u8; 32];
+    // Mod yanlılığı olmasın diye alfabenin katını aşan baytlar atlanır
+    let limit = (256 / ALPHABET.len() * ALPHABET.len()) as u8;
+    while out.len() < len {
+        getrandom::fill(&mut bytes).map_err(|e| format!("Rastgele veri üretilemedi: {e}"))?;
+        out.extend(
+            bytes
+                .iter()
+                .filter(|b| **b < limit)
+                .map(|b| ALPHABET[*b as usize % ALPHABET.len()] as char)
+                .take(len - out.len()),
+        );
+    }
+    Ok(out)
+}
+
+fn constant_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// İlk isteğin başlığını okur; başlıktan sonra gelen baytlar da döner.
+pub fn read_head<S: Read>(stream: &mut S) -> io::Result<Vec<u8>> {
+    let mut data = Vec::new();
+    let mut chunk = [0u8; 4096];
+    while find_head_end(&data).is_none() {
+        if data.len() > MAX_HEAD {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "istek başlığı çok uzun",
+            ));
+        }
+        let n = stream.read(&mut chunk)?;
+        if n == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        data.extend_from_slice(&chunk[..n]);
+    }
+    Ok(data)
+}
+
+fn find_head_end(data: &[u8]) -> Option<usize> {
+    data.windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .map(|pos| pos + 4)
+}
+
+/// Geçerli bir `Proxy-Authorization` başlığı varsa isteği başlık çıkarılmış
+/// olarak döndürür; bilgiler motora ve hedef sunucuya iletilmez.
+pub fn authorize_http(data: &[u8], credentials: &ProxyCredentials) -> Option<Vec<u8>> {
+    let end = find_head_end(data)?;
+    let head = std::str::from_utf8(&data[..end - 4]).ok()?;
+    let mut authorized = false;
+    let mut out = Vec::with_capacity(data.len());
+    for (index, line) in head.split("\r\n").enumerate() {
+        if index > 0
+            && let Some((name, value)) = line.split_once(':')
+            && name.trim().eq_ignore_ascii_case(HEADER)
+        {
+            authorized |= credentials.matches_basic(value);
+            continue;
+        }
+        out.extend_from_slice(line.as_bytes());
+        out.extend_from_slice(b"\r\n");
+    }
+    out.extend_from_slice(b"\r\n");
+    out.extend_from_slice(&data[end..]);
+    authorized.then_some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn credentials() -> ProxyCredentials {
+        ProxyCredentials {
+            username: "vexar-abc".to_string(),
+            password: "s3cret".to_string(),
+        }
+    }
+
+    #[test]
+    fn generates_usable_credentials() {
+        let a = ProxyCredentials::generate().unwrap();
+        let b = ProxyCredentials::generate().unwrap();
+        assert!(a.validate().is_ok());
+        assert!(a.username.starts_with(USERNAME_PREFIX));
+        assert_eq!(a.password.len(), PASSWORD_LEN);
+        assert!(a.password.bytes().all(|c| ALPHABET.contains(&c)));
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn strips_valid_header_and_rejects_others() {
+        let creds = credentials();
+        let token = STANDARD.encode("vexar-abc:s3cret");
+        let request = format!(
+            "CONNECT example.com:443 HTTP/1.1\r\nHost: example.com:443\r\n\
+             proxy-authorization: Basic {token}\r\n\r\nearly"
+        );
+        assert_eq!(
+            authorize_http(request.as_bytes(), &creds).unwrap(),
+            b"CONNECT example.com:443 HTTP/1.1\r\nHost: example.com:443\r\n\r\nearly"
+        );
+
+        let wrong = format!(
+            "GET http://a/ HTTP/1.1\r\nProxy-Authorization: Basic {}\r\n\r\n",
+            STANDARD.encode("vexar-abc:nope")
+        );
+        assert!(authorize_http(wrong.as_bytes(), &creds).is_none());
+        assert!(authorize_http(b"GET http://a/ HTTP/1.1\r\nHost: a\r\n\r\n", &creds).is_none());
+        assert!(
+            authorize_http(
+                b"GET http://a/ HTTP/1.1\r\nProxy-Authorization: Bearer x\r\n\r\n",
+                &creds
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn reads_head_with_trailing_bytes() {
+        let mut input: &[u8] = b"CONNECT a:443 HTTP/1.1\r\n\r\nrest";
+        assert_eq!(
+            read_head(&mut input).unwrap(),
+            b"CONNECT a:443 HTTP/1.1\r\n\r\nrest"
+        );
+        let mut truncated: &[u8] = b"CONNECT a:443 HTTP/1.1\r\n";
+        assert!(read_head(&mut truncated).is_err());
+    }
+}
@@ -0,0 +1,291 @@
+//! LAN paylaşımında bağlı cihazların oturumları.
+//!
+//! Ağ geçidinden ve SOCKS köprüsünden geçen her bağlantı, kaynak adresin
+//! oturumuna bir tünel olarak kaydedilir; açık tünel sayısı ve aktarılan
+//! baytlar buradan okunur. Bir cihazın bağlantısı kesildiğinde açık
+//! tünelleri kapatılır ve kısa bir süre yeniden bağlanamaz; kalıcı engel için
+//! izin listesi kullanılır. Bu makinenin kendi trafiği izlenmez.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Shutdown, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::reconnect::{Clock, SystemClock};
+
+/// Bağlı cihazlar değiştiğinde yayınlanan event.
+pub const CLIENTS_EVENT: &str = "lan://clients";
+/// Bağlantısı kesilen cihaz bu süre boyunca yeni tünel açamaz.
+const KICK_COOLDOWN: Duration = Duration::from_secs(60);
+/// Tüneli kalmayan oturum bu süreden sonra listeden düşer.
+const IDLE_FORGET: Duration = Duration::from_secs(10 * 60);
+
+/// Arayüze gönderilen oturum özeti; zamanlar Unix milisaniyesi.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct LanClient {
+    pub address: String,
+    pub first_seen_ms: u64,
+    pub last_seen_ms: u64,
+    pub active_tunnels: u32,
+    pub bytes_up: u64,
+    pub bytes_down: u64,
+    pub tunnel_limit: Option<u32>,
+    pub blocked: bool,
+}
+
+/// Bir cihazın iki yöndeki bayt sayaçları; aktarım thread'leri doğrudan artırır.
+#[derive(Default)]
+pub struct Traffic {
+    up: AtomicU64,
+    down: AtomicU64,
+}
+
+impl Traffic {
+    /// Cihazdan motora giden baytlar.
+    pub fn add_up(&self, bytes: usize) {
+        self.up.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    /// Motordan cihaza dönen baytlar.
+    pub fn add_down(&self, bytes: usize) {
+        self.down.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+}
+
+struct Session {
+    first_seen: Instant,
+    last_seen: Instant,
+    /// Bağlantıyı kesmek için tünellerin istemci soketleri.
+    tunnels: HashMap<u64, TcpStream>,
+    traffic: Arc<Traffic>,
+    blocked_until: Option<Instant>,
+}
+
+pub struct ClientTable<C: Clock = SystemClock> {
+    clock: C,
+    sessions: Mutex<HashMap<IpAddr, Session>>,
+    next_id: AtomicU64,
+    /// Son bildirimden beri tünel açıldı, kapandı veya cihaz atıldı.
+    changed: AtomicBool,
+}
+
+impl Default for ClientTable<SystemClock> {
+    fn default() -> Self {
+        Self::with_clock(SystemClock)
+    }
+}
+
+impl<C: Clock> ClientTable<C> {
+    pub fn with_clock(clock: C) -> Self {
+        Self {
+            clock,
+            sessions: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(0),
+            changed: AtomicBool::new(false),
+        }
+    }
+
+    /// Yeni tünel kaydeder; cihaz atılmışsa veya sınırına ulaştıysa hata döner.
+    /// Dönen değer bırakıldığında tünel kapanmış sayılır.
+    pub fn open(
+        self: &Arc<Self>,
+        ip: IpAddr,
+        stream: &TcpStream,
+        limit: Option<u32>,
+    ) -> Result<Tunnel<C>, String> {
+        let now = self.clock.now();
+        let mut sessions = self.sessions.lock().unwrap();
+        forget_idle(&mut sessions, now);
+        let session = sessions.entry(ip).or_insert_with(|| Session {
+            first_seen: now,
+            last_seen: now,
+            tunnels: HashMap::new(),
+            traffic: Arc::default(),
+            blocked_until: None,
+        });
+        if session.blocked_until.is_some_and(|until| now < until) {
+            return Err(format!(
+                "{ip} bağlantısı kesildi; kısa süre sonra yeniden bağlanabilir."
+            ));
+        }
+        if limit.is_some_and(|limit| session.tunnels.len() as u32 >= limit) {
+            return Err(format!("{ip} eş zamanlı bağlantı sınırına ulaştı."));
+        }
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let clone = stream
+            .try_clone()
+            .map_err(|e| format!("Bağlantı kaydedilemedi: {e}"))?;
+        session.blocked_until = None;
+        session.last_seen = now;
+        session.tunnels.insert(id, clone);
+        self.changed.store(true, Ordering::Relaxed);
+        Ok(Tunnel {
+            table: self.clone(),
+            ip,
+            id,
+            traffic: session.traffic.clone(),
+        })
+    }
+
+    /// Cihazın açık tünellerini kapatır; oturum yoksa false döner.
+    pub fn kick(&self, ip: IpAddr) -> bool {
+        let now = self.clock.now();
+        let mut sessions = self.sessions.lock().unwrap();
+        let Some(session) = sessions.get_mut(&ip) else {
+            return false;
+        };
+        for stream in session.tunnels.values() {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+        session.blocked_until = Some(now + KICK_COOLDOWN);
+        self.changed.store(true, Ordering::Relaxed);
+        true
+    }
+
+    pub fn snapshot(&self, limit: impl Fn(IpAddr) -> Option<u32>) -> Vec<LanClient> {
+        let now = self.clock.now();
+        let now_ms = super::pause::now_ms();
+        let to_ms = |at: Instant| now_ms.saturating_sub(now.duration_since(at).as_millis() as u64);
+        let mut sessions = self.sessions.lock().unwrap();
+        forget_idle(&mut sessions, now);
+        let mut clients: Vec<LanClient> = sessions
+            .iter()
+            .map(|(ip, session)| LanClient {
+                address: ip.to_string(),
+                first_seen_ms: to_ms(session.first_seen),
+                // Açık tüneli olan cihaz şu an bağlı sayılır
+                last_seen_ms: if session.tunnels.is_empty() {
+                    to_ms(session.last_seen)
+                } else {
+                    now_ms
+                },
+                active_tunnels: session.tunnels.len() as u32,
+                bytes_up: session.traffic.up.load(Ordering::Relaxed),
+                bytes_down: session.traffic.down.load(Ordering::Relaxed),
+                tunnel_limit: limit(*ip),
+                blocked: session.blocked_until.is_some_and(|until| now < until),
+            })
+            .collect();
+        clients.sort_by_key(|client| client.first_seen_ms);
+        clients
+    }
+
+    /// Bildirim gerekiyorsa true döner: değişiklik olduysa veya trafik akıyorsa.
+    pub fn take_changed(&self) -> bool {
+        let changed = self.changed.swap(false, Ordering::Relaxed);
+        changed
+            || self
+                .sessions
+                .lock()
+                .unwrap()
+                .values()
+                .any(|session| !session.tunnels.is_empty())
+    }
+
+    fn close(&self, ip: IpAddr, id: u64) {
+        let now = self.clock.now();
+        if let Some(session) = self.sessions.lock().unwrap().get_mut(&ip) {
+            session.tunnels.remove(&id);
+            session.last_seen = now;
+        }
+        self.changed.store(true, Ordering::Relaxed);
+    }
+}
+
+fn forget_idle(sessions: &mut HashMap<IpAddr, Session>, now: Instant) {
+    sessions.retain(|_, session| {
+        !session.tunnels.is_empty()
+            || session.blocked_until.is_some_and(|until| now < until)
+            || now.duration_since(session.last_seen) < IDLE_FORGET
+    });
+}
+
+/// Açık bir tünel; bırakıldığında oturumdan düşer.
+pub struct Tunnel<C: Clock = SystemClock> {
+    table: Arc<ClientTable<C>>,
+    ip: IpAddr,
+    id: u64,
+    traffic: Arc<Traffic>,
+}
+
+impl<C: Clock> Tunnel<C> {
+    pub fn traffic(&self) -> &Arc<Traffic> {
+        &self.traffic
+    }
+}
+
+impl<C: Clock> Drop for Tunnel<C> {
+    fn drop(&mut self) {
+        self.table.close(self.ip, self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::reconnect::ManualClock;
+    use std::net::TcpListener;
+
+    /// Bağlı bir soket çifti: (istemci, sunucu tarafı).
+    fn pair() -> (TcpStream, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (server, _) = listener.accept().unwrap();
+        (client, server)
+    }
+
+    #[test]
+    fn tracks_tunnels_and_traffic() {
+        let clock = ManualClock::new();
+        let table = Arc::new(ClientTable::with_clock(clock.clone()));
+        let phone: IpAddr = "192.168.1.20".parse().unwrap();
+        let (_client, server) = pair();
+
+        let first = table.open(phone, &server, Some(2)).unwrap();
+        let second = table.open(phone, &server, Some(2)).unwrap();
+        assert!(table.open(phone, &server, Some(2)).is_err());
+        first.traffic().add_up(100);
+        second.traffic().add_down(4000);
+
+        let clients = table.snapshot(|_| Some(2));
+        assert_eq!(clients.len(), 1);
+        assert_eq!(clients[0].address, "192.168.1.20");
+        assert_eq!(clients[0].active_tunnels, 2);
+        assert_eq!((clients[0].bytes_up, clients[0].bytes_down), (100, 4000));
+        assert_eq!(clients[0].tunnel_limit, Some(2));
+        assert!(table.take_changed());
+
+        drop(first);
+        drop(second);
+        assert!(table.take_changed());
+        assert!(!table.take_changed());
+        assert_eq!(table.snapshot(|_| None)[0].active_tunnels, 0);
+
+        // Uzun süre sessiz kalan cihaz listeden düşer
+        clock.advance(IDLE_FORGET + Duration::from_secs(1));
+        assert!(table.snapshot(|_| None).is_empty());
+    }
+
+    #[test]
+    fn kick_closes_tunnels_and_blocks_briefly() {
+        use std::io::Read;
+
+        let clock = ManualClock::new();
+        let table = Arc::new(ClientTable::with_clock(clock.clone()));
+        let console: IpAddr = "192.168.1.30".parse().unwrap();
+        let (mut client, server) = pair();
+        let tunnel = table.open(console, &server, None).unwrap();
+
+        assert!(table.kick(console));
+        assert!(!table.kick("192.168.1.31".parse().unwrap()));
+        // İstemci tarafı bağlantının kapandığını görür
+        assert_eq!(client.read(&mut [0u8; 1]).unwrap(), 0);
+        drop(tunnel);
+
+        assert!(table.open(console, &server, None).is_err());
+        assert!(table.snapshot(|_| None)[0].blocked);
+        clock.advance(KICK_COOLDOWN);
+        assert!(table.open(console, &server, None).is_ok());
+    }
+}
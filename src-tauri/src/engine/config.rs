@@ -0,0 +1,450 @@
+//! vexar-proxy komut satırı argümanlarının tipli hali.
+//!
+//! Arayüzden gelen ayarlar burada doğrulanır ve motorun beklediği argv'ye
+//! dönüştürülür; motor bayraklarındaki her değişiklik testlerde yakalanır.
+
+use std::net::IpAddr;
+
+use crate::proxy::ProxyMode;
+use crate::proxy::servers::SchemeLayout;
+
+pub const PORT_MIN: u16 = 1024;
+pub const WINDOW_SIZE_MAX: u16 = 1024;
+pub const TIMEOUT_MIN_MS: u32 = 1000;
+pub const TIMEOUT_MAX_MS: u32 = 60_000;
+
+pub const DEFAULT_PROBE_TARGET: &str = "www.cloudflare.com:443";
+
+/// Motorun DoH isteklerini gönderdiği sabit yol.
+const DOH_PATH: &str = "/dns-query";
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+#[serde(default)]
+pub struct EngineConfig {
+    /// 0 ise uygun port `get_sidecar_config` tarafından seçilir.
+    pub listen_port: u16,
+    pub listen_addr: IpAddr,
+    /// DNS sunucusu: IP adresi veya `https://<host>/dns-query` biçiminde DoH URL'si.
+    /// Boşsa sistem DNS'i kullanılır.
+    pub dns_addr: Option<String>,
+    pub window_size: u16,
+    pub enable_doh: bool,
+    pub timeout_ms: u32,
+    /// Hazırlık kontrolünde motor üzerinden CONNECT gönderilecek `host:port`.
+    /// Argv'ye eklenmez.
+    pub probe_target: String,
+    /// Bu oturumda sistem proxy'sinin modu. Argv'ye eklenmez.
+    pub proxy_mode: ProxyMode,
+    /// Motorun yanında SOCKS4/SOCKS5 dinleyicisi açılır. Argv'ye eklenmez.
+    pub socks: bool,
+    /// 0 ise önce 1080, doluysa rastgele bir port seçilir.
+    pub socks_port: u16,
+    /// Sistem proxy'sine yazılacak şemalar. Argv'ye eklenmez.
+    pub scheme_layout: SchemeLayout,
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        Self {
+            listen_port: 0,
+            listen_addr: IpAddr::from([127, 0, 0, 1]),
+            dns_addr: None,
+            window_size: 1,
+            enable_doh: true,
+            timeout_ms: 5000,
+            probe_target: DEFAULT_PROBE_TARGET.to_string(),
+            proxy_mode: ProxyMode::Global,
+            socks: false,
+            socks_port: 0,
+            scheme_layout: SchemeLayout::Single,
+        }
+    }
+}
+
+/// Doğrulanmış DNS hedefi.
+#[derive(Debug, PartialEq)]
+enum DnsTarget {
+    System,
+    Plain(IpAddr),
+    Doh(String),
+}
+
+impl EngineConfig {
+    /// Port otomatik seçildikten sonra aynı ayarların kopyasını döndürür.
+    pub fn with_port(&self, port: u16) -> Self {
+        Self {
+            listen_port: port,
+            ..self.clone()
+        }
+    }
+
+    pub fn is_lan_shared(&self) -> bool {
+        self.listen_addr.is_unspecified()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.listen_port != 0 && self.listen_port < PORT_MIN {
+            return Err(format!(
+                "Geçersiz port numarası: {} ({PORT_MIN}-65535 arası olmalı)",
+                self.listen_port
+            ));
+        }
+        if !(self.listen_addr.is_loopback() || self.listen_addr.is_unspecified()) {
+            return Err(format!(
+                "Geçersiz dinleme adresi: {} (yalnızca loopback veya 0.0.0.0)",
+                self.listen_addr
+            ));
+        }
+        if self.window_size > WINDOW_SIZE_MAX {
+            return Err(format!(
+                "Geçersiz pencere boyutu: {} (0-{WINDOW_SIZE_MAX} arası olmalı)",
+                self.window_size
+            ));
+        }
+        if !(TIMEOUT_MIN_MS..=TIMEOUT_MAX_MS).contains(&self.timeout_ms) {
+            return Err(format!(
+                "Geçersiz zaman aşımı: {} ms ({TIMEOUT_MIN_MS}-{TIMEOUT_MAX_MS} arası olmalı)",
+                self.timeout_ms
+            ));
+        }
+        if self.socks_port != 0 && self.socks_port < PORT_MIN {
+            return Err(format!(
+                "Geçersiz SOCKS portu: {} ({PORT_MIN}-65535 arası olmalı)",
+                self.socks_port
+            ));
+        }
+        if self.socks && self.socks_port != 0 && self.socks_port == self.listen_port {
+            return Err("SOCKS portu motor portuyla aynı olamaz.".to_string());
+        }
+        if self.scheme_layout.needs_socks() && !self.socks {
+            return Err("SOCKS şeması için SOCKS dinleyicisi açılmalı.".to_string());
+        }
+        super::readiness::validate_target(&self.probe_target)?;
+        self.dns_target().map(|_| ())
+    }
+
+    fn dns_target(&self) -> Result<DnsTarget, String> {
+        let Some(raw) = self
+            .dns_addr
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        else {
+            return Ok(DnsTarget::System);
+        };
+
+        if let Ok(ip) = raw.parse::<IpAddr>() {
+            if ip.is_unspecified() || ip.is_multicast() {
+                return Err(format!("Geçersiz DNS adresi: {raw}"));
+            }
+            return Ok(DnsTarget::Plain(ip));
+        }
+
+        let Some(rest) = raw.strip_prefix("https://") else {
+            return Err(format!(
+                "Geçersiz DNS adresi: {raw} (IP adresi veya https:// DoH URL'si olmalı)"
+            ));
+        };
+        let (host, path) = match rest.find('/') {
+            Some(i) => rest.split_at(i),
+            None => (rest, ""),
+        };
+        if !is_valid_host(host) {
+            return Err(format!("Geçersiz DoH sunucusu: {raw}"));
+        }
+        // Motor DoH yolunu kendisi ekler; farklı bir yol desteklenmiyor
+        if !(path.is_empty() || path == "/" || path == DOH_PATH) {
+            return Err(format!(
+                "Desteklenmeyen DoH yolu: {path} ({DOH_PATH} olmalı)"
+            ));
+        }
+        Ok(DnsTarget::Doh(host.to_string()))
+    }
+
+    /// Doğrulanmış ayarları vexar-proxy argv'sine dönüştürür.
+    pub fn to_args(&self) -> Result<Vec<String>, String> {
+        self.validate()?;
+        if self.listen_port == 0 {
+            return Err("Motor portu seçilmedi.".to_string());
+        }
+
+        let mut args = vec![
+            "-listen-port".to_string(),
+            self.listen_port.to_string(),
+            "-listen-addr".to_string(),
+            self.listen_addr.to_string(),
+        ];
+
+        let dns = self.dns_target()?;
+        match &dns {
+            DnsTarget::System => {}
+            DnsTarget::Plain(ip) => args.extend(["-dns-addr".to_string(), ip.to_string()]),
+            DnsTarget::Doh(host) => args.extend(["-dns-addr".to_string(), host.clone()]),
+        }
+
+        args.extend(["-window-size".to_string(), self.window_size.to_string()]);
+        if self.enable_doh || matches!(dns, DnsTarget::Doh(_)) {
+            args.push("-enable-doh".to_string());
+        }
+        args.extend(["-timeout".to_string(), self.timeout_ms.to_string()]);
+        Ok(args)
+    }
+}
+
+fn is_valid_host(host: &str) -> bool {
+    if host.parse::<IpAddr>().is_ok() {
+        return true;
+    }
+    !host.is_empty()
+        && host.len() <= 253
+        && host.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(port: u16) -> EngineConfig {
+        EngineConfig::default().with_port(port)
+    }
+
+    #[test]
+    fn default_argv() {
+        assert_eq!(
+            config(8080).to_args().unwrap(),
+            [
+                "-listen-port",
+                "8080",
+                "-listen-addr",
+                "127.0.0.1",
+                "-window-size",
+                "1",
+                "-enable-doh",
+                "-timeout",
+                "5000"
+            ]
+        );
+    }
+
+    #[test]
+    fn lan_shared_with_plain_dns() {
+        let cfg = EngineConfig {
+            listen_addr: IpAddr::from([0, 0, 0, 0]),
+            dns_addr: Some("1.1.1.1".to_string()),
+            window_size: 0,
+            ..config(8081)
+        };
+        assert!(cfg.is_lan_shared());
+        assert_eq!(
+            cfg.to_args().unwrap(),
+            [
+                "-listen-port",
+                "8081",
+                "-listen-addr",
+                "0.0.0.0",
+                "-dns-addr",
+                "1.1.1.1",
+                "-window-size",
+                "0",
+                "-enable-doh",
+                "-timeout",
+                "5000"
+            ]
+        );
+    }
+
+    #[test]
+    fn plain_dns_without_doh() {
+        let cfg = EngineConfig {
+            dns_addr: Some("9.9.9.9".to_string()),
+            enable_doh: false,
+            timeout_ms: 3000,
+            ..config(9000)
+        };
+        assert_eq!(
+            cfg.to_args().unwrap(),
+            [
+                "-listen-port",
+                "9000",
+                "-listen-addr",
+                "127.0.0.1",
+                "-dns-addr",
+                "9.9.9.9",
+                "-window-size",
+                "1",
+                "-timeout",
+                "3000"
+            ]
+        );
+    }
+
+    #[test]
+    fn doh_url_forces_doh() {
+        let cfg = EngineConfig {
+            dns_addr: Some("https://dns.adguard-dns.com/dns-query".to_string()),
+            enable_doh: false,
+            ..config(8080)
+        };
+        assert_eq!(
+            cfg.to_args().unwrap(),
+            [
+                "-listen-port",
+                "8080",
+                "-listen-addr",
+                "127.0.0.1",
+                "-dns-addr",
+                "dns.adguard-dns.com",
+                "-window-size",
+                "1",
+                "-enable-doh",
+                "-timeout",
+                "5000"
+            ]
+        );
+    }
+
+    #[test]
+    fn ipv6_loopback() {
+        let cfg = EngineConfig {
+            listen_addr: "::1".parse().unwrap(),
+            dns_addr: Some("2606:4700:4700::1111".to_string()),
+            ..config(8082)
+        };
+        assert_eq!(
+            cfg.to_args().unwrap(),
+            [
+                "-listen-port",
+                "8082",
+                "-listen-addr",
+                "::1",
+                "-dns-addr",
+                "2606:4700:4700::1111",
+                "-window-size",
+                "1",
+                "-enable-doh",
+                "-timeout",
+                "5000"
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_fields() {
+        assert!(config(80).validate().is_err());
+        assert!(config(0).to_args().is_err());
+        assert!(
+            EngineConfig {
+                listen_addr: IpAddr::from([192, 168, 1, 5]),
+                ..config(8080)
+            }
+            .validate()
+            .is_err()
+        );
+        assert!(
+            EngineConfig {
+                window_size: WINDOW_SIZE_MAX + 1,
+                ..config(8080)
+            }
+            .validate()
+            .is_err()
+        );
+        assert!(
+            EngineConfig {
+                timeout_ms: 10,
+                ..config(8080)
+            }
+            .validate()
+            .is_err()
+        );
+        assert!(
+            EngineConfig {
+                timeout_ms: TIMEOUT_MAX_MS + 1,
+                ..config(8080)
+            }
+            .validate()
+            .is_err()
+        );
+        assert!(
+            EngineConfig {
+                probe_target: "cloudflare.com".to_string(),
+                ..config(8080)
+            }
+            .validate()
+            .is_err()
+        );
+        assert!(
+            EngineConfig {
+                socks: true,
+                socks_port: 8080,
+                ..config(8080)
+            }
+            .validate()
+            .is_err()
+        );
+        assert!(
+            EngineConfig {
+                socks_port: 1023,
+                ..config(8080)
+            }
+            .validate()
+            .is_err()
+        );
+        assert!(
+            EngineConfig {
+                scheme_layout: SchemeLayout::WebSocks,
+                ..config(8080)
+            }
+            .validate()
+            .is_err()
+        );
+        for dns in [
+            "0.0.0.0",
+            "224.0.0.1",
+            "dns.example",
+            "http://1.1.1.1",
+            "https://",
+            "https://bad_host/dns-query",
+            "https://1.1.1.1/resolve",
+        ] {
+            let cfg = EngineConfig {
+                dns_addr: Some(dns.to_string()),
+                ..config(8080)
+            };
+            assert!(cfg.validate().is_err(), "{dns} kabul edilmemeli");
+        }
+    }
+
+    #[test]
+    fn socks_listener_stays_out_of_argv() {
+        let cfg = EngineConfig {
+            socks: true,
+            socks_port: 1080,
+            scheme_layout: SchemeLayout::WebSocks,
+            ..config(8080)
+        };
+        assert_eq!(cfg.to_args().unwrap(), config(8080).to_args().unwrap());
+    }
+
+    #[test]
+    fn deserializes_partial_json() {
+        let cfg: EngineConfig = serde_json::from_str(
+            r#"{"listen_addr":"0.0.0.0","dns_addr":"8.8.8.8","window_size":0}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            cfg,
+            EngineConfig {
+                listen_addr: IpAddr::from([0, 0, 0, 0]),
+                dns_addr: Some("8.8.8.8".to_string()),
+                window_size: 0,
+                ..EngineConfig::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,223 @@
+//! Bağlıyken motorun hâlâ tünel açabildiğini düzenli olarak kontrol eder.
+//!
+//! Motor kapanmadan kilitlenirse Windows trafiği ölü bir porta yönlendirmeye
+//! devam eder. Art arda yeterince probe başarısız olursa sistem proxy'si
+//! kapatılır (fail-open); motor toparlanınca yeniden açılır.
+
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct HeartbeatPolicy {
+    pub enabled: bool,
+    pub interval_ms: u64,
+    pub timeout_ms: u64,
+    /// Proxy'nin kapatılması için gereken art arda başarısız probe sayısı.
+    pub failure_threshold: u32,
+    /// Proxy'nin yeniden açılması için gereken art arda başarılı probe sayısı.
+    pub recovery_threshold: u32,
+}
+
+impl Default for HeartbeatPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_ms: 5000,
+            timeout_ms: 3000,
+            failure_threshold: 3,
+            recovery_threshold: 2,
+        }
+    }
+}
+
+impl HeartbeatPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1000..=60_000).contains(&self.interval_ms) {
+            return Err("Heartbeat aralığı 1-60 saniye arasında olmalı.".to_string());
+        }
+        if self.timeout_ms < 500 || self.timeout_ms > self.interval_ms {
+            return Err(
+                "Heartbeat zaman aşımı en az 500 ms olmalı ve aralığı geçmemeli.".to_string(),
+            );
+        }
+        if !(1..=20).contains(&self.failure_threshold)
+            || !(1..=20).contains(&self.recovery_threshold)
+        {
+            return Err("Heartbeat eşikleri 1 ile 20 arasında olmalı.".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn interval(&self) -> Duration {
+        Duration::from_millis(self.interval_ms)
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Transition {
+    /// Eşik aşıldı; sistem proxy'si kapatılmalı.
+    FailOpen { failures: u32 },
+    /// Motor yeniden yanıt veriyor; sistem proxy'si açılabilir.
+    Recovered,
+}
+
+/// Probe sonuçlarını sayan, yan etkisi olmayan durum makinesi.
+pub struct HeartbeatGuard {
+    failure_threshold: u32,
+    recovery_threshold: u32,
+    failures: u32,
+    successes: u32,
+    failed_open: bool,
+}
+
+impl HeartbeatGuard {
+    pub fn new(policy: &HeartbeatPolicy) -> Self {
+        Self {
+            failure_threshold: policy.failure_threshold,
+            recovery_threshold: policy.recovery_threshold,
+            failures: 0,
+            successes: 0,
+            failed_open: false,
+        }
+    }
+
+    pub fn record(&mut self, healthy: bool) -> Option<Transition> {
+        if healthy {
+            self.failures = 0;
+            self.successes += 1;
+            if self.failed_open && self.successes >= self.recovery_threshold {
+                self.failed_open = false;
+                return Some(Transition::Recovered);
+            }
+        } else {
+            self.successes = 0;
+            self.failures += 1;
+            if !self.failed_open && self.failures >= self.failure_threshold {
+                self.failed_open = true;
+                return Some(Transition::FailOpen {
+                    failures: self.failures,
+                });
+            }
+        }
+        None
+    }
+
+    /// Proxy yeniden açılamadıysa tekrar fail-open durumuna döner.
+    pub fn revert_recovery(&mut self) {
+        self.failed_open = true;
+        self.successes = 0;
+    }
+
+    /// Sayımı baştan başlatır. Duraklatılmışken veya fail-open uygulanamadığında
+    /// çağrılır; aksi halde guard kapalı sanıp sonraki kilitlenmeyi kaçırır.
+    pub fn reset(&mut self) {
+        self.failures = 0;
+        self.successes = 0;
+        self.failed_open = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guard() -> HeartbeatGuard {
+        HeartbeatGuard::new(&HeartbeatPolicy::default())
+    }
+
+    #[test]
+    fn fails_open_after_consecutive_failures() {
+        let mut guard = guard();
+        assert_eq!(guard.record(false), None);
+        assert_eq!(guard.record(false), None);
+        assert_eq!(
+            guard.record(false),
+            Some(Transition::FailOpen { failures: 3 })
+        );
+        // Kapalıyken yeni hatalar tekrar geçiş üretmez
+        assert_eq!(guard.record(false), None);
+    }
+
+    #[test]
+    fn success_resets_failure_streak() {
+        let mut guard = guard();
+        guard.record(false);
+        guard.record(false);
+        assert_eq!(guard.record(true), None);
+        assert_eq!(guard.record(false), None);
+        assert_eq!(guard.record(false), None);
+    }
+
+    #[test]
+    fn recovers_after_consecutive_successes() {
+        let mut guard = guard();
+        for _ in 0..3 {
+            guard.record(false);
+        }
+        assert_eq!(guard.record(true), None);
+        assert_eq!(guard.record(false), None);
+        assert_eq!(guard.record(true), None);
+        assert_eq!(guard.record(true), Some(Transition::Recovered));
+        // Açıldıktan sonra yeni bir hata serisi gerekir
+        assert_eq!(guard.record(false), None);
+    }
+
+    #[test]
+    fn reverted_recovery_needs_new_streak() {
+        let mut guard = guard();
+        for _ in 0..3 {
+            guard.record(false);
+        }
+        guard.record(true);
+        assert!(guard.record(true).is_some());
+        guard.revert_recovery();
+        assert_eq!(guard.record(true), None);
+        assert!(guard.record(true).is_some());
+    }
+
+    #[test]
+    fn trips_again_after_hang_while_paused() {
+        let mut guard = guard();
+        // Duraklatılmışken kilitlenme: fail-open uygulanmaz, guard sıfırlanır
+        for _ in 0..2 {
+            guard.record(false);
+        }
+        assert!(guard.record(false).is_some());
+        guard.reset();
+
+        // Devam edildikten sonra motor bir süre sağlıklı, sonra yine kilitleniyor
+        assert_eq!(guard.record(true), None);
+        assert_eq!(guard.record(true), None);
+        assert_eq!(guard.record(false), None);
+        assert_eq!(guard.record(false), None);
+        assert_eq!(
+            guard.record(false),
+            Some(Transition::FailOpen { failures: 3 })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_policies() {
+        assert!(HeartbeatPolicy::default().validate().is_ok());
+        for policy in [
+            HeartbeatPolicy {
+                interval_ms: 100,
+                ..HeartbeatPolicy::default()
+            },
+            HeartbeatPolicy {
+                timeout_ms: 10_000,
+                ..HeartbeatPolicy::default()
+            },
+            HeartbeatPolicy {
+                failure_threshold: 0,
+                ..HeartbeatPolicy::default()
+            },
+        ] {
+            assert!(policy.validate().is_err(), "{policy:?}");
+        }
+    }
+}
@@ -0,0 +1,1015 @@
+//! LAN paylaşımında motorun önündeki erişim denetimi.
+//!
+//! Paylaşım açıkken motor yalnızca loopback'te, rastgele bir portta dinler;
+//! `0.0.0.0` üzerindeki port bu ağ geçidine aittir. Ağ geçidi ve SOCKS
+//! köprüsü her bağlantıyı `Gate`'e sorar: izin listesi, kimlik doğrulaması,
+//! bağlantı ve hız sınırları orada uygulanır.
+
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use super::auth::{self, ProxyCredentials};
+use super::clients::{ClientTable, LanClient, Traffic, Tunnel};
+use super::config::EngineConfig;
+use super::shaping::{Direction, Shaper, ShapingSettings};
+use crate::proxy::bypass::network;
+use crate::proxy::endpoint::ProxyEndpoint;
+use crate::proxy::pac;
+
+/// Reddedilen bir istemci bildirildiğinde yayınlanan event.
+pub const REJECTED_EVENT: &str = "lan://rejected";
+/// Aynı adres bu süre içinde tekrar bildirilmez; tarayan bir cihaz günlüğü doldurmasın.
+const REJECT_REPORT_INTERVAL: Duration = Duration::from_secs(60);
+const ACCEPT_POLL: Duration = Duration::from_millis(100);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Diğer cihazların ilk isteğini göndermesi için süre.
+const AUTH_TIMEOUT: Duration = Duration::from_secs(10);
+/// Bağlı cihaz listesinin en sık yayınlanma aralığı.
+const CLIENTS_REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct LanSettings {
+    /// Paylaşılan motora bağlanabilecek adresler.
+    pub allow: AccessList,
+    /// Diğer cihazlardan kullanıcı adı ve parola istenir.
+    pub require_auth: bool,
+    /// İlk açılışta üretilir; kapatınca da saklanır ki yeniden açınca değişmesin.
+    pub credentials: Option<ProxyCredentials>,
+    /// Cihaz başına eş zamanlı bağlantı (tünel) sınırı.
+    pub tunnel_limits: BTreeMap<IpAddr, u32>,
+    /// Toplam ve cihaz başına yükleme/indirme hız sınırları.
+    pub shaping: ShapingSettings,
+}
+
+impl LanSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.tunnel_limits.values().any(|limit| *limit == 0) {
+            return Err("Bağlantı sınırı en az 1 olmalı.".to_string());
+        }
+        self.shaping.validate()?;
+        match &self.credentials {
+            Some(credentials) => credentials.validate(),
+            None if self.require_auth => {
+                Err("Kimlik doğrulaması için kullanıcı adı ve parola gerekli.".to_string())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// İzin listesi gelmeden önce paylaşım açıkken yerel ağdaki her cihaz
+    /// bağlanabiliyordu. Yükseltmede bu makinenin alt ağı (IPv4 `/24`, IPv6
+    /// `/64`) eklenir ki mevcut cihazlar kesilmesin; liste boş değilse
+    /// dokunulmaz. Ekleme yapıldıysa true döner.
+    pub fn seed_subnet(&mut self, lan_ip: IpAddr) -> bool {
+        let lan_ip = normalize(lan_ip);
+        if !self.allow.0.is_empty() || lan_ip.is_loopback() || lan_ip.is_unspecified() {
+            return false;
+        }
+        let prefix = if lan_ip.is_ipv4() { 24 } else { 64 };
+        self.allow.add(AllowEntry {
+            addr: network(lan_ip, prefix),
+            prefix,
+        })
+    }
+
+    /// Bu istemcinin doğrulanması gereken bilgiler; yerel bağlantılar için yok.
+    fn credentials_for(&self, ip: IpAddr) -> Option<&ProxyCredentials> {
+        if self.require_auth && !is_local(ip) {
+            self.credentials.as_ref()
+        } else {
+            None
+        }
+    }
+}
+
+/// Bu makinenin kendi bağlantıları; izin listesi ve kimlik doğrulaması uygulanmaz.
+fn is_local(ip: IpAddr) -> bool {
+    let ip = normalize(ip);
+    ip == IpAddr::V4(Ipv4Addr::LOCALHOST) || ip == IpAddr::V6(Ipv6Addr::LOCALHOST)
+}
+
+/// Tek IP (`/32`, `/128`) veya ağ adresine indirgenmiş aralık.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AllowEntry {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl AllowEntry {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        let (addr, prefix) = match input.split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix)),
+            None => (input, None),
+        };
+        let addr = normalize(
+            addr.parse::<IpAddr>()
+                .map_err(|_| format!("Geçersiz IP adresi: {input}"))?,
+        );
+        let max = max_prefix(addr);
+        let prefix = match prefix {
+            Some(prefix) => prefix
+                .parse::<u8>()
+                .ok()
+                .filter(|p| *p <= max)
+                .ok_or_else(|| format!("Geçersiz CIDR önek uzunluğu: {input}"))?,
+            None => max,
+        };
+        Ok(Self {
+            addr: network(addr, prefix),
+            prefix,
+        })
+    }
+
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        let ip = normalize(ip);
+        ip.is_ipv4() == self.addr.is_ipv4() && network(ip, self.prefix) == self.addr
+    }
+}
+
+impl std::fmt::Display for AllowEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.prefix == max_prefix(self.addr) {
+            write!(f, "{}", self.addr)
+        } else {
+            write!(f, "{}/{}", self.addr, self.prefix)
+        }
+    }
+}
+
+fn max_prefix(addr: IpAddr) -> u8 {
+    if addr.is_ipv4() { 32 } else { 128 }
+}
+
+/// Çift yığınlı soketlerde IPv4 istemciler `::ffff:a.b.c.d` olarak görünür.
+fn normalize(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(v6) => v6
+            .to_ipv4_mapped()
+            .map(IpAddr::V4)
+            .unwrap_or(IpAddr::V6(v6)),
+        ip => ip,
+    }
+}
+
+/// Sıralı ve tekrarsız izin listesi (tek IP veya CIDR). Listede olmayan
+/// adreslerin bağlantıları kapatılır; bu makinenin kendisi her zaman kabul edilir.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "Vec<String>", into = "Vec<String>")]
+pub struct AccessList(Vec<AllowEntry>);
+
+impl AccessList {
+    /// Girdiyi ekler; zaten listedeyse false döner.
+    pub fn add(&mut self, entry: AllowEntry) -> bool {
+        if self.0.contains(&entry) {
+            return false;
+        }
+        self.0.push(entry);
+        true
+    }
+
+    /// Girdiyi çıkarır; listede yoksa false döner.
+    pub fn remove(&mut self, entry: &AllowEntry) -> bool {
+        let before = self.0.len();
+        self.0.retain(|e| e != entry);
+        self.0.len() != before
+    }
+
+    pub fn allows(&self, ip: IpAddr) -> bool {
+        is_local(ip) || self.0.iter().any(|entry| entry.contains(ip))
+    }
+}
+
+impl TryFrom<Vec<String>> for AccessList {
+    type Error = String;
+
+    fn try_from(entries: Vec<String>) -> Result<Self, String> {
+        let mut list = AccessList::default();
+        for entry in entries {
+            list.add(AllowEntry::parse(&entry)?);
+        }
+        Ok(list)
+    }
+}
+
+impl From<AccessList> for Vec<String> {
+    fn from(list: AccessList) -> Self {
+        list.0.iter().map(ToString::to_string).collect()
+    }
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct Rejection {
+    pub address: String,
+}
+
+/// Arayüzdeki kimlik doğrulaması ayarı ve bilgiler.
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct LanAuth {
+    pub enabled: bool,
+    pub credentials: Option<ProxyCredentials>,
+}
+
+impl From<&LanSettings> for LanAuth {
+    fn from(settings: &LanSettings) -> Self {
+        Self {
+            enabled: settings.require_auth,
+            credentials: settings.credentials.clone(),
+        }
+    }
+}
+
+/// Ağ geçidi ve SOCKS köprüsünün ortak kapısı.
+pub struct Gate {
+    settings: RwLock<LanSettings>,
+    reported: Mutex<HashMap<IpAddr, Instant>>,
+    on_reject: Box<dyn Fn(SocketAddr) + Send + Sync>,
+    clients: Arc<ClientTable>,
+    shaper: Arc<Shaper>,
+}
+
+impl Gate {
+    pub fn new(
+        settings: LanSettings,
+        on_reject: impl Fn(SocketAddr) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            shaper: Arc::new(Shaper::new(settings.shaping.clone())),
+            settings: RwLock::new(settings),
+            reported: Mutex::new(HashMap::new()),
+            on_reject: Box::new(on_reject),
+            clients: Arc::default(),
+        }
+    }
+
+    /// Motor çalışırken çağrılabilir; yeni kurallar sonraki bağlantılarda,
+    /// hız sınırları açık bağlantılarda da hemen geçerlidir.
+    pub fn set_settings(&self, settings: LanSettings) {
+        let mut current = self.settings.write().unwrap();
+        if current.shaping != settings.shaping {
+            self.shaper.set_settings(settings.shaping.clone());
+        }
+        *current = settings;
+    }
+
+    /// Bağlantının doğrulanması gereken bilgiler; gerekmiyorsa None.
+    pub fn credentials_for(&self, peer: SocketAddr) -> Option<ProxyCredentials> {
+        self.settings
+            .read()
+            .unwrap()
+            .credentials_for(peer.ip())
+            .cloned()
+    }
+
+    /// Diğer cihazların bağlantısını oturumuna kaydeder; yerel bağlantılar
+    /// izlenmez. Cihaz atılmışsa veya sınırına ulaştıysa hata döner.
+    pub fn track(&self, peer: SocketAddr, stream: &TcpStream) -> Result<Option<Tunnel>, String> {
+        let ip = normalize(peer.ip());
+        if is_local(ip) {
+            return Ok(None);
+        }
+        let limit = self
+            .settings
+            .read()
+            .unwrap()
+            .tunnel_limits
+            .get(&ip)
+            .copied();
+        self.clients.open(ip, stream, limit).map(Some)
+    }
+
+    /// Bağlantının sayaç ve hız sınırı kancası; izlenmeyen ve sınırlanmayan
+    /// yerel bağlantılar için None.
+    pub fn meter(&self, peer: SocketAddr, tunnel: Option<&Tunnel>) -> Option<Meter> {
+        let ip = normalize(peer.ip());
+        let shaped = !is_local(ip) || self.settings.read().unwrap().shaping.shape_loopback;
+        let traffic = tunnel.map(|tunnel| tunnel.traffic().clone());
+        let shaper = shaped.then(|| self.shaper.clone());
+        (traffic.is_some() || shaper.is_some()).then_some(Meter {
+            ip,
+            traffic,
+            shaper,
+        })
+    }
+
+    pub fn clients(&self) -> Vec<LanClient> {
+        let settings = self.settings.read().unwrap();
+        self.clients
+            .snapshot(|ip| settings.tunnel_limits.get(&ip).copied())
+    }
+
+    /// Cihazın açık bağlantılarını keser; bağlı değilse false döner.
+    pub fn kick(&self, ip: IpAddr) -> bool {
+        self.clients.kick(normalize(ip))
+    }
+
+    /// Bağlantı kabul edilecekse true döner; reddedilen adres aralıklı bildirilir.
+    pub fn admit(&self, peer: SocketAddr) -> bool {
+        if self.settings.read().unwrap().allow.allows(peer.ip()) {
+            return true;
+        }
+        let now = Instant::now();
+        let mut reported = self.reported.lock().unwrap();
+        reported.retain(|_, at| now.duration_since(*at) < REJECT_REPORT_INTERVAL);
+        let ip = normalize(peer.ip());
+        if let Entry::Vacant(entry) = reported.entry(ip) {
+            entry.insert(now);
+            (self.on_reject)(peer);
+        }
+        false
+    }
+}
+
+/// Aktarılan baytları cihazın sayaçlarına ekler ve hız sınırı aşıldıysa
+/// okuyan thread'i bekletir.
+#[derive(Clone)]
+pub struct Meter {
+    ip: IpAddr,
+    traffic: Option<Arc<Traffic>>,
+    shaper: Option<Arc<Shaper>>,
+}
+
+impl Meter {
+    pub fn record(&self, direction: Direction, bytes: usize) {
+        match (&self.traffic, direction) {
+            (Some(traffic), Direction::Upload) => traffic.add_up(bytes),
+            (Some(traffic), Direction::Download) => traffic.add_down(bytes),
+            (None, _) => {}
+        }
+        if let Some(shaper) = &self.shaper {
+            let wait = shaper.delay(self.ip, direction, bytes);
+            if !wait.is_zero() {
+                std::thread::sleep(wait);
+            }
+        }
+    }
+}
+
+/// Paylaşılan portu açar; motor başlamadan önce alınır.
+pub fn bind(addr: IpAddr, port: u16) -> Result<TcpListener, String> {
+    TcpListener::bind((addr, port)).map_err(|e| format!("LAN portu {port} açılamadı: {e}"))
+}
+
+/// Cihazlara gösterilen otomatik yapılandırma adresi.
+pub fn pac_url(lan_ip: &str, port: u16) -> Option<String> {
+    let endpoint = ProxyEndpoint::new(lan_ip, port).ok()?;
+    Some(format!("http://{endpoint}{}", pac::PAC_PATH))
+}
+
+/// Paylaşılan `lan_ip:port`'u gösteren PAC betiği; her başlatmada yeniden üretilir.
+pub fn pac_script(lan_ip: &str, port: u16) -> Option<String> {
+    ProxyEndpoint::new(lan_ip, port)
+        .ok()
+        .map(|endpoint| pac::generate_shared(&endpoint))
+}
+
+/// Ağ geçidinin arkasındaki motorun ayarları: yalnızca loopback, rastgele port.
+pub fn engine_config(shared: &EngineConfig) -> Result<EngineConfig, String> {
+    let port = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+        .and_then(|listener| listener.local_addr())
+        .map_err(|e| format!("Motor için port bulunamadı: {e}"))?
+        .port();
+    Ok(EngineConfig {
+        listen_addr: IpAddr::V4(Ipv4Addr::LOCALHOST),
+        listen_port: port,
+        ..shared.clone()
+    })
+}
+
+/// Çalışan ağ geçidi; bırakıldığında dinleyici kapanır. Açık bağlantılar
+/// kendi thread'lerinde bitene kadar sürer.
+pub struct Gateway {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Gateway {
+    /// `on_clients` bağlı cihazlar değiştikçe (trafik akarken de) aralıklı çağrılır.
+    /// `pac` verilmişse diğer cihazların `/proxy.pac` ve `/wpad.dat` istekleri
+    /// motora gitmeden yanıtlanır.
+    pub fn start(
+        listener: TcpListener,
+        upstream: SocketAddr,
+        gate: Arc<Gate>,
+        pac: Option<String>,
+        on_clients: impl Fn(Vec<LanClient>) + Send + 'static,
+    ) -> Result<Self, String> {
+        listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let flag = stop.clone();
+        let pac: Option<Arc<str>> = pac.map(Arc::from);
+        let handle = std::thread::spawn(move || {
+            accept_loop(listener, upstream, &gate, pac, &on_clients, &flag)
+        });
+        Ok(Self {
+            stop,
+            handle: Some(handle),
+        })
+    }
+}
+
+impl Drop for Gateway {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn accept_loop(
+    listener: TcpListener,
+    upstream: SocketAddr,
+    gate: &Gate,
+    pac: Option<Arc<str>>,
+    on_clients: &dyn Fn(Vec<LanClient>),
+    stop: &AtomicBool,
+) {
+    let mut last_report = Instant::now();
+    while !stop.load(Ordering::Relaxed) {
+        match listener.accept() {
+            // Reddedilen bağlantı hemen kapanır
+            Ok((_, peer)) if !gate.admit(peer) => {}
+            Ok((stream, peer)) => {
+                // Atılmış veya sınırına ulaşmış cihazın bağlantısı da kapanır
+                if let Ok(tunnel) = gate.track(peer, &stream) {
+                    let credentials = gate.credentials_for(peer);
+                    let meter = gate.meter(peer, tunnel.as_ref());
+                    // Yerel trafik incelenmez; betik yalnızca diğer cihazlara verilir
+                    let pac = pac.clone().filter(|_| !is_local(peer.ip()));
+                    std::thread::spawn(move || {
+                        let _ = forward(
+                            stream,
+                            upstream,
+                            credentials.as_ref(),
+                            pac.as_deref(),
+                            meter,
+                        );
+                        drop(tunnel);
+                    });
+                }
+            }
+            Err(_) => std::thread::sleep(ACCEPT_POLL),
+        }
+        if last_report.elapsed() >= CLIENTS_REPORT_INTERVAL {
+            last_report = Instant::now();
+            if gate.clients.take_changed() {
+                on_clients(gate.clients());
+            }
+        }
+    }
+}
+
+/// Betik isteği ve kimlik doğrulaması bağlantı başına bir kez, ilk istekte
+/// yapılır; aynı bağlantıdaki sonraki istekler doğrudan aktarılır. Betik
+/// kimlik doğrulamasından önce verilir, çünkü sistemler onu bilgisiz indirir.
+/// Yerel bağlantılar hiç incelenmeden aktarılır.
+fn forward(
+    mut client: TcpStream,
+    upstream: SocketAddr,
+    credentials: Option<&ProxyCredentials>,
+    pac: Option<&str>,
+    meter: Option<Meter>,
+) -> io::Result<()> {
+    client.set_nonblocking(false)?;
+    let request = if credentials.is_some() || pac.is_some() {
+        client.set_read_timeout(Some(AUTH_TIMEOUT))?;
+        let head = auth::read_head(&mut client)?;
+        if let Some(response) = pac.and_then(|script| pac::respond(&head, script)) {
+            return client.write_all(response.as_bytes());
+        }
+        match credentials {
+            Some(credentials) => match auth::authorize_http(&head, credentials) {
+                Some(request) => request,
+                None => return client.write_all(auth::PROXY_AUTH_REQUIRED),
+            },
+            None => head,
+        }
+    } else {
+        Vec::new()
+    };
+    let mut upstream = TcpStream::connect_timeout(&upstream, CONNECT_TIMEOUT)?;
+    upstream.write_all(&request)?;
+    super::socks::relay(client, upstream, meter)
+}
+
+#[tauri::command]
+pub fn list_lan_acl(app: tauri::AppHandle) -> Vec<String> {
+    use tauri::Manager;
+
+    app.state::<crate::settings::SettingsStore>()
+        .get()
+        .lan
+        .allow
+        .into()
+}
+
+#[tauri::command]
+pub fn add_lan_acl(app: tauri::AppHandle, entry: String) -> Result<Vec<String>, String> {
+    let entry = AllowEntry::parse(&entry)?;
+    let lan = update_lan(&app, |lan| {
+        lan.allow.add(entry);
+        Ok(())
+    })?;
+    Ok(lan.allow.into())
+}
+
+#[tauri::command]
+pub fn remove_lan_acl(app: tauri::AppHandle, entry: String) -> Result<Vec<String>, String> {
+    let entry = AllowEntry::parse(&entry)?;
+    let lan = update_lan(&app, |lan| {
+        lan.allow.remove(&entry);
+        Ok(())
+    })?;
+    Ok(lan.allow.into())
+}
+
+/// Arayüz, paylaşım önceki sürümde açıksa bunu yalnızca bir kez çağırır.
+#[tauri::command]
+pub fn seed_lan_acl(app: tauri::AppHandle) -> Result<Vec<String>, String> {
+    let lan_ip =
+        local_ip_address::local_ip().map_err(|e| format!("Yerel IP adresi bulunamadı: {e}"))?;
+    let lan = update_lan(&app, |lan| {
+        lan.seed_subnet(lan_ip);
+        Ok(())
+    })?;
+    Ok(lan.allow.into())
+}
+
+#[tauri::command]
+pub fn get_lan_auth(app: tauri::AppHandle) -> LanAuth {
+    use tauri::Manager;
+
+    (&app.state::<crate::settings::SettingsStore>().get().lan).into()
+}
+
+/// İlk açılışta kimlik bilgileri üretilir.
+#[tauri::command]
+pub fn set_lan_auth(app: tauri::AppHandle, enabled: bool) -> Result<LanAuth, String> {
+    let lan = update_lan(&app, |lan| {
+        if enabled && lan.credentials.is_none() {
+            lan.credentials = Some(ProxyCredentials::generate()?);
+        }
+        lan.require_auth = enabled;
+        Ok(())
+    })?;
+    Ok((&lan).into())
+}
+
+/// Yeni bilgiler sonraki bağlantılarda geçerlidir; açık bağlantılar kesilmez.
+#[tauri::command]
+pub fn rotate_lan_credentials(app: tauri::AppHandle) -> Result<LanAuth, String> {
+    let lan = update_lan(&app, |lan| {
+        lan.credentials = Some(ProxyCredentials::generate()?);
+        Ok(())
+    })?;
+    Ok((&lan).into())
+}
+
+#[tauri::command]
+pub fn list_lan_clients(app: tauri::AppHandle) -> Vec<LanClient> {
+    use tauri::Manager;
+
+    app.state::<super::EngineSupervisor>().lan_clients()
+}
+
+/// Cihazın açık bağlantılarını keser; cihaz kısa bir süre yeniden bağlanamaz.
+#[tauri::command]
+pub fn disconnect_lan_client(
+    app: tauri::AppHandle,
+    address: String,
+) -> Result<Vec<LanClient>, String> {
+    use tauri::Manager;
+
+    let ip = parse_client(&address)?;
+    let supervisor = app.state::<super::EngineSupervisor>();
+    if !supervisor.kick_lan_client(ip) {
+        return Err(format!("{ip} bağlı değil."));
+    }
+    Ok(supervisor.lan_clients())
+}
+
+/// `limit` boşsa sınır kaldırılır; açık bağlantılar kesilmez.
+#[tauri::command]
+pub fn set_lan_client_limit(
+    app: tauri::AppHandle,
+    address: String,
+    limit: Option<u32>,
+) -> Result<Vec<LanClient>, String> {
+    use tauri::Manager;
+
+    let ip = parse_client(&address)?;
+    update_lan(&app, |lan| {
+        match limit {
+            Some(limit) => lan.tunnel_limits.insert(ip, limit),
+            None => lan.tunnel_limits.remove(&ip),
+        };
+        Ok(())
+    })?;
+    Ok(app.state::<super::EngineSupervisor>().lan_clients())
+}
+
+#[tauri::command]
+pub fn get_lan_shaping(app: tauri::AppHandle) -> ShapingSettings {
+    use tauri::Manager;
+
+    app.state::<crate::settings::SettingsStore>()
+        .get()
+        .lan
+        .shaping
+}
+
+/// Sınırlar kaydedilir ve açık bağlantılara da hemen uygulanır.
+#[tauri::command]
+pub fn update_lan_shaping(
+    app: tauri::AppHandle,
+    mut shaping: ShapingSettings,
+) -> Result<ShapingSettings, String> {
+    // Çift yığınlı soketlerde görünen adresle eşleşsin
+    shaping.clients = shaping
+        .clients
+        .into_iter()
+        .map(|(ip, limit)| (normalize(ip), limit))
+        .filter(|(_, limit)| !limit.is_unlimited())
+        .collect();
+    let lan = update_lan(&app, |lan| {
+        lan.shaping = shaping;
+        Ok(())
+    })?;
+    Ok(lan.shaping)
+}
+
+fn parse_client(address: &str) -> Result<IpAddr, String> {
+    address
+        .trim()
+        .parse::<IpAddr>()
+        .map(normalize)
+        .map_err(|_| format!("Geçersiz IP adresi: {address}"))
+}
+
+/// Ayarları kaydeder ve çalışan ağ geçidine hemen uygular.
+fn update_lan(
+    app: &tauri::AppHandle,
+    change: impl FnOnce(&mut LanSettings) -> Result<(), String>,
+) -> Result<LanSettings, String> {
+    use tauri::Manager;
+
+    let store = app.state::<crate::settings::SettingsStore>();
+    let mut settings = store.get();
+    change(&mut settings.lan)?;
+    let settings = store.replace(settings)?;
+    app.state::<super::EngineSupervisor>()
+        .set_lan_settings(&settings.lan);
+    Ok(settings.lan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::shaping::RateLimit;
+    use std::io::{Read, Write};
+
+    fn list(entries: &[&str]) -> AccessList {
+        AccessList::try_from(entries.iter().map(ToString::to_string).collect::<Vec<_>>()).unwrap()
+    }
+
+    fn allow(entries: &[&str]) -> LanSettings {
+        LanSettings {
+            allow: list(entries),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_and_matches_entries() {
+        let acl = list(&["192.168.1.0/24", "10.0.0.7", "fd00::/8"]);
+        assert_eq!(
+            Vec::<String>::from(acl.clone()),
+            vec!["192.168.1.0/24", "10.0.0.7", "fd00::/8"]
+        );
+        assert!(acl.allows("192.168.1.42".parse().unwrap()));
+        assert!(acl.allows("::ffff:192.168.1.42".parse().unwrap()));
+        assert!(acl.allows("10.0.0.7".parse().unwrap()));
+        assert!(!acl.allows("10.0.0.8".parse().unwrap()));
+        assert!(acl.allows("fd12::1".parse().unwrap()));
+        assert!(!acl.allows("192.168.2.1".parse().unwrap()));
+
+        // Boş listede bile bu makinenin kendisi kabul edilir
+        let empty = AccessList::default();
+        assert!(empty.allows(Ipv4Addr::LOCALHOST.into()));
+        assert!(empty.allows(Ipv6Addr::LOCALHOST.into()));
+        assert!(!empty.allows("127.0.0.2".parse().unwrap()));
+
+        assert_eq!(
+            AllowEntry::parse("192.168.1.77/24").unwrap().to_string(),
+            "192.168.1.0/24"
+        );
+        assert!(AllowEntry::parse("192.168.1.0/33").is_err());
+        assert!(AllowEntry::parse("router.local").is_err());
+    }
+
+    #[test]
+    fn upgrade_seeds_current_subnet_once() {
+        // İzin listesinden önceki sürümün ayar dosyasında `lan` yok
+        let mut settings: crate::settings::Settings =
+            serde_json::from_str(r#"{"reconnect":{"max_attempts":3}}"#).unwrap();
+        assert!(!settings.lan.allow.allows("192.168.1.42".parse().unwrap()));
+
+        assert!(settings.lan.seed_subnet("192.168.1.20".parse().unwrap()));
+        assert_eq!(
+            Vec::<String>::from(settings.lan.allow.clone()),
+            vec!["192.168.1.0/24"]
+        );
+        assert!(settings.lan.allow.allows("192.168.1.42".parse().unwrap()));
+        assert!(!settings.lan.allow.allows("192.168.2.42".parse().unwrap()));
+
+        // Kullanıcının listesine dokunulmaz
+        let mut configured = allow(&["10.0.0.7"]);
+        assert!(!configured.seed_subnet("192.168.1.20".parse().unwrap()));
+        assert_eq!(configured, allow(&["10.0.0.7"]));
+
+        // Ağ yoksa eklenecek alt ağ da yok
+        let mut offline = LanSettings::default();
+        assert!(!offline.seed_subnet(Ipv4Addr::LOCALHOST.into()));
+        assert!(offline.seed_subnet("fd00::1:2".parse().unwrap()));
+        assert_eq!(Vec::<String>::from(offline.allow), vec!["fd00::/64"]);
+    }
+
+    #[test]
+    fn reports_each_rejected_address_once() {
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let sink = reported.clone();
+        let gate = Gate::new(allow(&["10.0.0.0/8"]), move |peer| {
+            sink.lock().unwrap().push(peer.ip())
+        });
+        let peer = |s: &str| s.parse::<SocketAddr>().unwrap();
+
+        assert!(gate.admit(peer("10.1.2.3:5000")));
+        assert!(!gate.admit(peer("172.16.0.9:5000")));
+        assert!(!gate.admit(peer("172.16.0.9:5001")));
+        assert_eq!(*reported.lock().unwrap(), vec![peer("172.16.0.9:0").ip()]);
+
+        // Kural çalışırken güncellenir
+        gate.set_settings(allow(&["172.16.0.0/12"]));
+        assert!(gate.admit(peer("172.16.0.9:5002")));
+        assert!(!gate.admit(peer("10.1.2.3:5000")));
+    }
+
+    #[test]
+    fn shapes_other_devices_and_exempts_loopback_by_default() {
+        let peer = |s: &str| s.parse::<SocketAddr>().unwrap();
+        let gate = Gate::new(LanSettings::default(), |_| {});
+        assert!(gate.meter(peer("127.0.0.1:5000"), None).is_none());
+        assert!(gate.meter(peer("[::1]:5000"), None).is_none());
+        let meter = gate
+            .meter(peer("[::ffff:192.168.1.30]:5000"), None)
+            .unwrap();
+        assert_eq!(meter.ip, "192.168.1.30".parse::<IpAddr>().unwrap());
+        assert!(meter.shaper.is_some());
+
+        gate.set_settings(LanSettings {
+            shaping: ShapingSettings {
+                shape_loopback: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        assert!(gate.meter(peer("127.0.0.1:5000"), None).is_some());
+
+        let invalid = LanSettings {
+            shaping: ShapingSettings {
+                global: RateLimit {
+                    upload: Some(0),
+                    download: None,
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    /// Belirli bir loopback adresinden bağlanır; std'de bağlanmadan önce bind yok.
+    #[cfg(target_os = "linux")]
+    fn connect_from(source: Ipv4Addr, target: SocketAddr) -> io::Result<TcpStream> {
+        use std::os::fd::FromRawFd;
+
+        let sockaddr = |ip: Ipv4Addr, port: u16| libc::sockaddr_in {
+            sin_family: libc::AF_INET as libc::sa_family_t,
+            sin_port: port.to_be(),
+            sin_addr: libc::in_addr {
+                s_addr: u32::from(ip).to_be(),
+            },
+            sin_zero: [0; 8],
+        };
+        let len = std::mem::size_of::<libc::sockaddr_in>() as libc::socklen_t;
+        let SocketAddr::V4(target) = target else {
+            return Err(io::Error::other("yalnızca IPv4"));
+        };
+        unsafe {
+            let fd = libc::socket(libc::AF_INET, libc::SOCK_STREAM, 0);
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            // Hata durumunda da soket kapansın
+            let stream = TcpStream::from_raw_fd(fd);
+            let src = sockaddr(source, 0);
+            if libc::bind(fd, (&src as *const libc::sockaddr_in).cast(), len) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let dst = sockaddr(*target.ip(), target.port());
+            if libc::connect(fd, (&dst as *const libc::sockaddr_in).cast(), len) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(stream)
+        }
+    }
+
+    /// Yankı yapan sahte motorun önünde ağ geçidi başlatır. Betik verilirse
+    /// diğer cihazların ilk isteği HTTP olmalıdır.
+    fn echo_gateway(gate: Arc<Gate>, serve_pac: bool) -> (Gateway, SocketAddr) {
+        let engine = TcpListener::bind("127.0.0.1:0").unwrap();
+        let upstream = engine.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for stream in engine.incoming().flatten() {
+                std::thread::spawn(move || {
+                    let _ = io::copy(&mut stream.try_clone().unwrap(), &mut &stream);
+                });
+            }
+        });
+        let listener = TcpListener::bind("0.0.0.0:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let pac = pac_script("127.0.0.1", port).filter(|_| serve_pac);
+        let gateway = Gateway::start(listener, upstream, gate, pac, |_| {}).unwrap();
+        (gateway, SocketAddr::from((Ipv4Addr::LOCALHOST, port)))
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn gateway_enforces_rules_per_source_address() {
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let sink = reported.clone();
+        let gate = Arc::new(Gate::new(allow(&["127.0.0.2"]), move |peer| {
+            sink.lock().unwrap().push(peer.ip())
+        }));
+        let (_gateway, target) = echo_gateway(gate.clone(), false);
+
+        let echo = |source: Ipv4Addr| -> io::Result<Vec<u8>> {
+            let mut stream = connect_from(source, target)?;
+            stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+            stream.write_all(b"ping")?;
+            let mut buf = [0u8; 4];
+            let n = stream.read(&mut buf)?;
+            Ok(buf[..n].to_vec())
+        };
+
+        assert_eq!(echo(Ipv4Addr::LOCALHOST).unwrap(), b"ping");
+        assert_eq!(echo(Ipv4Addr::new(127, 0, 0, 2)).unwrap(), b"ping");
+        // Reddedilen istemcinin bağlantısı veri almadan kapanır
+        assert!(echo(Ipv4Addr::new(127, 0, 0, 3)).map_or(true, |data| data.is_empty()));
+        assert_eq!(
+            *reported.lock().unwrap(),
+            vec![IpAddr::V4(Ipv4Addr::new(127, 0, 0, 3))]
+        );
+
+        gate.set_settings(allow(&["127.0.0.3"]));
+        assert_eq!(echo(Ipv4Addr::new(127, 0, 0, 3)).unwrap(), b"ping");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn gateway_requires_credentials_from_other_devices() {
+        use base64::Engine;
+
+        let credentials = ProxyCredentials {
+            username: "vexar-abc".to_string(),
+            password: "s3cret".to_string(),
+        };
+        let settings = LanSettings {
+            require_auth: true,
+            credentials: Some(credentials),
+            ..allow(&["127.0.0.2"])
+        };
+        assert!(settings.validate().is_ok());
+        let (_gateway, target) = echo_gateway(Arc::new(Gate::new(settings, |_| {})), false);
+        let send = |source: Ipv4Addr, request: &str| -> Vec<u8> {
+            let mut stream = connect_from(source, target).unwrap();
+            stream
+                .set_read_timeout(Some(Duration::from_secs(2)))
+                .unwrap();
+            stream.write_all(request.as_bytes()).unwrap();
+            let mut reply = vec![0u8; request.len()];
+            let n = stream.read(&mut reply).unwrap();
+            reply.truncate(n);
+            reply
+        };
+
+        // Bu makinenin trafiği incelenmeden aktarılır
+        assert_eq!(send(Ipv4Addr::LOCALHOST, "ping"), b"ping");
+        let rejected = send(
+            Ipv4Addr::new(127, 0, 0, 2),
+            "CONNECT example.com:443 HTTP/1.1\r\n\r\n",
+        );
+        assert!(rejected.starts_with(b"HTTP/1.1 407"));
+
+        let token = base64::engine::general_purpose::STANDARD.encode("vexar-abc:s3cret");
+        let accepted = send(
+            Ipv4Addr::new(127, 0, 0, 2),
+            &format!(
+                "CONNECT example.com:443 HTTP/1.1\r\nProxy-Authorization: Basic {token}\r\n\r\n"
+            ),
+        );
+        assert_eq!(accepted, b"CONNECT example.com:443 HTTP/1.1\r\n\r\n");
+
+        let missing = LanSettings {
+            require_auth: true,
+            ..Default::default()
+        };
+        assert!(missing.validate().is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn gateway_serves_pac_before_authentication() {
+        let settings = LanSettings {
+            require_auth: true,
+            credentials: Some(ProxyCredentials {
+                username: "vexar-abc".to_string(),
+                password: "s3cret".to_string(),
+            }),
+            ..allow(&["127.0.0.2"])
+        };
+        let (_gateway, target) = echo_gateway(Arc::new(Gate::new(settings, |_| {})), true);
+        let fetch = |request: &str| -> String {
+            let mut stream = connect_from(Ipv4Addr::new(127, 0, 0, 2), target).unwrap();
+            stream
+                .set_read_timeout(Some(Duration::from_secs(2)))
+                .unwrap();
+            stream.write_all(request.as_bytes()).unwrap();
+            let mut response = String::new();
+            let _ = stream.read_to_string(&mut response);
+            response
+        };
+
+        for path in ["/proxy.pac", "/wpad.dat"] {
+            let response = fetch(&format!("GET {path} HTTP/1.1\r\nHost: wpad\r\n\r\n"));
+            assert!(response.starts_with("HTTP/1.1 200 OK"), "{response}");
+            assert!(response.contains(&format!("PROXY 127.0.0.1:{}; DIRECT", target.port())));
+        }
+        // Proxy istekleri yine kimlik doğrulamasından geçer
+        assert!(fetch("GET http://a/proxy.pac HTTP/1.1\r\n\r\n").starts_with("HTTP/1.1 407"));
+        assert_eq!(
+            pac_url("192.168.1.10", 8080).as_deref(),
+            Some("http://192.168.1.10:8080/proxy.pac")
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn gateway_tracks_limits_and_kicks_clients() {
+        let phone = Ipv4Addr::new(127, 0, 0, 2);
+        let settings = LanSettings {
+            tunnel_limits: BTreeMap::from([(IpAddr::V4(phone), 1)]),
+            ..allow(&["127.0.0.2"])
+        };
+        let gate = Arc::new(Gate::new(settings, |_| {}));
+        let (_gateway, target) = echo_gateway(gate.clone(), false);
+
+        let mut first = connect_from(phone, target).unwrap();
+        first
+            .set_read_timeout(Some(Duration::from_secs(2)))
+            .unwrap();
+        first.write_all(b"ping").unwrap();
+        first.read_exact(&mut [0u8; 4]).unwrap();
+
+        // Sınırdaki cihazın yeni bağlantısı veri almadan kapanır
+        let mut second = connect_from(phone, target).unwrap();
+        second
+            .set_read_timeout(Some(Duration::from_secs(2)))
+            .unwrap();
+        let _ = second.write_all(b"ping");
+        assert!(second.read(&mut [0u8; 4]).map_or(true, |n| n == 0));
+
+        // Yerel bağlantılar listede görünmez
+        let mut local = connect_from(Ipv4Addr::LOCALHOST, target).unwrap();
+        local.write_all(b"ping").unwrap();
+
+        let clients = gate.clients();
+        assert_eq!(clients.len(), 1);
+        assert_eq!(clients[0].address, "127.0.0.2");
+        assert_eq!(clients[0].active_tunnels, 1);
+        assert_eq!(clients[0].tunnel_limit, Some(1));
+        assert_eq!((clients[0].bytes_up, clients[0].bytes_down), (4, 4));
+
+        assert!(gate.kick(IpAddr::V4(phone)));
+        assert_eq!(first.read(&mut [0u8; 4]).unwrap(), 0);
+        assert!(gate.clients()[0].blocked);
+    }
+}
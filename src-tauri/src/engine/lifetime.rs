@@ -0,0 +1,443 @@
+//! Motor sürecinin uygulamadan uzun yaşamamasını sağlar.
+//!
+//! `run()` içindeki çıkış katmanları uygulama öldürüldüğünde veya
+//! `panic = "abort"` ile çöktüğünde çalışmaz. Bu yüzden süreç ebeveynin
+//! ömrüne işletim sistemi seviyesinde bağlanır (Linux'ta parent-death
+//! sinyali, Windows'ta kapanınca öldüren job object) ve açılışta önceki
+//! oturumlardan kalan motorlar temizlenir.
+
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus};
+use std::time::{Duration, Instant};
+
+/// Kibar durdurma isteğinden sonra zorla sonlandırmadan önce beklenecek süre.
+pub const GRACEFUL_STOP_TIMEOUT: Duration = Duration::from_secs(3);
+
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Motoru ebeveynin ömrüne bağlı olarak başlatır.
+pub fn spawn(command: Command) -> std::io::Result<Child> {
+    #[cfg(target_os = "linux")]
+    {
+        linux::spawn(command)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let mut command = command;
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            command.creation_flags(windows::CREATION_FLAGS);
+        }
+        let child = command.spawn()?;
+        #[cfg(windows)]
+        windows::attach(&child);
+        Ok(child)
+    }
+}
+
+/// Önce kibarca durdurmayı dener, `timeout` içinde kapanmazsa zorla sonlandırır.
+pub fn terminate(child: &mut Child, timeout: Duration) -> Option<ExitStatus> {
+    if let Ok(Some(status)) = child.try_wait() {
+        return Some(status);
+    }
+    if request_stop(child.id()) {
+        let started = Instant::now();
+        while started.elapsed() < timeout {
+            match child.try_wait() {
+                Ok(Some(status)) => return Some(status),
+                Ok(None) => std::thread::sleep(EXIT_POLL_INTERVAL),
+                Err(_) => break,
+            }
+        }
+    }
+    let _ = child.kill();
+    child.wait().ok()
+}
+
+/// Süreçten kapanmasını ister; istek gönderilemediyse false döner.
+fn request_stop(pid: u32) -> bool {
+    #[cfg(unix)]
+    {
+        // SAFETY: kill(2) yalnızca sinyal gönderir, bellek erişimi yoktur
+        unsafe { libc::kill(pid as libc::pid_t, libc::SIGTERM) == 0 }
+    }
+    #[cfg(windows)]
+    {
+        windows::request_stop(pid)
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = pid;
+        false
+    }
+}
+
+/// Bu paketteki sidecar'ın yolu; Tauri sidecar'ları ana programın yanına koyar.
+pub fn sidecar_path(name: &str) -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    let file = if cfg!(windows) {
+        format!("{name}.exe")
+    } else {
+        name.to_string()
+    };
+    Some(exe.parent()?.join(file))
+}
+
+/// Önceki oturumlardan kalan motor süreçlerini sonlandırır ve PID'lerini döndürür.
+///
+/// Yalnızca `sidecar` yolundan çalışan süreçler adaydır; kullanıcının başka
+/// bir yerden başlattığı aynı adlı motora dokunulmaz. Ebeveyni hâlâ çalışan
+/// başka bir Vexar olan süreçler de bırakılır.
+pub fn sweep_stale(sidecar: &Path) -> Vec<u32> {
+    use sysinfo::{Pid, System};
+
+    let mut sys = System::new();
+    sys.refresh_processes();
+
+    let sidecar_name = sidecar
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let own_pid = std::process::id();
+    let app_name = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.file_name().map(|n| n.to_string_lossy().into_owned()))
+        .unwrap_or_default();
+
+    let stale: Vec<Pid> = sys
+        .processes()
+        .iter()
+        .filter(|(_, process)| {
+            same_program(process.name(), &sidecar_name) && same_executable(process.exe(), sidecar)
+        })
+        .filter(|(_, process)| {
+            let parent = process
+                .parent()
+                .and_then(|pid| sys.process(pid).map(|p| (pid.as_u32(), p.name())));
+            is_stale(parent, own_pid, &app_name)
+        })
+        .map(|(pid, _)| *pid)
+        .collect();
+
+    if stale.is_empty() {
+        return Vec::new();
+    }
+
+    // İstek ulaşmayan süreç için beklemenin anlamı yok; hemen zorla sonlandırılır
+    let (asked, unreachable): (Vec<Pid>, Vec<Pid>) =
+        stale.iter().partition(|pid| request_stop(pid.as_u32()));
+    for pid in unreachable {
+        if let Some(process) = sys.process(pid) {
+            process.kill();
+        }
+    }
+
+    let started = Instant::now();
+    loop {
+        sys.refresh_processes();
+        let alive: Vec<Pid> = asked
+            .iter()
+            .copied()
+            .filter(|pid| sys.process(*pid).is_some())
+            .collect();
+        if alive.is_empty() {
+            break;
+        }
+        if started.elapsed() >= GRACEFUL_STOP_TIMEOUT {
+            for pid in alive {
+                if let Some(process) = sys.process(pid) {
+                    process.kill();
+                }
+            }
+            break;
+        }
+        std::thread::sleep(EXIT_POLL_INTERVAL * 2);
+    }
+
+    stale.iter().map(|pid| pid.as_u32()).collect()
+}
+
+/// Motorun ebeveyni yoksa ya da artık bir Vexar süreci değilse (init'e veya
+/// başka bir sürece devredildiyse) süreç önceki bir oturumdan kalmıştır.
+fn is_stale(parent: Option<(u32, &str)>, own_pid: u32, app_name: &str) -> bool {
+    match parent {
+        None => true,
+        Some((pid, _)) if pid == own_pid => false,
+        Some((_, name)) => !same_program(name, app_name),
+    }
+}
+
+/// Süreç adlarını uzantı ve büyük/küçük harf farkı gözetmeden karşılaştırır.
+fn same_program(process_name: &str, program: &str) -> bool {
+    fn stem(name: &str) -> &str {
+        name.strip_suffix(".exe")
+            .or_else(|| name.strip_suffix(".EXE"))
+            .unwrap_or(name)
+    }
+    let program = stem(program);
+    !program.is_empty() && stem(process_name).eq_ignore_ascii_case(program)
+}
+
+/// Yolu okunamayan süreç başka bir kullanıcıya ait olabilir; ona da dokunulmaz.
+fn same_executable(exe: Option<&Path>, sidecar: &Path) -> bool {
+    let Some(exe) = exe else {
+        return false;
+    };
+    // Linux'ta güncellemeyle silinen eski dosya " (deleted)" ekiyle görünür
+    let exe = exe
+        .to_str()
+        .and_then(|path| path.strip_suffix(" (deleted)"))
+        .map_or(exe, Path::new);
+    let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let (exe, sidecar) = (canonical(exe), canonical(sidecar));
+    if cfg!(windows) {
+        exe.to_string_lossy()
+            .eq_ignore_ascii_case(&sidecar.to_string_lossy())
+    } else {
+        exe == sidecar
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::os::unix::process::CommandExt;
+    use std::process::{Child, Command};
+    use std::sync::mpsc::{self, Sender};
+    use std::sync::{Mutex, OnceLock};
+
+    type Request = (Command, Sender<std::io::Result<Child>>);
+
+    /// PR_SET_PDEATHSIG süreci değil, fork eden thread'i izler. Yeniden
+    /// bağlanma thread'leri kısa ömürlü olduğundan spawn'lar uygulama
+    /// boyunca yaşayan tek bir thread'den yapılır.
+    static SPAWNER: OnceLock<Mutex<Sender<Request>>> = OnceLock::new();
+
+    pub fn spawn(mut command: Command) -> std::io::Result<Child> {
+        let parent = std::process::id() as libc::pid_t;
+        // SAFETY: closure fork sonrası yalnızca async-signal-safe çağrılar yapar
+        unsafe {
+            command.pre_exec(move || {
+                if libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGTERM) == -1 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                // Ebeveyn fork ile prctl arasında öldüyse sinyal hiç gelmez
+                if libc::getppid() != parent {
+                    return Err(std::io::Error::other("ebeveyn süreç sonlandı"));
+                }
+                Ok(())
+            });
+        }
+
+        let sender = SPAWNER
+            .get_or_init(|| {
+                let (tx, rx) = mpsc::channel::<Request>();
+                std::thread::spawn(move || {
+                    for (mut command, reply) in rx {
+                        let _ = reply.send(command.spawn());
+                    }
+                });
+                Mutex::new(tx)
+            })
+            .lock()
+            .unwrap()
+            .clone();
+
+        let (reply, result) = mpsc::channel();
+        sender
+            .send((command, reply))
+            .map_err(|_| std::io::Error::other("spawn thread'i kapandı"))?;
+        result
+            .recv()
+            .map_err(|_| std::io::Error::other("spawn thread'i kapandı"))?
+    }
+}
+
+#[cfg(windows)]
+mod windows {
+    use std::os::windows::io::AsRawHandle;
+    use std::process::Child;
+    use std::sync::{Mutex, OnceLock};
+
+    use winapi::um::jobapi2::{
+        AssignProcessToJobObject, CreateJobObjectW, SetInformationJobObject,
+    };
+    use winapi::um::winbase::{CREATE_NEW_PROCESS_GROUP, CREATE_NO_WINDOW};
+    use winapi::um::wincon::{
+        AttachConsole, CTRL_BREAK_EVENT, FreeConsole, GenerateConsoleCtrlEvent,
+    };
+    use winapi::um::winnt::{
+        HANDLE, JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE, JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+        JobObjectExtendedLimitInformation,
+    };
+
+    struct Job(HANDLE);
+    // SAFETY: job handle'ı yalnızca çekirdek nesnesine referanstır, thread'e bağlı değildir
+    unsafe impl Send for Job {}
+    unsafe impl Sync for Job {}
+
+    /// Uygulama boyunca açık kalan job; handle süreçle birlikte kapanınca
+    /// içindeki tüm motorlar işletim sistemi tarafından sonlandırılır.
+    static JOB: OnceLock<Option<Job>> = OnceLock::new();
+
+    fn job() -> Option<&'static Job> {
+        JOB.get_or_init(|| unsafe {
+            let handle = CreateJobObjectW(std::ptr::null_mut(), std::ptr::null());
+            if handle.is_null() {
+                return None;
+            }
+            let mut info: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = std::mem::zeroed();
+            info.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+            let ok = SetInformationJobObject(
+                handle,
+                JobObjectExtendedLimitInformation,
+                &mut info as *mut _ as *mut _,
+                std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+            );
+            (ok != 0).then_some(Job(handle))
+        })
+        .as_ref()
+    }
+
+    /// Motor kendi süreç grubunda başlar ki Ctrl+Break yalnızca ona gönderilebilsin.
+    pub const CREATION_FLAGS: u32 = CREATE_NO_WINDOW | CREATE_NEW_PROCESS_GROUP;
+
+    /// Penceresiz motora kapanma isteği ancak konsolu üzerinden gönderilebilir:
+    /// konsoluna bağlanılır ve süreç grubuna Ctrl+Break gönderilir; Go çalışma
+    /// zamanı bunu `os.Interrupt` olarak alır. Uygulamanın kendi konsolu varsa
+    /// (geliştirme derlemesi) bağlanılamaz ve false döner.
+    pub fn request_stop(pid: u32) -> bool {
+        // Konsol süreç başına tektir; aynı anda iki motora bağlanılmasın
+        static CONSOLE: Mutex<()> = Mutex::new(());
+        let _guard = CONSOLE.lock().unwrap();
+        // SAFETY: yalnızca konsol bağlantısı değişir; her başarılı AttachConsole'dan sonra FreeConsole çağrılır
+        unsafe {
+            if AttachConsole(pid) == 0 {
+                return false;
+            }
+            // Grup kimliği grubu başlatan sürecin PID'idir; bu süreç o grupta değildir
+            let sent = GenerateConsoleCtrlEvent(CTRL_BREAK_EVENT, pid) != 0;
+            FreeConsole();
+            sent
+        }
+    }
+
+    pub fn attach(child: &Child) {
+        if let Some(job) = job() {
+            // SAFETY: handle'lar geçerli; başarısızlıkta açılış taraması yedek olarak kalır
+            unsafe {
+                AssignProcessToJobObject(job.0, child.as_raw_handle() as HANDLE);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orphaned_engine_is_stale() {
+        assert!(is_stale(None, 100, "vexar"));
+        // Linux'ta init'e, Windows'ta başka bir sürece devredilmiş
+        assert!(is_stale(Some((1, "systemd")), 100, "vexar"));
+        assert!(is_stale(Some((4321, "explorer.exe")), 100, "vexar.exe"));
+    }
+
+    #[test]
+    fn engines_of_running_instances_are_kept() {
+        assert!(!is_stale(Some((100, "vexar")), 100, "vexar"));
+        assert!(!is_stale(Some((200, "Vexar.exe")), 100, "vexar.exe"));
+    }
+
+    #[test]
+    fn matches_program_names() {
+        assert!(same_program("vexar-proxy", "vexar-proxy"));
+        assert!(same_program("vexar-proxy.exe", "vexar-proxy"));
+        assert!(same_program("VEXAR-PROXY.EXE", "vexar-proxy"));
+        assert!(!same_program("vexar-proxy-helper", "vexar-proxy"));
+        assert!(!same_program("vexar", ""));
+    }
+
+    #[test]
+    fn matches_only_the_bundled_executable() {
+        let sidecar = Path::new("/opt/vexar/vexar-proxy");
+        assert!(same_executable(Some(sidecar), sidecar));
+        assert!(same_executable(
+            Some(Path::new("/opt/vexar/vexar-proxy (deleted)")),
+            sidecar
+        ));
+        assert!(!same_executable(
+            Some(Path::new("/home/user/bin/vexar-proxy")),
+            sidecar
+        ));
+        assert!(!same_executable(None, sidecar));
+    }
+
+    /// Aynı adlı iki sahipsiz süreçten yalnızca paketteki yoldan çalışan sonlandırılır.
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn sweep_leaves_same_named_process_from_other_path() {
+        let root = std::env::temp_dir().join(format!("vexar-sweep-{}", std::process::id()));
+        let name = "vexar-sweep";
+        let start_orphan = |dir: &str| -> (PathBuf, u32) {
+            let dir = root.join(dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            let exe = dir.join(name);
+            std::fs::copy("/bin/sleep", &exe).unwrap();
+            // Kabuk hemen çıkar; süreç init'e devredilir
+            let output = Command::new("sh")
+                .arg("-c")
+                .arg(format!("'{}' 30 >/dev/null 2>&1 & echo $!", exe.display()))
+                .output()
+                .unwrap();
+            let pid = String::from_utf8_lossy(&output.stdout)
+                .trim()
+                .parse()
+                .unwrap();
+            (exe, pid)
+        };
+        let (bundled, stale) = start_orphan("bundle");
+        let (_, foreign) = start_orphan("elsewhere");
+        let alive = |pid: u32| unsafe { libc::kill(pid as libc::pid_t, 0) == 0 };
+
+        let swept = sweep_stale(&bundled);
+        assert_eq!(swept, vec![stale]);
+        assert!(alive(foreign));
+
+        unsafe { libc::kill(foreign as libc::pid_t, libc::SIGKILL) };
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn terminate_stops_gracefully() {
+        let mut child = Command::new("sleep").arg("30").spawn().unwrap();
+        let started = Instant::now();
+        let status = terminate(&mut child, Duration::from_secs(2)).unwrap();
+        assert!(started.elapsed() < Duration::from_secs(2));
+        use std::os::unix::process::ExitStatusExt;
+        assert_eq!(status.signal(), Some(libc::SIGTERM));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn terminate_force_kills_after_timeout() {
+        let mut child = Command::new("sh")
+            .args(["-c", "trap '' TERM; exec sleep 30"])
+            .spawn()
+            .unwrap();
+        // Kabuğun trap'i kurması için kısa bir süre tanı
+        std::thread::sleep(Duration::from_millis(200));
+        let status = terminate(&mut child, Duration::from_millis(300)).unwrap();
+        use std::os::unix::process::ExitStatusExt;
+        assert_eq!(status.signal(), Some(libc::SIGKILL));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn spawned_child_is_bound_to_parent() {
+        let mut child = spawn(Command::new("true")).unwrap();
+        assert!(child.wait().unwrap().success());
+    }
+}
@@ -0,0 +1,1071 @@
+//! vexar-proxy sidecar'ının sahibi olan supervisor.
+//!
+//! Motorun süreci, seçilen port/bind adresi ve bağlantı durum makinesi
+//! burada tutulur; tray, `run()` içindeki çıkış katmanları ve arayüz aynı
+//! kaynaktan okur. Arayüz yeniden yüklense bile motor çalışmaya devam eder.
+
+pub mod auth;
+pub mod clients;
+pub mod config;
+pub mod heartbeat;
+pub mod lan;
+pub mod lifetime;
+pub mod pause;
+pub mod readiness;
+pub mod reconnect;
+pub mod remote;
+pub mod shaping;
+pub mod socks;
+
+use std::io::{BufRead, BufReader, Read};
+use std::net::SocketAddr;
+use std::process::Child;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_shell::ShellExt;
+
+use config::EngineConfig;
+use heartbeat::{HeartbeatGuard, Transition};
+use readiness::{ProbeFailure, ReadinessReport};
+use reconnect::{Decision, ReconnectTracker};
+use remote::{DiscoveredEngine, RemoteConfig};
+
+use crate::proxy::drift::{DriftAction, DriftChange, DriftWatch};
+use crate::proxy::endpoint::ProxyEndpoint;
+use crate::proxy::servers::{ProxyServers, SchemeLayout};
+use crate::proxy::{ProxyController, ProxyMode};
+use crate::settings::SettingsStore;
+
+/// Durum değişikliklerinin yayınlandığı event.
+pub const STATE_EVENT: &str = "engine://state";
+/// Motorun stdout/stderr satırlarının yayınlandığı event.
+pub const LOG_EVENT: &str = "engine://log";
+/// Hazırlık kontrolünün sonucunun yayınlandığı event.
+pub const READY_EVENT: &str = "engine://ready";
+/// Çökme döngüsü kesicisi açıldığında yayınlanan event.
+pub const BREAKER_EVENT: &str = "engine://breaker";
+/// Heartbeat sistem proxy'sini kapattığında veya yeniden açtığında yayınlanan event.
+pub const HEARTBEAT_EVENT: &str = "engine://heartbeat";
+/// Sistem proxy'si bağlıyken başka bir araç tarafından değiştirildiğinde yayınlanan event.
+pub const DRIFT_EVENT: &str = "engine://drift";
+
+const SIDECAR_NAME: &str = "vexar-proxy";
+const MONITOR_INTERVAL: Duration = Duration::from_millis(250);
+const READY_DEADLINE: Duration = Duration::from_secs(10);
+const READY_INTERVAL: Duration = Duration::from_millis(200);
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum EngineState {
+    Idle,
+    Starting,
+    Ready,
+    /// Motor çalışıyor ama heartbeat'e yanıt vermiyor; sistem proxy'si kapatıldı.
+    Degraded {
+        failures: u32,
+        reason: String,
+    },
+    /// Motor çalışıyor ama sistem proxy'si kullanıcı isteğiyle geçici olarak kapalı.
+    Paused {
+        resume_at_ms: u64,
+    },
+    Reconnecting {
+        attempt: u32,
+        max_attempts: u32,
+        delay_ms: u64,
+    },
+    Failed {
+        reason: String,
+    },
+    Stopping,
+}
+
+impl EngineState {
+    fn is_active(&self) -> bool {
+        matches!(
+            self,
+            EngineState::Starting
+                | EngineState::Ready
+                | EngineState::Degraded { .. }
+                | EngineState::Paused { .. }
+                | EngineState::Reconnecting { .. }
+        )
+    }
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct EngineStatus {
+    state: EngineState,
+    port: Option<u16>,
+    bind_address: Option<String>,
+    lan_ip: Option<String>,
+    /// LAN paylaşımında cihazların otomatik yapılandırma adresi.
+    pac_url: Option<String>,
+    pid: Option<u32>,
+    /// İstemci modunda bağlanılan uzak Vexar (`host:port`).
+    remote: Option<String>,
+    socks_port: Option<u16>,
+    readiness: Option<ReadinessReport>,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct BreakerTrip {
+    crashes: u32,
+    window_secs: u64,
+    reason: String,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct HeartbeatReport {
+    healthy: bool,
+    failures: u32,
+    reason: Option<String>,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct DriftReport {
+    action: DriftAction,
+    changes: Vec<DriftChange>,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct EngineLogLine {
+    line: String,
+    stream: &'static str,
+}
+
+/// Sistem proxy'sinin yönlendirileceği hedef; kilit altında hesaplanır,
+/// kilit bırakıldıktan sonra uygulanır.
+struct ProxyTarget {
+    servers: ProxyServers,
+    mode: ProxyMode,
+}
+
+impl ProxyTarget {
+    /// Sistem proxy'si ve araç entegrasyonları.
+    fn apply(&self, app: &AppHandle) -> Result<(), String> {
+        crate::set_system_proxy(app.clone(), self.servers.to_string(), Some(self.mode))
+    }
+
+    /// Yalnızca sistem proxy'si; duraklatmadan dönerken entegrasyonlar zaten uygulanmış.
+    fn apply_system(&self, app: &AppHandle) -> Result<(), String> {
+        crate::enable_system_proxy(app, &self.servers, self.mode)
+    }
+}
+
+/// İstemci modundaki oturum.
+struct RemoteSession {
+    endpoint: ProxyEndpoint,
+    mode: ProxyMode,
+    layout: SchemeLayout,
+}
+
+struct Inner {
+    state: EngineState,
+    child: Option<Child>,
+    port: Option<u16>,
+    bind_address: Option<String>,
+    lan_ip: Option<String>,
+    config: Option<EngineConfig>,
+    /// İstemci modunda dolu; yerel motor başlatıldığında temizlenir.
+    remote: Option<RemoteSession>,
+    /// Motor süreciyle birlikte yaşar; bırakıldığında dinleyici kapanır.
+    socks: Option<socks::Bridge>,
+    /// LAN paylaşımında paylaşılan portu dinler; motorla birlikte yaşar.
+    gateway: Option<lan::Gateway>,
+    /// Ağ geçidi ve SOCKS köprüsünün ortak izin listesi ve hız sınırları.
+    gate: Option<Arc<lan::Gate>>,
+    tracker: ReconnectTracker,
+    readiness: Option<ReadinessReport>,
+    /// Her spawn'da artar; eski oturumlara ait thread'ler kendini bununla fark eder.
+    generation: u64,
+}
+
+impl Inner {
+    fn status(&self) -> EngineStatus {
+        EngineStatus {
+            state: self.state.clone(),
+            port: self.port,
+            bind_address: self.bind_address.clone(),
+            lan_ip: self.lan_ip.clone(),
+            pac_url: self
+                .gateway
+                .as_ref()
+                .and(self.lan_ip.as_deref().zip(self.port))
+                .and_then(|(lan_ip, port)| lan::pac_url(lan_ip, port)),
+            pid: self.child.as_ref().map(|c| c.id()),
+            remote: self.remote.as_ref().map(|r| r.endpoint.to_string()),
+            socks_port: self.socks.as_ref().map(socks::Bridge::port),
+            readiness: self.readiness.clone(),
+        }
+    }
+
+    /// Motorla birlikte yaşayan dinleyicileri kapatır.
+    fn close_listeners(&mut self) {
+        self.socks = None;
+        self.gateway = None;
+        self.gate = None;
+    }
+
+    /// Oturumun HTTP proxy adresi: yerel motor veya uzak Vexar.
+    fn http_endpoint(&self) -> Result<ProxyEndpoint, String> {
+        match &self.remote {
+            Some(remote) => Ok(remote.endpoint.clone()),
+            None => self
+                .port
+                .map(ProxyEndpoint::local)
+                .ok_or_else(|| "Motor portu bulunamadı.".to_string()),
+        }
+    }
+
+    /// Bu oturumun proxy hedefi: yerel motor veya uzak Vexar.
+    fn proxy_target(&self) -> Result<ProxyTarget, String> {
+        let http = self.http_endpoint()?;
+        let (layout, mode) = match &self.remote {
+            Some(remote) => (remote.layout, remote.mode),
+            None => {
+                let config = self.config.as_ref();
+                (
+                    config.map(|c| c.scheme_layout).unwrap_or_default(),
+                    config.map(|c| c.proxy_mode).unwrap_or_default(),
+                )
+            }
+        };
+        let socks = self
+            .socks
+            .as_ref()
+            .map(|bridge| ProxyEndpoint::local(bridge.port()));
+        let servers = layout.servers(&http, socks.as_ref())?;
+        Ok(ProxyTarget { servers, mode })
+    }
+}
+
+/// Managed state olarak tutulan motor supervisor'ı.
+pub struct EngineSupervisor {
+    inner: Mutex<Inner>,
+    /// Sistem proxy'sine dokunan işlemleri sıraya koyar. `inner`'dan önce alınır;
+    /// registry ve dosya yazılırken `inner` tutulmaz ki durum sorguları beklemesin.
+    proxy: Mutex<()>,
+}
+
+impl Default for EngineSupervisor {
+    fn default() -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                state: EngineState::Idle,
+                child: None,
+                port: None,
+                bind_address: None,
+                lan_ip: None,
+                config: None,
+                remote: None,
+                socks: None,
+                gateway: None,
+                gate: None,
+                tracker: ReconnectTracker::new(Default::default()),
+                readiness: None,
+                generation: 0,
+            }),
+            proxy: Mutex::new(()),
+        }
+    }
+}
+
+impl EngineSupervisor {
+    pub fn status(&self) -> EngineStatus {
+        self.inner.lock().unwrap().status()
+    }
+
+    /// Uygulamaların doğrudan yönlendirilebileceği HTTP proxy. Duraklatılmışken
+    /// de motor çalıştığı için adres döner; hazır değilse hata verir.
+    pub fn http_endpoint(&self) -> Result<ProxyEndpoint, String> {
+        let inner = self.inner.lock().unwrap();
+        match inner.state {
+            EngineState::Ready | EngineState::Paused { .. } => inner.http_endpoint(),
+            _ => Err("Vexar bağlı değil; önce bağlanın.".to_string()),
+        }
+    }
+
+    pub fn start(&self, app: &AppHandle, config: EngineConfig) -> Result<EngineStatus, String> {
+        config.validate()?;
+        let policy = app.state::<SettingsStore>().get().reconnect;
+        let mut inner = self.inner.lock().unwrap();
+        if inner.state.is_active() {
+            return Ok(inner.status());
+        }
+        inner.config = Some(config);
+        inner.remote = None;
+        // Kullanıcı elle başlattığında çökme geçmişi de sıfırlanır
+        inner.tracker = ReconnectTracker::new(policy);
+        let result = self.spawn_locked(app, &mut inner);
+        let status = inner.status();
+        drop(inner);
+        emit_status(app, &status);
+        result.map(|_| status)
+    }
+
+    /// Yerel motor başlatmadan sistem proxy'sini uzak bir Vexar'a yönlendirir.
+    /// Proxy, yerel motordaki gibi yalnızca uzak adres CONNECT probe'una
+    /// geçerli yanıt verdiyse açılır; heartbeat ve sapma izleme de aynen sürer.
+    pub fn connect_remote(
+        &self,
+        app: &AppHandle,
+        remote: RemoteConfig,
+    ) -> Result<EngineStatus, String> {
+        let endpoint = remote.endpoint()?;
+        let probe_addr = endpoint.resolve()?;
+        let mut inner = self.inner.lock().unwrap();
+        if inner.state.is_active() {
+            return Err("Önce mevcut bağlantıyı kesin.".to_string());
+        }
+        inner.generation += 1;
+        let generation = inner.generation;
+        inner.port = Some(endpoint.port());
+        inner.bind_address = None;
+        inner.lan_ip = None;
+        inner.readiness = None;
+        inner.remote = Some(RemoteSession {
+            endpoint,
+            mode: remote.proxy_mode,
+            layout: remote.scheme_layout,
+        });
+        inner.state = EngineState::Starting;
+        let status = inner.status();
+        drop(inner);
+        emit_status(app, &status);
+
+        spawn_readiness_check(app.clone(), generation, probe_addr, remote.probe_target);
+        Ok(status)
+    }
+
+    /// Motoru durdurur ve sistem proxy'sini temizler.
+    pub fn stop(&self, app: &AppHandle) -> EngineStatus {
+        self.stop_with(app, true)
+    }
+
+    /// Sistem proxy'si başka bir araca geçtiyse bağlantıyı ona dokunmadan keser.
+    fn disconnect_after_drift(&self, app: &AppHandle, generation: u64) {
+        if !self.is_connected(generation) {
+            return;
+        }
+        let _ = app.state::<ProxyController>().release();
+        // Araç dosyaları sistem proxy'sinden bağımsız; yine de geri alınır
+        crate::restore_integrations(app);
+        self.stop_with(app, false);
+    }
+
+    /// Motoru çalışır bırakıp bağlantı öncesi proxy ayarlarını geri yükler;
+    /// süre dolunca veya `resume` ile Vexar proxy'si yeniden uygulanır.
+    pub fn pause(&self, app: &AppHandle, duration: Duration) -> Result<EngineStatus, String> {
+        pause::validate(duration)?;
+        let _proxy = self.proxy.lock().unwrap();
+        let mut inner = self.inner.lock().unwrap();
+        if inner.state != EngineState::Ready {
+            return Err("Yalnızca bağlıyken duraklatılabilir.".to_string());
+        }
+        let resume_at_ms = pause::resume_at_ms(duration);
+        inner.state = EngineState::Paused { resume_at_ms };
+        let generation = inner.generation;
+        let status = inner.status();
+        drop(inner);
+
+        // Motor çalıştığı için araç entegrasyonları yerinde kalır; başarısız
+        // olursa bağlı duruma dönülür
+        if let Err(e) = crate::disable_system_proxy(app) {
+            let mut inner = self.inner.lock().unwrap();
+            if inner.generation == generation
+                && inner.state == (EngineState::Paused { resume_at_ms })
+            {
+                inner.state = EngineState::Ready;
+            }
+            return Err(e);
+        }
+        emit_status(app, &status);
+
+        let app_handle = app.clone();
+        std::thread::spawn(move || {
+            loop {
+                let left = pause::remaining(resume_at_ms);
+                if left.is_zero() {
+                    break;
+                }
+                std::thread::sleep(left.min(MONITOR_INTERVAL * 4));
+                // Elle devam edildiyse veya yeniden duraklatıldıysa bu zamanlayıcı bitti
+                if !app_handle
+                    .state::<EngineSupervisor>()
+                    .is_paused_until(generation, resume_at_ms)
+                {
+                    return;
+                }
+            }
+            let _ = app_handle.state::<EngineSupervisor>().resume(&app_handle);
+        });
+        Ok(status)
+    }
+
+    /// Duraklatılmış bağlantıda Vexar proxy'sini yeniden uygular.
+    pub fn resume(&self, app: &AppHandle) -> Result<EngineStatus, String> {
+        let _proxy = self.proxy.lock().unwrap();
+        let (target, generation) = {
+            let inner = self.inner.lock().unwrap();
+            if !matches!(inner.state, EngineState::Paused { .. }) {
+                return Ok(inner.status());
+            }
+            (inner.proxy_target(), inner.generation)
+        };
+        target
+            .and_then(|target| target.apply_system(app))
+            .map_err(|e| format!("Proxy ayarlanamadı: {e}"))?;
+
+        let mut inner = self.inner.lock().unwrap();
+        // Bu arada durdurulduysa proxy'yi durdurma işlemi temizler
+        if inner.generation == generation {
+            inner.state = EngineState::Ready;
+        }
+        let status = inner.status();
+        drop(inner);
+        emit_status(app, &status);
+        Ok(status)
+    }
+
+    /// LAN ayarlarını çalışan ağ geçidine uygular; paylaşım kapalıysa yalnızca kaydedilmiştir.
+    pub fn set_lan_settings(&self, settings: &lan::LanSettings) {
+        if let Some(gate) = &self.inner.lock().unwrap().gate {
+            gate.set_settings(settings.clone());
+        }
+    }
+
+    /// LAN paylaşımında bağlı cihazlar; paylaşım yoksa boş.
+    pub fn lan_clients(&self) -> Vec<clients::LanClient> {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .gate
+            .as_ref()
+            .map(|gate| gate.clients())
+            .unwrap_or_default()
+    }
+
+    /// Cihazın bağlantılarını keser; bağlı değilse false döner.
+    pub fn kick_lan_client(&self, ip: std::net::IpAddr) -> bool {
+        let inner = self.inner.lock().unwrap();
+        inner.gate.as_ref().is_some_and(|gate| gate.kick(ip))
+    }
+
+    fn stop_with(&self, app: &AppHandle, clear_proxy: bool) -> EngineStatus {
+        let (status, child, listeners) = {
+            let mut inner = self.inner.lock().unwrap();
+            inner.generation += 1;
+            inner.state = EngineState::Stopping;
+            inner.gate = None;
+            (
+                inner.status(),
+                inner.child.take(),
+                (inner.socks.take(), inner.gateway.take()),
+            )
+        };
+        emit_status(app, &status);
+
+        drop(listeners);
+        if let Some(mut child) = child {
+            lifetime::terminate(&mut child, lifetime::GRACEFUL_STOP_TIMEOUT);
+        }
+        if clear_proxy {
+            let _proxy = self.proxy.lock().unwrap();
+            let _ = crate::clear_system_proxy(app.clone());
+        }
+
+        let status = {
+            let mut inner = self.inner.lock().unwrap();
+            inner.state = EngineState::Idle;
+            inner.status()
+        };
+        emit_status(app, &status);
+        status
+    }
+
+    /// Çıkış katmanlarından çağrılır; çalışan bir motor yoksa sadece proxy'yi temizler.
+    pub fn shutdown(&self, app: &AppHandle) {
+        let running = self.inner.lock().unwrap().child.is_some();
+        if running {
+            self.stop(app);
+        } else {
+            let _proxy = self.proxy.lock().unwrap();
+            let _ = crate::clear_system_proxy(app.clone());
+        }
+    }
+
+    fn spawn_locked(&self, app: &AppHandle, inner: &mut Inner) -> Result<(), String> {
+        let engine_config = inner.config.clone().ok_or("Motor ayarları bulunamadı.")?;
+
+        let bind_addr = engine_config.listen_addr;
+        let probe_target = engine_config.probe_target.clone();
+        let socks_config = engine_config.socks.then_some(engine_config.socks_port);
+        let lan_settings = app.state::<SettingsStore>().get().lan;
+        let shared = engine_config.is_lan_shared();
+        // Yeniden denemede önceki köprünün ve ağ geçidinin portu serbest kalsın
+        inner.close_listeners();
+
+        // Her denemede port yeniden seçilir; argv burada doğrulanmış olarak gelir.
+        // Paylaşımda seçilen port ağ geçidine kalır, motor loopback'te başka bir portu dinler.
+        let prepared = crate::get_sidecar_config(engine_config.clone()).and_then(|config| {
+            if !shared {
+                let engine_addr = readiness::probe_addr(bind_addr, config.port);
+                return Ok((config.args.clone(), engine_addr, None, config));
+            }
+            let listener = lan::bind(bind_addr, config.port)?;
+            let internal = lan::engine_config(&engine_config)?;
+            let engine_addr = readiness::probe_addr(internal.listen_addr, internal.listen_port);
+            Ok((internal.to_args()?, engine_addr, Some(listener), config))
+        });
+        let (args, engine_addr, gateway_listener, config) = match prepared {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                inner.state = EngineState::Failed { reason: e.clone() };
+                return Err(e);
+            }
+        };
+
+        // Motor başlamadan önce açılır; port alınamazsa motor hiç başlatılmaz
+        let socks_listener = match socks_config.map(|port| socks::bind(bind_addr, port)) {
+            Some(Err(e)) => {
+                inner.state = EngineState::Failed { reason: e.clone() };
+                return Err(e);
+            }
+            Some(Ok(listener)) => Some(listener),
+            None => None,
+        };
+
+        let spawned = app
+            .shell()
+            .sidecar(SIDECAR_NAME)
+            .map_err(|e| e.to_string())
+            .and_then(|command| {
+                lifetime::spawn(std::process::Command::from(command.args(&args)))
+                    .map_err(|e| e.to_string())
+            });
+        let mut child = match spawned {
+            Ok(child) => child,
+            Err(e) => {
+                let reason = format!("Motor başlatılamadı: {e}");
+                inner.state = EngineState::Failed {
+                    reason: reason.clone(),
+                };
+                return Err(reason);
+            }
+        };
+
+        inner.generation += 1;
+        let generation = inner.generation;
+
+        if let Some(stdout) = child.stdout.take() {
+            spawn_log_reader(app.clone(), stdout, "stdout");
+        }
+        if let Some(stderr) = child.stderr.take() {
+            spawn_log_reader(app.clone(), stderr, "stderr");
+        }
+
+        // Hazırlık ve heartbeat paylaşımda ağ geçidinden geçer; loopback her zaman kabul edilir
+        let upstream = readiness::probe_addr(bind_addr, config.port);
+        let gate = gateway_listener.as_ref().map(|_| {
+            let app_handle = app.clone();
+            Arc::new(lan::Gate::new(lan_settings, move |peer| {
+                let _ = app_handle.emit(
+                    lan::REJECTED_EVENT,
+                    lan::Rejection {
+                        address: peer.ip().to_string(),
+                    },
+                );
+            }))
+        });
+        let started = gateway_listener
+            .zip(gate.clone())
+            .map(|(listener, gate)| {
+                let app_handle = app.clone();
+                // Port veya IP değiştiyse betik de yeni adresi gösterir
+                let pac = lan::pac_script(&config.lan_ip, config.port);
+                lan::Gateway::start(listener, engine_addr, gate, pac, move |clients| {
+                    let _ = app_handle.emit(clients::CLIENTS_EVENT, clients);
+                })
+            })
+            .transpose()
+            .map_err(|e| format!("LAN ağ geçidi başlatılamadı: {e}"))
+            .and_then(|gateway| {
+                socks_listener
+                    .map(|listener| socks::Bridge::start(listener, engine_addr, gate.clone()))
+                    .transpose()
+                    .map_err(|e| format!("SOCKS dinleyicisi başlatılamadı: {e}"))
+                    .map(|bridge| (gateway, bridge))
+            });
+        match started {
+            Ok((gateway, bridge)) => {
+                inner.gateway = gateway;
+                inner.socks = bridge;
+                inner.gate = gate;
+            }
+            Err(reason) => {
+                lifetime::terminate(&mut child, lifetime::GRACEFUL_STOP_TIMEOUT);
+                inner.state = EngineState::Failed {
+                    reason: reason.clone(),
+                };
+                return Err(reason);
+            }
+        }
+
+        inner.child = Some(child);
+        inner.port = Some(config.port);
+        inner.bind_address = Some(config.bind_address);
+        inner.lan_ip = Some(config.lan_ip);
+        inner.readiness = None;
+        inner.state = EngineState::Starting;
+
+        spawn_monitor(app.clone(), generation);
+        spawn_readiness_check(app.clone(), generation, upstream, probe_target);
+
+        Ok(())
+    }
+
+    /// Sistem proxy'si yalnızca motor CONNECT probe'una geçerli yanıt verdiyse açılır.
+    /// Motor hazır hale geldiyse true döner.
+    fn finish_readiness(&self, app: &AppHandle, generation: u64, report: ReadinessReport) -> bool {
+        let _proxy = self.proxy.lock().unwrap();
+        let target = {
+            let mut inner = self.inner.lock().unwrap();
+            if inner.generation != generation || inner.state != EngineState::Starting {
+                return false;
+            }
+            inner.readiness = Some(report.clone());
+            let _ = app.emit(READY_EVENT, report.clone());
+            if report.ready {
+                inner.proxy_target()
+            } else {
+                Err(match &report.failure {
+                    Some(failure) => format!("Motor hazır değil: {failure}"),
+                    None => "Motor hazır değil".to_string(),
+                })
+            }
+        };
+        let result = target.and_then(|target| {
+            target
+                .apply(app)
+                .map_err(|e| format!("Proxy ayarlanamadı: {e}"))
+        });
+
+        let mut inner = self.inner.lock().unwrap();
+        // Bu arada durdurulduysa proxy'yi durdurma işlemi temizler
+        if inner.generation != generation || inner.state != EngineState::Starting {
+            return false;
+        }
+
+        let ready = result.is_ok();
+        let child = match result {
+            Ok(()) => {
+                inner.state = EngineState::Ready;
+                inner.tracker.on_ready();
+                None
+            }
+            Err(reason) => {
+                inner.generation += 1;
+                inner.close_listeners();
+                inner.state = EngineState::Failed { reason };
+                inner.child.take()
+            }
+        };
+        let status = inner.status();
+        drop(inner);
+        emit_status(app, &status);
+        // Kilit bırakıldıktan sonra; kapanış beklenirken durum sorguları bloklanmasın
+        if let Some(mut child) = child {
+            lifetime::terminate(&mut child, lifetime::GRACEFUL_STOP_TIMEOUT);
+        }
+        ready
+    }
+
+    /// Heartbeat geçişini uygular; proxy değişmediyse false döner.
+    fn apply_heartbeat(
+        &self,
+        app: &AppHandle,
+        generation: u64,
+        transition: &Transition,
+        failure: Option<&ProbeFailure>,
+    ) -> bool {
+        let _proxy = self.proxy.lock().unwrap();
+        let mut inner = self.inner.lock().unwrap();
+        if inner.generation != generation {
+            return false;
+        }
+        let report = match (transition, &inner.state) {
+            (Transition::FailOpen { failures }, EngineState::Ready) => {
+                let reason = match failure {
+                    Some(failure) => format!("Motor yanıt vermiyor: {failure}"),
+                    None => "Motor yanıt vermiyor".to_string(),
+                };
+                inner.state = EngineState::Degraded {
+                    failures: *failures,
+                    reason: reason.clone(),
+                };
+                HeartbeatReport {
+                    healthy: false,
+                    failures: *failures,
+                    reason: Some(reason),
+                }
+            }
+            (Transition::Recovered, EngineState::Degraded { .. }) => {
+                let Ok(target) = inner.proxy_target() else {
+                    return false;
+                };
+                drop(inner);
+                if target.apply(app).is_err() {
+                    return false;
+                }
+                inner = self.inner.lock().unwrap();
+                // Bu arada durdurulduysa proxy'yi durdurma işlemi temizler
+                if inner.generation != generation {
+                    return false;
+                }
+                inner.state = EngineState::Ready;
+                HeartbeatReport {
+                    healthy: true,
+                    failures: 0,
+                    reason: None,
+                }
+            }
+            _ => return false,
+        };
+        let degraded = !report.healthy;
+        let status = inner.status();
+        drop(inner);
+        if degraded {
+            // Trafik doğrudan çıksın; motor toparlanırsa geri açılır
+            let _ = crate::clear_system_proxy(app.clone());
+        }
+        let _ = app.emit(HEARTBEAT_EVENT, report);
+        emit_status(app, &status);
+        true
+    }
+
+    /// Motor süreci kendiliğinden kapandığında monitor thread'inden çağrılır.
+    fn handle_exit(&self, app: &AppHandle, generation: u64, code: Option<i32>) {
+        let _proxy = self.proxy.lock().unwrap();
+        let mut inner = self.inner.lock().unwrap();
+        if inner.generation != generation {
+            return;
+        }
+        inner.child = None;
+        inner.close_listeners();
+
+        let exit_reason = match code {
+            Some(code) => format!("Motor beklenmedik şekilde durdu (Kod: {code})"),
+            None => "Motor beklenmedik şekilde durdu".to_string(),
+        };
+        let max_attempts = inner.tracker.policy().max_attempts;
+        let mut trip = None;
+
+        inner.state = match inner.tracker.on_crash() {
+            Decision::Retry { attempt, delay } => {
+                let app_handle = app.clone();
+                std::thread::spawn(move || {
+                    std::thread::sleep(delay);
+                    app_handle
+                        .state::<EngineSupervisor>()
+                        .respawn(&app_handle, generation);
+                });
+                EngineState::Reconnecting {
+                    attempt,
+                    max_attempts,
+                    delay_ms: delay.as_millis() as u64,
+                }
+            }
+            Decision::Disabled => EngineState::Failed {
+                reason: exit_reason,
+            },
+            Decision::GiveUp { attempts } => EngineState::Failed {
+                reason: format!("{exit_reason}; {attempts} denemeden sonra vazgeçildi"),
+            },
+            Decision::Trip { crashes, window } => {
+                // Proxy aşağıda temizlenir; kullanıcının interneti doğrudan çalışmaya devam eder
+                let reason = format!(
+                    "Motor {} sn içinde {crashes} kez çöktü; yeniden deneme durduruldu ve sistem proxy'si kapatıldı",
+                    window.as_secs()
+                );
+                trip = Some(BreakerTrip {
+                    crashes,
+                    window_secs: window.as_secs(),
+                    reason: reason.clone(),
+                });
+                EngineState::Failed { reason }
+            }
+        };
+
+        let status = inner.status();
+        drop(inner);
+        let _ = crate::clear_system_proxy(app.clone());
+        if let Some(trip) = trip {
+            let _ = app.emit(BREAKER_EVENT, trip);
+        }
+        emit_status(app, &status);
+    }
+
+    fn respawn(&self, app: &AppHandle, generation: u64) {
+        let mut inner = self.inner.lock().unwrap();
+        // Bekleme sırasında kullanıcı durdurduysa yeniden başlatma
+        if inner.generation != generation
+            || !matches!(inner.state, EngineState::Reconnecting { .. })
+        {
+            return;
+        }
+        let _ = self.spawn_locked(app, &mut inner);
+        let status = inner.status();
+        drop(inner);
+        emit_status(app, &status);
+    }
+
+    fn is_current(&self, generation: u64) -> bool {
+        self.inner.lock().unwrap().generation == generation
+    }
+
+    fn is_starting(&self, generation: u64) -> bool {
+        let inner = self.inner.lock().unwrap();
+        inner.generation == generation && inner.state == EngineState::Starting
+    }
+
+    /// Heartbeat ve sapma izleme duraklatma boyunca da çalışmaya devam eder.
+    fn is_connected(&self, generation: u64) -> bool {
+        let inner = self.inner.lock().unwrap();
+        inner.generation == generation
+            && matches!(
+                inner.state,
+                EngineState::Ready | EngineState::Degraded { .. } | EngineState::Paused { .. }
+            )
+    }
+
+    /// Yerel motor LAN'a paylaşılıyorsa keşif sorgularına verilecek port ve
+    /// izin listesini uygulayan kapı.
+    fn shared_port(&self, generation: u64) -> Option<(u16, Arc<lan::Gate>)> {
+        let inner = self.inner.lock().unwrap();
+        let shared = inner.generation == generation
+            && inner.remote.is_none()
+            && inner.config.as_ref().is_some_and(|c| c.is_lan_shared());
+        if shared {
+            inner.port.zip(inner.gate.clone())
+        } else {
+            None
+        }
+    }
+
+    fn is_paused(&self, generation: u64) -> bool {
+        let inner = self.inner.lock().unwrap();
+        inner.generation == generation && matches!(inner.state, EngineState::Paused { .. })
+    }
+
+    fn is_paused_until(&self, generation: u64, resume_at_ms: u64) -> bool {
+        let inner = self.inner.lock().unwrap();
+        inner.generation == generation && inner.state == EngineState::Paused { resume_at_ms }
+    }
+}
+
+/// Açılışta, önceki oturumlardan sahipsiz kalmış motorları sonlandırır.
+pub fn sweep_stale_engines() -> Vec<u32> {
+    lifetime::sidecar_path(SIDECAR_NAME)
+        .map(|sidecar| lifetime::sweep_stale(&sidecar))
+        .unwrap_or_default()
+}
+
+fn emit_status(app: &AppHandle, status: &EngineStatus) {
+    let _ = app.emit(STATE_EVENT, status.clone());
+}
+
+fn spawn_log_reader<R: Read + Send + 'static>(app: AppHandle, pipe: R, stream: &'static str) {
+    std::thread::spawn(move || {
+        for line in BufReader::new(pipe).lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            let _ = app.emit(LOG_EVENT, EngineLogLine { line, stream });
+        }
+    });
+}
+
+fn spawn_readiness_check(app: AppHandle, generation: u64, proxy: SocketAddr, target: String) {
+    std::thread::spawn(move || {
+        let report = {
+            let supervisor = app.state::<EngineSupervisor>();
+            readiness::wait_until_ready(proxy, &target, READY_DEADLINE, READY_INTERVAL, || {
+                supervisor.is_starting(generation)
+            })
+        };
+        let ready = app
+            .state::<EngineSupervisor>()
+            .finish_readiness(&app, generation, report);
+        if ready {
+            spawn_drift_watch(app.clone(), generation);
+            if let Some((port, gate)) = app.state::<EngineSupervisor>().shared_port(generation) {
+                spawn_discovery_responder(app.clone(), generation, port, gate);
+            }
+            run_heartbeat(&app, generation, proxy, &target);
+        }
+    });
+}
+
+/// Motor bağlı kaldığı sürece aynı thread'de çalışır.
+fn run_heartbeat(app: &AppHandle, generation: u64, proxy: SocketAddr, target: &str) {
+    let policy = app.state::<SettingsStore>().get().heartbeat;
+    if !policy.enabled {
+        return;
+    }
+    let mut guard = HeartbeatGuard::new(&policy);
+    loop {
+        std::thread::sleep(policy.interval());
+        let supervisor = app.state::<EngineSupervisor>();
+        if !supervisor.is_connected(generation) {
+            return;
+        }
+        // Duraklatılmışken kapatılacak proxy yok; devam edilince sayım baştan başlar
+        if supervisor.is_paused(generation) {
+            guard.reset();
+            continue;
+        }
+
+        let failure = readiness::probe(proxy, target, policy.timeout()).err();
+        if let Some(transition) = guard.record(failure.is_none()) {
+            let applied =
+                supervisor.apply_heartbeat(app, generation, &transition, failure.as_ref());
+            if !applied {
+                match transition {
+                    Transition::Recovered => guard.revert_recovery(),
+                    Transition::FailOpen { .. } => guard.reset(),
+                }
+            }
+        }
+    }
+}
+
+/// Motor bağlı kaldığı sürece sistem proxy'sinin başka araçlarca
+/// değiştirilip değiştirilmediğini kontrol eder.
+fn spawn_drift_watch(app: AppHandle, generation: u64) {
+    let policy = app.state::<SettingsStore>().get().drift;
+    if !policy.enabled {
+        return;
+    }
+    std::thread::spawn(move || {
+        let mut watch = DriftWatch::default();
+        loop {
+            std::thread::sleep(policy.interval());
+            let supervisor = app.state::<EngineSupervisor>();
+            if !supervisor.is_connected(generation) {
+                return;
+            }
+            let controller = app.state::<ProxyController>();
+            let Ok(changes) = controller.check_drift() else {
+                continue;
+            };
+            if watch.observe(&changes) {
+                let _ = app.emit(
+                    DRIFT_EVENT,
+                    DriftReport {
+                        action: policy.action,
+                        changes: changes.clone(),
+                    },
+                );
+            }
+            if changes.is_empty() {
+                continue;
+            }
+            match policy.action {
+                DriftAction::Reassert => {
+                    let _ = controller.reassert();
+                }
+                DriftAction::Notify => {}
+                DriftAction::Disconnect => {
+                    supervisor.disconnect_after_drift(&app, generation);
+                    return;
+                }
+            }
+        }
+    });
+}
+
+/// Paylaşılan motor bağlı kaldığı sürece LAN'daki istemcilerin keşif
+/// sorgularına yanıt verir. Port başka bir Vexar'daysa sessizce vazgeçer.
+/// İzin listesinde olmayan cihazlara motorun varlığı da bildirilmez.
+fn spawn_discovery_responder(app: AppHandle, generation: u64, port: u16, gate: Arc<lan::Gate>) {
+    let Ok(socket) = remote::bind_responder() else {
+        return;
+    };
+    std::thread::spawn(move || {
+        remote::serve(
+            &socket,
+            port,
+            |from| gate.admit(from),
+            || app.state::<EngineSupervisor>().is_connected(generation),
+        );
+    });
+}
+
+fn spawn_monitor(app: AppHandle, generation: u64) {
+    std::thread::spawn(move || {
+        loop {
+            std::thread::sleep(MONITOR_INTERVAL);
+            let supervisor = app.state::<EngineSupervisor>();
+            if !supervisor.is_current(generation) {
+                return;
+            }
+
+            let exited = {
+                let mut inner = supervisor.inner.lock().unwrap();
+                match inner.child.as_mut().map(|child| child.try_wait()) {
+                    Some(Ok(Some(status))) => Some(status.code()),
+                    Some(Ok(None)) => None,
+                    Some(Err(_)) => Some(None),
+                    None => return,
+                }
+            };
+
+            if let Some(code) = exited {
+                supervisor.handle_exit(&app, generation, code);
+                return;
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn start_engine(app: AppHandle, config: EngineConfig) -> Result<EngineStatus, String> {
+    app.state::<EngineSupervisor>().start(&app, config)
+}
+
+#[tauri::command]
+pub async fn stop_engine(app: AppHandle) -> Result<EngineStatus, String> {
+    Ok(app.state::<EngineSupervisor>().stop(&app))
+}
+
+#[tauri::command]
+pub async fn connect_remote(app: AppHandle, config: RemoteConfig) -> Result<EngineStatus, String> {
+    app.state::<EngineSupervisor>().connect_remote(&app, config)
+}
+
+#[tauri::command]
+pub async fn discover_remote_engines(timeout_ms: u64) -> Result<Vec<DiscoveredEngine>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        remote::discover(Duration::from_millis(timeout_ms))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn pause_proxy(app: AppHandle, duration_secs: u64) -> Result<EngineStatus, String> {
+    app.state::<EngineSupervisor>()
+        .pause(&app, Duration::from_secs(duration_secs))
+}
+
+#[tauri::command]
+pub async fn resume_proxy(app: AppHandle) -> Result<EngineStatus, String> {
+    app.state::<EngineSupervisor>().resume(&app)
+}
+
+#[tauri::command]
+pub fn engine_status(supervisor: tauri::State<'_, EngineSupervisor>) -> EngineStatus {
+    supervisor.status()
+}
@@ -0,0 +1,62 @@
+//! Motoru durdurmadan sistem proxy'sini geçici olarak kapatma.
+//!
+//! Banka siteleri veya captive portal girişleri için birkaç dakikalık
+//! doğrudan bağlantı gerektiğinde motor (ve sıcak DNS önbelleği) çalışmaya
+//! devam eder; yalnızca bağlantı öncesi proxy ayarları geri yüklenir ve süre
+//! dolunca Vexar proxy'si yeniden uygulanır.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const PAUSE_MIN: Duration = Duration::from_secs(10);
+pub const PAUSE_MAX: Duration = Duration::from_secs(4 * 60 * 60);
+/// Tepsi menüsündeki "Duraklat" öğesinin süresi.
+pub const TRAY_PAUSE: Duration = Duration::from_secs(5 * 60);
+
+pub fn validate(duration: Duration) -> Result<(), String> {
+    if !(PAUSE_MIN..=PAUSE_MAX).contains(&duration) {
+        return Err(format!(
+            "Geçersiz duraklatma süresi: {} sn ({}-{} sn arası olmalı)",
+            duration.as_secs(),
+            PAUSE_MIN.as_secs(),
+            PAUSE_MAX.as_secs()
+        ));
+    }
+    Ok(())
+}
+
+/// Duraklatmanın biteceği an (Unix zamanı, ms); arayüz kalan süreyi bununla gösterir.
+pub fn resume_at_ms(duration: Duration) -> u64 {
+    now_ms().saturating_add(duration.as_millis() as u64)
+}
+
+/// Süre dolduysa sıfır döner.
+pub fn remaining(resume_at_ms: u64) -> Duration {
+    Duration::from_millis(resume_at_ms.saturating_sub(now_ms()))
+}
+
+pub(super) fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_duration() {
+        assert!(validate(TRAY_PAUSE).is_ok());
+        assert!(validate(Duration::from_secs(1)).is_err());
+        assert!(validate(Duration::from_secs(24 * 60 * 60)).is_err());
+    }
+
+    #[test]
+    fn remaining_counts_down_to_zero() {
+        let at = resume_at_ms(Duration::from_secs(60));
+        let left = remaining(at);
+        assert!(left <= Duration::from_secs(60) && left > Duration::from_secs(58));
+        assert_eq!(remaining(now_ms() - 1000), Duration::ZERO);
+    }
+}
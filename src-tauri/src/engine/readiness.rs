@@ -0,0 +1,358 @@
+//! Sistem proxy'si açılmadan önce motorun gerçekten tünel açabildiğini doğrular.
+//!
+//! Log satırlarına veya kör bir zamanlayıcıya güvenmek yerine motora TCP ile
+//! bağlanılır, probe hedefine HTTP CONNECT gönderilir ve geçerli bir proxy
+//! yanıtı beklenir.
+
+use std::io::{Read, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream};
+use std::time::{Duration, Instant};
+
+const MAX_RESPONSE_HEAD: usize = 8 * 1024;
+
+/// Probe başarısızlığının nedeni.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[serde(tag = "kind", content = "detail", rename_all = "snake_case")]
+pub enum ProbeFailure {
+    /// Motorun portuna TCP bağlantısı kurulamadı.
+    Connect(String),
+    /// Motor zamanında yanıt vermedi.
+    Timeout,
+    /// Yanıt okunurken bağlantı koptu.
+    Io(String),
+    /// Yanıt HTTP proxy yanıtına benzemiyor.
+    InvalidResponse(String),
+    /// Motor CONNECT isteğini reddetti (ör. hedefe ulaşamadı).
+    Rejected(u16),
+    /// Hedef CONNECT satırına yazılamaz; motora hiç gidilmedi.
+    InvalidTarget(String),
+}
+
+impl std::fmt::Display for ProbeFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProbeFailure::Connect(e) => write!(f, "Motora bağlanılamadı: {e}"),
+            ProbeFailure::Timeout => write!(f, "Motor zamanında yanıt vermedi"),
+            ProbeFailure::Io(e) => write!(f, "Motor bağlantısı koptu: {e}"),
+            ProbeFailure::InvalidResponse(line) => write!(f, "Geçersiz proxy yanıtı: {line}"),
+            ProbeFailure::Rejected(code) => write!(f, "Motor tüneli reddetti (HTTP {code})"),
+            ProbeFailure::InvalidTarget(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Hazırlık kontrolünün arayüze ve loglara giden sonucu.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct ReadinessReport {
+    pub ready: bool,
+    pub proxy: String,
+    pub target: String,
+    pub attempts: u32,
+    pub elapsed_ms: u64,
+    pub failure: Option<ProbeFailure>,
+}
+
+/// `0.0.0.0` gibi joker adreslere bağlanılamayacağı için loopback'e çevirir.
+pub fn probe_addr(bind_address: IpAddr, port: u16) -> SocketAddr {
+    let ip = match bind_address {
+        IpAddr::V4(ip) if ip.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
+        IpAddr::V6(ip) if ip.is_unspecified() => IpAddr::V6(Ipv6Addr::LOCALHOST),
+        ip => ip,
+    };
+    SocketAddr::new(ip, port)
+}
+
+/// `host:port` biçimindeki probe hedefini doğrular.
+pub fn validate_target(target: &str) -> Result<(), String> {
+    let valid = target.rsplit_once(':').is_some_and(|(host, port)| {
+        !host.is_empty()
+            && !host.contains(|c: char| c.is_whitespace() || c.is_control())
+            && port.parse::<u16>().is_ok_and(|p| p != 0)
+    });
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Geçersiz probe hedefi: {target} (host:port olmalı)"
+        ))
+    }
+}
+
+/// Proxy üzerinden tek bir CONNECT denemesi yapar ve HTTP durum kodunu döndürür.
+pub fn probe(proxy: SocketAddr, target: &str, timeout: Duration) -> Result<u16, ProbeFailure> {
+    open_tunnel(proxy, target, timeout).map(|tunnel| tunnel.status)
+}
+
+/// Açılmış bir CONNECT tüneli.
+pub struct Tunnel {
+    pub stream: TcpStream,
+    pub status: u16,
+    /// Yanıt başlığıyla aynı okumada gelmiş tünel verisi.
+    pub early_data: Vec<u8>,
+}
+
+/// Proxy'ye CONNECT gönderir; yanıt 2xx ise tüneli döndürür. Zaman aşımları
+/// akışta kalır, uzun ömürlü kullanımda çağıran kaldırmalıdır.
+pub fn open_tunnel(
+    proxy: SocketAddr,
+    target: &str,
+    timeout: Duration,
+) -> Result<Tunnel, ProbeFailure> {
+    // Hedef isteğe olduğu gibi yazılır; satır sonu içeren hedef başlık ekleyebilir
+    validate_target(target).map_err(ProbeFailure::InvalidTarget)?;
+    let mut stream = TcpStream::connect_timeout(&proxy, timeout)
+        .map_err(|e| ProbeFailure::Connect(e.to_string()))?;
+    let _ = stream.set_read_timeout(Some(timeout));
+    let _ = stream.set_write_timeout(Some(timeout));
+    let _ = stream.set_nodelay(true);
+
+    let request = format!("CONNECT {target} HTTP/1.1\r\nHost: {target}\r\n\r\n");
+    stream.write_all(request.as_bytes()).map_err(io_failure)?;
+
+    let (head, early_data) = read_response_head(&mut stream)?;
+    let status = parse_status_line(&head)?;
+    if (200..300).contains(&status) {
+        Ok(Tunnel {
+            stream,
+            status,
+            early_data,
+        })
+    } else {
+        Err(ProbeFailure::Rejected(status))
+    }
+}
+
+/// Motor hazır olana, `deadline` dolana veya `keep_going` false dönene kadar probe'u tekrarlar.
+pub fn wait_until_ready(
+    proxy: SocketAddr,
+    target: &str,
+    deadline: Duration,
+    interval: Duration,
+    keep_going: impl Fn() -> bool,
+) -> ReadinessReport {
+    let started = Instant::now();
+    let mut attempts = 0;
+    let mut last_failure = ProbeFailure::Timeout;
+
+    while started.elapsed() < deadline && keep_going() {
+        attempts += 1;
+        let remaining = deadline.saturating_sub(started.elapsed());
+        match probe(proxy, target, remaining.max(Duration::from_millis(100))) {
+            Ok(_) => {
+                return ReadinessReport {
+                    ready: true,
+                    proxy: proxy.to_string(),
+                    target: target.to_string(),
+                    attempts,
+                    elapsed_ms: started.elapsed().as_millis() as u64,
+                    failure: None,
+                };
+            }
+            Err(failure) => last_failure = failure,
+        }
+        std::thread::sleep(interval);
+    }
+
+    ReadinessReport {
+        ready: false,
+        proxy: proxy.to_string(),
+        target: target.to_string(),
+        attempts,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+        failure: Some(last_failure),
+    }
+}
+
+fn io_failure(e: std::io::Error) -> ProbeFailure {
+    match e.kind() {
+        std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut => ProbeFailure::Timeout,
+        _ => ProbeFailure::Io(e.to_string()),
+    }
+}
+
+/// Başlığı ve başlıktan sonra okunmuş baytları döndürür.
+fn read_response_head(stream: &mut TcpStream) -> Result<(String, Vec<u8>), ProbeFailure> {
+    let mut buf = Vec::with_capacity(256);
+    let mut chunk = [0u8; 512];
+    loop {
+        let n = stream.read(&mut chunk).map_err(io_failure)?;
+        if n == 0 {
+            if buf.is_empty() {
+                return Err(ProbeFailure::Io("bağlantı yanıtsız kapandı".to_string()));
+            }
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            let rest = buf.split_off(end + 4);
+            return Ok((String::from_utf8_lossy(&buf).into_owned(), rest));
+        }
+        if buf.len() >= MAX_RESPONSE_HEAD {
+            break;
+        }
+    }
+    Ok((String::from_utf8_lossy(&buf).into_owned(), Vec::new()))
+}
+
+fn parse_status_line(head: &str) -> Result<u16, ProbeFailure> {
+    let line = head.lines().next().unwrap_or_default().trim();
+    let mut parts = line.split_whitespace();
+    let version = parts.next().unwrap_or_default();
+    let code = parts.next().and_then(|c| c.parse::<u16>().ok());
+    match code {
+        Some(code) if version.starts_with("HTTP/1.") && (100..600).contains(&code) => Ok(code),
+        _ => Err(ProbeFailure::InvalidResponse(
+            line.chars().take(80).collect(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    const TARGET: &str = "example.com:443";
+
+    /// Tek bağlantı kabul edip verilen yanıtı yazan sahte proxy.
+    fn stand_in(response: &'static [u8]) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 256];
+            let n = stream.read(&mut buf).unwrap();
+            let request = String::from_utf8_lossy(&buf[..n]);
+            assert!(request.starts_with(&format!("CONNECT {TARGET} HTTP/1.1\r\n")));
+            let _ = stream.write_all(response);
+        });
+        addr
+    }
+
+    fn quick_probe(addr: SocketAddr) -> Result<u16, ProbeFailure> {
+        probe(addr, TARGET, Duration::from_millis(500))
+    }
+
+    #[test]
+    fn accepts_connection_established() {
+        let addr = stand_in(b"HTTP/1.1 200 Connection established\r\n\r\n");
+        assert_eq!(quick_probe(addr), Ok(200));
+    }
+
+    #[test]
+    fn tunnel_keeps_bytes_after_head() {
+        let addr = stand_in(b"HTTP/1.1 200 OK\r\n\r\nSSH-2.0");
+        let tunnel = open_tunnel(addr, TARGET, Duration::from_millis(500)).unwrap();
+        assert_eq!(tunnel.status, 200);
+        assert_eq!(tunnel.early_data, b"SSH-2.0");
+    }
+
+    #[test]
+    fn reports_rejected_tunnel() {
+        let addr = stand_in(b"HTTP/1.1 502 Bad Gateway\r\nContent-Length: 0\r\n\r\n");
+        assert_eq!(quick_probe(addr), Err(ProbeFailure::Rejected(502)));
+    }
+
+    #[test]
+    fn reports_non_http_response() {
+        let addr = stand_in(b"SSH-2.0-OpenSSH_9.6\r\n\r\n");
+        assert!(matches!(
+            quick_probe(addr),
+            Err(ProbeFailure::InvalidResponse(_))
+        ));
+    }
+
+    #[test]
+    fn reports_closed_connection() {
+        let addr = stand_in(b"");
+        assert!(matches!(quick_probe(addr), Err(ProbeFailure::Io(_))));
+    }
+
+    #[test]
+    fn reports_silent_server_as_timeout() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let _hold = std::thread::spawn(move || {
+            let (_stream, _) = listener.accept().unwrap();
+            std::thread::sleep(Duration::from_secs(1));
+        });
+        assert_eq!(
+            probe(addr, TARGET, Duration::from_millis(100)),
+            Err(ProbeFailure::Timeout)
+        );
+    }
+
+    #[test]
+    fn reports_closed_port() {
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        assert!(matches!(quick_probe(addr), Err(ProbeFailure::Connect(_))));
+    }
+
+    #[test]
+    fn wait_gives_up_with_last_reason() {
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let report = wait_until_ready(
+            addr,
+            TARGET,
+            Duration::from_millis(300),
+            Duration::from_millis(50),
+            || true,
+        );
+        assert!(!report.ready);
+        assert!(report.attempts >= 1);
+        assert!(matches!(report.failure, Some(ProbeFailure::Connect(_))));
+    }
+
+    #[test]
+    fn wait_succeeds_once_engine_is_up() {
+        let addr = stand_in(b"HTTP/1.0 200 OK\r\n\r\n");
+        let report = wait_until_ready(
+            addr,
+            TARGET,
+            Duration::from_secs(1),
+            Duration::from_millis(50),
+            || true,
+        );
+        assert!(report.ready);
+        assert_eq!(report.failure, None);
+    }
+
+    #[test]
+    fn maps_wildcard_bind_to_loopback() {
+        assert_eq!(
+            probe_addr(IpAddr::from([0, 0, 0, 0]), 8080),
+            "127.0.0.1:8080".parse().unwrap()
+        );
+        assert_eq!(
+            probe_addr("::".parse().unwrap(), 8080),
+            "[::1]:8080".parse().unwrap()
+        );
+    }
+
+    #[test]
+    fn validates_targets() {
+        assert!(validate_target("www.cloudflare.com:443").is_ok());
+        assert!(validate_target("[2606:4700::1111]:443").is_ok());
+        assert!(validate_target("cloudflare.com").is_err());
+        assert!(validate_target(":443").is_err());
+        assert!(validate_target("host:0").is_err());
+        assert!(validate_target("a.com\r\nX-Injected: 1:443").is_err());
+        assert!(validate_target("a.com\0:443").is_err());
+        // Geçersiz hedefle motora bağlanılmaz bile
+        let closed = "127.0.0.1:1".parse().unwrap();
+        assert!(matches!(
+            open_tunnel(
+                closed,
+                "a.com\r\nHost: b.com:443",
+                Duration::from_millis(100)
+            ),
+            Err(ProbeFailure::InvalidTarget(_))
+        ));
+    }
+}
@@ -0,0 +1,384 @@
+//! Motor beklenmedik şekilde kapandığında ne zaman yeniden deneneceğine karar verir.
+//!
+//! Gecikme üstel olarak büyür ve jitter eklenir. Kısa bir pencere içinde çok
+//! fazla çökme olursa devre kesici açılır: yeniden deneme durur ve supervisor
+//! kullanıcının internetinin çalışmaya devam etmesi için proxy'yi temizler.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ReconnectPolicy {
+    pub enabled: bool,
+    pub base_delay_ms: u64,
+    pub max_delay_ms: u64,
+    pub multiplier: f64,
+    /// Gecikmeye uygulanacak rastgele sapma oranı (0.2 = ±%20).
+    pub jitter: f64,
+    pub max_attempts: u32,
+    /// Bu pencere içinde `crash_threshold` kadar çökme olursa kesici açılır.
+    pub crash_window_secs: u64,
+    pub crash_threshold: u32,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            base_delay_ms: 1000,
+            max_delay_ms: 30_000,
+            multiplier: 2.0,
+            jitter: 0.2,
+            max_attempts: 5,
+            crash_window_secs: 120,
+            crash_threshold: 4,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.base_delay_ms > self.max_delay_ms {
+            return Err("Başlangıç gecikmesi azami gecikmeden büyük olamaz.".to_string());
+        }
+        if self.max_delay_ms > 10 * 60 * 1000 {
+            return Err("Azami gecikme 10 dakikayı geçemez.".to_string());
+        }
+        if !(1.0..=10.0).contains(&self.multiplier) {
+            return Err("Çarpan 1 ile 10 arasında olmalı.".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.jitter) {
+            return Err("Jitter 0 ile 1 arasında olmalı.".to_string());
+        }
+        if self.max_attempts == 0 || self.max_attempts > 100 {
+            return Err("Deneme sayısı 1 ile 100 arasında olmalı.".to_string());
+        }
+        if self.crash_threshold < 2 || self.crash_window_secs == 0 {
+            return Err("Çökme eşiği en az 2, pencere en az 1 saniye olmalı.".to_string());
+        }
+        Ok(())
+    }
+
+    /// Jitter uygulanmamış gecikme; `attempt` 1'den başlar.
+    pub fn base_delay(&self, attempt: u32) -> Duration {
+        let exp = self.multiplier.powi(attempt.saturating_sub(1) as i32);
+        let ms = (self.base_delay_ms as f64 * exp).min(self.max_delay_ms as f64);
+        Duration::from_millis(ms as u64)
+    }
+}
+
+/// Test edilebilirlik için enjekte edilen saat.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Testlerde elle ilerletilen saat.
+#[cfg(test)]
+#[derive(Clone)]
+pub(crate) struct ManualClock(std::sync::Arc<std::sync::Mutex<Instant>>);
+
+#[cfg(test)]
+impl ManualClock {
+    pub(crate) fn new() -> Self {
+        Self(std::sync::Arc::new(std::sync::Mutex::new(Instant::now())))
+    }
+
+    pub(crate) fn advance(&self, by: Duration) {
+        *self.0.lock().unwrap() += by;
+    }
+}
+
+#[cfg(test)]
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        *self.0.lock().unwrap()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Decision {
+    Retry {
+        attempt: u32,
+        delay: Duration,
+    },
+    /// Yeniden bağlanma kapalı.
+    Disabled,
+    /// `max_attempts` tükendi.
+    GiveUp {
+        attempts: u32,
+    },
+    /// Çökme döngüsü algılandı; kesici açıldı.
+    Trip {
+        crashes: u32,
+        window: Duration,
+    },
+}
+
+pub struct ReconnectTracker<C: Clock = SystemClock> {
+    policy: ReconnectPolicy,
+    clock: C,
+    attempt: u32,
+    crashes: VecDeque<Instant>,
+    rng: u64,
+}
+
+impl ReconnectTracker<SystemClock> {
+    pub fn new(policy: ReconnectPolicy) -> Self {
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0x9E37_79B9_7F4A_7C15);
+        Self::with_clock(policy, SystemClock, seed)
+    }
+}
+
+impl<C: Clock> ReconnectTracker<C> {
+    pub fn with_clock(policy: ReconnectPolicy, clock: C, seed: u64) -> Self {
+        Self {
+            policy,
+            clock,
+            attempt: 0,
+            crashes: VecDeque::new(),
+            rng: seed | 1,
+        }
+    }
+
+    pub fn policy(&self) -> &ReconnectPolicy {
+        &self.policy
+    }
+
+    /// Motor hazır hale geldi: deneme sayacı sıfırlanır, çökme geçmişi korunur.
+    pub fn on_ready(&mut self) {
+        self.attempt = 0;
+    }
+
+    pub fn on_crash(&mut self) -> Decision {
+        let now = self.clock.now();
+        let window = Duration::from_secs(self.policy.crash_window_secs);
+        self.crashes.push_back(now);
+        while self
+            .crashes
+            .front()
+            .is_some_and(|t| now.duration_since(*t) > window)
+        {
+            self.crashes.pop_front();
+        }
+
+        if self.crashes.len() as u32 >= self.policy.crash_threshold {
+            return Decision::Trip {
+                crashes: self.crashes.len() as u32,
+                window,
+            };
+        }
+        if !self.policy.enabled {
+            return Decision::Disabled;
+        }
+        if self.attempt >= self.policy.max_attempts {
+            return Decision::GiveUp {
+                attempts: self.attempt,
+            };
+        }
+
+        self.attempt += 1;
+        Decision::Retry {
+            attempt: self.attempt,
+            delay: self.jittered(self.policy.base_delay(self.attempt)),
+        }
+    }
+
+    fn jittered(&mut self, delay: Duration) -> Duration {
+        if self.policy.jitter == 0.0 {
+            return delay;
+        }
+        // xorshift64: -1..1 aralığında sapma
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        let unit = (self.rng >> 11) as f64 / (1u64 << 53) as f64;
+        let factor = 1.0 + self.policy.jitter * (unit * 2.0 - 1.0);
+        Duration::from_millis((delay.as_millis() as f64 * factor).round() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_jitter() -> ReconnectPolicy {
+        ReconnectPolicy {
+            jitter: 0.0,
+            ..ReconnectPolicy::default()
+        }
+    }
+
+    fn retry_delay(decision: Decision) -> Duration {
+        match decision {
+            Decision::Retry { delay, .. } => delay,
+            other => panic!("retry bekleniyordu: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn delays_grow_exponentially_and_cap() {
+        let policy = ReconnectPolicy {
+            max_attempts: 10,
+            crash_threshold: 100,
+            ..no_jitter()
+        };
+        let clock = ManualClock::new();
+        let mut tracker = ReconnectTracker::with_clock(policy, clock.clone(), 1);
+        let delays: Vec<u64> = (0..7)
+            .map(|_| {
+                clock.advance(Duration::from_secs(60));
+                retry_delay(tracker.on_crash()).as_millis() as u64
+            })
+            .collect();
+        assert_eq!(delays, [1000, 2000, 4000, 8000, 16000, 30000, 30000]);
+    }
+
+    #[test]
+    fn gives_up_after_max_attempts() {
+        let clock = ManualClock::new();
+        let mut tracker = ReconnectTracker::with_clock(no_jitter(), clock.clone(), 1);
+        for attempt in 1..=5 {
+            clock.advance(Duration::from_secs(121));
+            assert_eq!(
+                tracker.on_crash(),
+                Decision::Retry {
+                    attempt,
+                    delay: no_jitter().base_delay(attempt)
+                }
+            );
+        }
+        clock.advance(Duration::from_secs(121));
+        assert_eq!(tracker.on_crash(), Decision::GiveUp { attempts: 5 });
+    }
+
+    #[test]
+    fn ready_resets_attempts() {
+        let clock = ManualClock::new();
+        let mut tracker = ReconnectTracker::with_clock(no_jitter(), clock.clone(), 1);
+        assert_eq!(retry_delay(tracker.on_crash()), Duration::from_millis(1000));
+        clock.advance(Duration::from_secs(200));
+        assert_eq!(retry_delay(tracker.on_crash()), Duration::from_millis(2000));
+        tracker.on_ready();
+        clock.advance(Duration::from_secs(200));
+        assert_eq!(retry_delay(tracker.on_crash()), Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn trips_on_crash_loop_even_after_ready() {
+        let clock = ManualClock::new();
+        let mut tracker = ReconnectTracker::with_clock(no_jitter(), clock.clone(), 1);
+        for _ in 0..3 {
+            assert!(matches!(tracker.on_crash(), Decision::Retry { .. }));
+            tracker.on_ready();
+            clock.advance(Duration::from_secs(10));
+        }
+        assert_eq!(
+            tracker.on_crash(),
+            Decision::Trip {
+                crashes: 4,
+                window: Duration::from_secs(120)
+            }
+        );
+    }
+
+    #[test]
+    fn old_crashes_leave_the_window() {
+        let clock = ManualClock::new();
+        let mut tracker = ReconnectTracker::with_clock(no_jitter(), clock.clone(), 1);
+        for _ in 0..3 {
+            tracker.on_crash();
+            tracker.on_ready();
+            clock.advance(Duration::from_secs(50));
+        }
+        // İlk çökme artık 150 sn önce: pencerede sadece 3 çökme kalır
+        assert!(matches!(tracker.on_crash(), Decision::Retry { .. }));
+    }
+
+    #[test]
+    fn disabled_policy_still_detects_loops() {
+        let policy = ReconnectPolicy {
+            enabled: false,
+            crash_threshold: 2,
+            ..no_jitter()
+        };
+        let clock = ManualClock::new();
+        let mut tracker = ReconnectTracker::with_clock(policy, clock, 1);
+        assert_eq!(tracker.on_crash(), Decision::Disabled);
+        assert!(matches!(
+            tracker.on_crash(),
+            Decision::Trip { crashes: 2, .. }
+        ));
+    }
+
+    #[test]
+    fn jitter_is_bounded_and_deterministic() {
+        let policy = ReconnectPolicy {
+            jitter: 0.25,
+            max_attempts: 100,
+            crash_threshold: 1000,
+            ..ReconnectPolicy::default()
+        };
+        let run = |seed| {
+            let clock = ManualClock::new();
+            let mut tracker = ReconnectTracker::with_clock(policy.clone(), clock, seed);
+            (0..20)
+                .map(|_| {
+                    let delay = retry_delay(tracker.on_crash());
+                    tracker.on_ready();
+                    delay
+                })
+                .collect::<Vec<_>>()
+        };
+        let first = run(42);
+        assert_eq!(first, run(42));
+        assert_ne!(first, run(7));
+        for delay in first {
+            assert!(
+                (750..=1250).contains(&(delay.as_millis() as u64)),
+                "{delay:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_policies() {
+        assert!(ReconnectPolicy::default().validate().is_ok());
+        for policy in [
+            ReconnectPolicy {
+                multiplier: 0.5,
+                ..ReconnectPolicy::default()
+            },
+            ReconnectPolicy {
+                jitter: 1.5,
+                ..ReconnectPolicy::default()
+            },
+            ReconnectPolicy {
+                max_attempts: 0,
+                ..ReconnectPolicy::default()
+            },
+            ReconnectPolicy {
+                base_delay_ms: 60_000,
+                max_delay_ms: 1000,
+                ..ReconnectPolicy::default()
+            },
+            ReconnectPolicy {
+                crash_threshold: 1,
+                ..ReconnectPolicy::default()
+            },
+        ] {
+            assert!(policy.validate().is_err(), "{policy:?}");
+        }
+    }
+}
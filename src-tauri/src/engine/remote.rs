@@ -0,0 +1,347 @@
+//! İstemci modu: yerel motor başlatmadan sistem proxy'sini LAN'daki başka
+//! bir Vexar'a yönlendirme.
+//!
+//! LAN paylaşımı açık bir Vexar, motoru hazır olduğu sürece UDP
+//! `DISCOVERY_PORT` üzerinden izin listesindeki cihazların yayın sorgularına
+//! motor portuyla yanıt verir. İstemci adresi elle girebilir veya bu yanıtlardan seçebilir;
+//! bağlanmadan önce yerel motordaki gibi hazırlık kontrolü yapılır.
+
+use std::collections::HashSet;
+use std::net::{Ipv4Addr, SocketAddr, UdpSocket};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use crate::proxy::ProxyMode;
+use crate::proxy::endpoint::ProxyEndpoint;
+use crate::proxy::servers::SchemeLayout;
+
+use super::config::DEFAULT_PROBE_TARGET;
+
+pub const DISCOVERY_PORT: u16 = 47_823;
+/// Sorgu biçimi değişirse artırılır; farklı sürümler birbirini yok sayar.
+const PROTOCOL: u32 = 1;
+const MAX_DATAGRAM: usize = 1024;
+/// Yanıtçının `keep_going` kontrol aralığı.
+const RESPONDER_POLL: Duration = Duration::from_millis(500);
+pub const DISCOVERY_TIMEOUT_MIN: Duration = Duration::from_millis(200);
+pub const DISCOVERY_TIMEOUT_MAX: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+#[serde(default)]
+pub struct RemoteConfig {
+    /// Uzak Vexar'ın `host:port` adresi.
+    pub address: String,
+    /// Hazırlık kontrolünde uzak proxy üzerinden CONNECT gönderilecek `host:port`.
+    pub probe_target: String,
+    pub proxy_mode: ProxyMode,
+    /// Uzak Vexar yalnızca HTTP proxy sunduğundan SOCKS şeması seçilemez.
+    pub scheme_layout: SchemeLayout,
+}
+
+impl Default for RemoteConfig {
+    fn default() -> Self {
+        Self {
+            address: String::new(),
+            probe_target: DEFAULT_PROBE_TARGET.to_string(),
+            proxy_mode: ProxyMode::Global,
+            scheme_layout: SchemeLayout::Single,
+        }
+    }
+}
+
+impl RemoteConfig {
+    pub fn endpoint(&self) -> Result<ProxyEndpoint, String> {
+        super::readiness::validate_target(&self.probe_target)?;
+        if self.scheme_layout.needs_socks() {
+            return Err("Uzak Vexar için SOCKS şeması kullanılamaz.".to_string());
+        }
+        ProxyEndpoint::parse(&self.address)
+    }
+}
+
+/// Keşifte bulunan bir Vexar.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct DiscoveredEngine {
+    pub name: String,
+    /// `connect_remote` için `host:port`.
+    pub address: String,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Message {
+    Query {
+        protocol: u32,
+        instance: u64,
+    },
+    Announce {
+        protocol: u32,
+        instance: u64,
+        name: String,
+        port: u16,
+    },
+}
+
+impl Message {
+    fn encode(&self) -> Vec<u8> {
+        serde_json::to_vec(self).unwrap_or_default()
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        let message: Self = serde_json::from_slice(bytes).ok()?;
+        let protocol = match &message {
+            Message::Query { protocol, .. } | Message::Announce { protocol, .. } => *protocol,
+        };
+        (protocol == PROTOCOL).then_some(message)
+    }
+}
+
+/// Bu uygulama sürecinin kimliği; kendi yayınımıza kendimiz yanıt vermeyelim.
+fn instance_id() -> u64 {
+    static ID: OnceLock<u64> = OnceLock::new();
+    *ID.get_or_init(|| {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        nanos ^ (u64::from(std::process::id()) << 32)
+    })
+}
+
+fn host_name() -> String {
+    sysinfo::System::host_name().unwrap_or_else(|| "Vexar".to_string())
+}
+
+/// Yanıtçı soketini açar. Port başka bir Vexar tarafından tutuluyorsa hata döner.
+pub fn bind_responder() -> std::io::Result<UdpSocket> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, DISCOVERY_PORT))?;
+    socket.set_read_timeout(Some(RESPONDER_POLL))?;
+    Ok(socket)
+}
+
+/// `keep_going` false dönene kadar sorgulara motor portuyla yanıt verir.
+/// `admit` false döndüğü adresten gelen sorgu yanıtsız kalır.
+pub fn serve(
+    socket: &UdpSocket,
+    port: u16,
+    admit: impl Fn(SocketAddr) -> bool,
+    keep_going: impl Fn() -> bool,
+) {
+    serve_as(socket, instance_id(), &host_name(), port, admit, keep_going)
+}
+
+fn serve_as(
+    socket: &UdpSocket,
+    instance: u64,
+    name: &str,
+    port: u16,
+    admit: impl Fn(SocketAddr) -> bool,
+    keep_going: impl Fn() -> bool,
+) {
+    let announce = Message::Announce {
+        protocol: PROTOCOL,
+        instance,
+        name: name.to_string(),
+        port,
+    }
+    .encode();
+    let mut buf = [0u8; MAX_DATAGRAM];
+    while keep_going() {
+        let Ok((len, from)) = socket.recv_from(&mut buf) else {
+            continue;
+        };
+        match Message::decode(&buf[..len]) {
+            Some(Message::Query {
+                instance: asker, ..
+            }) if asker != instance && admit(from) => {
+                let _ = socket.send_to(&announce, from);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// LAN'a yayın sorgusu gönderir ve `timeout` boyunca gelen yanıtları toplar.
+pub fn discover(timeout: Duration) -> Result<Vec<DiscoveredEngine>, String> {
+    if !(DISCOVERY_TIMEOUT_MIN..=DISCOVERY_TIMEOUT_MAX).contains(&timeout) {
+        return Err(format!(
+            "Geçersiz arama süresi: {} ms ({}-{} ms arası olmalı)",
+            timeout.as_millis(),
+            DISCOVERY_TIMEOUT_MIN.as_millis(),
+            DISCOVERY_TIMEOUT_MAX.as_millis()
+        ));
+    }
+    discover_on(
+        SocketAddr::from((Ipv4Addr::BROADCAST, DISCOVERY_PORT)),
+        instance_id(),
+        timeout,
+    )
+}
+
+fn discover_on(
+    target: SocketAddr,
+    instance: u64,
+    timeout: Duration,
+) -> Result<Vec<DiscoveredEngine>, String> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+        .map_err(|e| format!("Keşif soketi açılamadı: {e}"))?;
+    socket
+        .set_broadcast(true)
+        .map_err(|e| format!("Keşif soketi açılamadı: {e}"))?;
+    let query = Message::Query {
+        protocol: PROTOCOL,
+        instance,
+    };
+    socket
+        .send_to(&query.encode(), target)
+        .map_err(|e| format!("Keşif sorgusu gönderilemedi: {e}"))?;
+
+    let deadline = Instant::now() + timeout;
+    let mut seen = HashSet::new();
+    let mut found = Vec::new();
+    let mut buf = [0u8; MAX_DATAGRAM];
+    loop {
+        let left = deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            break;
+        }
+        let _ = socket.set_read_timeout(Some(left));
+        let Ok((len, from)) = socket.recv_from(&mut buf) else {
+            continue;
+        };
+        let Some(Message::Announce {
+            instance: responder,
+            name,
+            port,
+            ..
+        }) = Message::decode(&buf[..len])
+        else {
+            continue;
+        };
+        if responder == instance {
+            continue;
+        }
+        // Adres, yanıtın geldiği IP'dir; yanıt içeriğindeki adrese güvenilmez
+        let Ok(endpoint) = ProxyEndpoint::new(&from.ip().to_string(), port) else {
+            continue;
+        };
+        let address = endpoint.to_string();
+        if seen.insert(address.clone()) {
+            found.push(DiscoveredEngine { name, address });
+        }
+    }
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn validates_remote_config() {
+        let config = RemoteConfig {
+            address: "192.168.1.20:8080".to_string(),
+            ..RemoteConfig::default()
+        };
+        assert_eq!(config.endpoint().unwrap().to_string(), "192.168.1.20:8080");
+        assert!(RemoteConfig::default().endpoint().is_err());
+        assert!(
+            RemoteConfig {
+                scheme_layout: SchemeLayout::WebSocks,
+                ..config.clone()
+            }
+            .endpoint()
+            .is_err()
+        );
+        let config = RemoteConfig {
+            probe_target: "yok".to_string(),
+            ..config
+        };
+        assert!(config.endpoint().is_err());
+    }
+
+    #[test]
+    fn ignores_foreign_datagrams() {
+        let query = Message::Query {
+            protocol: PROTOCOL,
+            instance: 7,
+        };
+        assert_eq!(Message::decode(&query.encode()), Some(query));
+        assert_eq!(
+            Message::decode(br#"{"kind":"query","protocol":99,"instance":7}"#),
+            None
+        );
+        assert_eq!(Message::decode(b"M-SEARCH * HTTP/1.1"), None);
+    }
+
+    #[test]
+    fn discovers_responder_over_loopback() {
+        let socket = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        socket.set_read_timeout(Some(RESPONDER_POLL)).unwrap();
+        let target = socket.local_addr().unwrap();
+        let running = Arc::new(AtomicBool::new(true));
+        let responder = {
+            let running = running.clone();
+            std::thread::spawn(move || {
+                serve_as(
+                    &socket,
+                    1,
+                    "ofis-pc",
+                    8081,
+                    |from| from.ip().is_loopback(),
+                    || running.load(Ordering::SeqCst),
+                )
+            })
+        };
+
+        let found = discover_on(target, 2, Duration::from_millis(500)).unwrap();
+        assert_eq!(
+            found,
+            [DiscoveredEngine {
+                name: "ofis-pc".to_string(),
+                address: "127.0.0.1:8081".to_string(),
+            }]
+        );
+        // Kendi sorgumuza yanıt verilmez
+        assert!(
+            discover_on(target, 1, Duration::from_millis(300))
+                .unwrap()
+                .is_empty()
+        );
+
+        running.store(false, Ordering::SeqCst);
+        responder.join().unwrap();
+    }
+
+    #[test]
+    fn ignores_queries_from_outside_allow_list() {
+        let socket = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        socket.set_read_timeout(Some(RESPONDER_POLL)).unwrap();
+        let target = socket.local_addr().unwrap();
+        let running = Arc::new(AtomicBool::new(true));
+        let responder = {
+            let running = running.clone();
+            std::thread::spawn(move || {
+                serve_as(
+                    &socket,
+                    1,
+                    "ofis-pc",
+                    8081,
+                    |_| false,
+                    || running.load(Ordering::SeqCst),
+                )
+            })
+        };
+
+        assert!(
+            discover_on(target, 2, Duration::from_millis(300))
+                .unwrap()
+                .is_empty()
+        );
+
+        running.store(false, Ordering::SeqCst);
+        responder.join().unwrap();
+    }
+}
@@ -0,0 +1,320 @@
+//! LAN paylaşımında bant genişliği sınırlama.
+//!
+//! Her yön için bir token bucket kullanılır: kova saniyede `rate` bayt
+//! dolar ve kısa patlamalar için sınırlı bir kapasitesi vardır. Aktarım
+//! thread'i okuduğu baytları bildirir, kova borca girerse borç kapanana
+//! kadar bekler. Toplam sınır tüm cihazların ortak kovasıyla, cihaz sınırı
+//! o cihazın kendi kovasıyla uygulanır; ikisinden uzun olan bekleme geçerlidir.
+//! Bu modül adres türlerini bilmez; hangi bağlantıların sınırlanacağına
+//! ağ geçidi karar verir.
+
+use std::collections::{BTreeMap, HashMap};
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::reconnect::{Clock, SystemClock};
+
+/// Kova en az bu kadar süreyi karşılayacak patlamaya izin verir.
+const BURST: Duration = Duration::from_millis(250);
+/// Küçük sınırlarda bile bir okuma tamponu beklemeden geçebilsin.
+const MIN_CAPACITY: f64 = 16.0 * 1024.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Direction {
+    /// Cihazdan motora.
+    Upload,
+    /// Motordan cihaza.
+    Download,
+}
+
+/// Bayt/saniye cinsinden sınırlar; boş yön sınırsızdır.
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct RateLimit {
+    pub upload: Option<u64>,
+    pub download: Option<u64>,
+}
+
+impl RateLimit {
+    pub fn is_unlimited(&self) -> bool {
+        self.upload.is_none() && self.download.is_none()
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.upload == Some(0) || self.download == Some(0) {
+            return Err("Hız sınırı sıfırdan büyük olmalı.".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ShapingSettings {
+    /// Tüm cihazların toplam sınırı.
+    pub global: RateLimit,
+    /// Cihaza özel sınırlar.
+    pub clients: BTreeMap<IpAddr, RateLimit>,
+    /// Bu makinenin kendi trafiği de sınırlanır; varsayılan olarak muaftır.
+    pub shape_loopback: bool,
+}
+
+impl ShapingSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        self.global.validate()?;
+        self.clients.values().try_for_each(RateLimit::validate)
+    }
+}
+
+pub struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    /// Negatifse kova borçtadır.
+    tokens: f64,
+    last: Instant,
+}
+
+impl TokenBucket {
+    /// Kova dolu başlar.
+    pub fn new(rate: u64, now: Instant) -> Self {
+        let rate = rate as f64;
+        let capacity = (rate * BURST.as_secs_f64()).max(MIN_CAPACITY);
+        Self {
+            rate,
+            capacity,
+            tokens: capacity,
+            last: now,
+        }
+    }
+
+    /// `bytes` baytı kovadan düşer ve gönderimden önce beklenmesi gereken
+    /// süreyi döndürür. Eş zamanlı bağlantılar borcu paylaştığı için toplam
+    /// hız `rate` etrafında kalır.
+    pub fn take(&mut self, bytes: usize, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+        self.last = now.max(self.last);
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.tokens -= bytes as f64;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rate)
+        }
+    }
+}
+
+struct Buckets {
+    upload: Option<TokenBucket>,
+    download: Option<TokenBucket>,
+}
+
+impl Buckets {
+    fn new(limit: &RateLimit, now: Instant) -> Self {
+        Self {
+            upload: limit.upload.map(|rate| TokenBucket::new(rate, now)),
+            download: limit.download.map(|rate| TokenBucket::new(rate, now)),
+        }
+    }
+
+    fn take(&mut self, direction: Direction, bytes: usize, now: Instant) -> Duration {
+        let bucket = match direction {
+            Direction::Upload => &mut self.upload,
+            Direction::Download => &mut self.download,
+        };
+        bucket
+            .as_mut()
+            .map_or(Duration::ZERO, |bucket| bucket.take(bytes, now))
+    }
+}
+
+struct State {
+    settings: ShapingSettings,
+    global: Buckets,
+    clients: HashMap<IpAddr, Buckets>,
+}
+
+pub struct Shaper<C: Clock = SystemClock> {
+    clock: C,
+    state: Mutex<State>,
+}
+
+impl Shaper<SystemClock> {
+    pub fn new(settings: ShapingSettings) -> Self {
+        Self::with_clock(settings, SystemClock)
+    }
+}
+
+impl<C: Clock> Shaper<C> {
+    pub fn with_clock(settings: ShapingSettings, clock: C) -> Self {
+        let global = Buckets::new(&settings.global, clock.now());
+        Self {
+            clock,
+            state: Mutex::new(State {
+                settings,
+                global,
+                clients: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Yeni sınırlar hemen geçerlidir; kovalar dolu olarak yeniden başlar.
+    pub fn set_settings(&self, settings: ShapingSettings) {
+        let mut state = self.state.lock().unwrap();
+        state.global = Buckets::new(&settings.global, self.clock.now());
+        state.clients.clear();
+        state.settings = settings;
+    }
+
+    /// `ip` cihazının `bytes` baytı için beklenecek süre.
+    pub fn delay(&self, ip: IpAddr, direction: Direction, bytes: usize) -> Duration {
+        let now = self.clock.now();
+        let mut state = self.state.lock().unwrap();
+        let State {
+            settings,
+            global,
+            clients,
+        } = &mut *state;
+        let global = global.take(direction, bytes, now);
+        let client = match settings.clients.get(&ip) {
+            Some(limit) => clients
+                .entry(ip)
+                .or_insert_with(|| Buckets::new(limit, now))
+                .take(direction, bytes, now),
+            None => Duration::ZERO,
+        };
+        global.max(client)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::reconnect::ManualClock;
+
+    /// `total` baytı `chunk`'lar hâlinde gönderir ve beklemeleri uygular; geçen süre döner.
+    fn transfer(
+        shaper: &Shaper<ManualClock>,
+        clock: &ManualClock,
+        ip: IpAddr,
+        direction: Direction,
+        total: usize,
+        chunk: usize,
+    ) -> Duration {
+        let start = clock.now();
+        let mut sent = 0;
+        while sent < total {
+            clock.advance(shaper.delay(ip, direction, chunk));
+            sent += chunk;
+        }
+        clock.now() - start
+    }
+
+    fn assert_close(actual: Duration, expected: Duration) {
+        let error = (actual.as_secs_f64() - expected.as_secs_f64()).abs();
+        assert!(
+            error <= expected.as_secs_f64() * 0.01,
+            "beklenen {expected:?}, ölçülen {actual:?}"
+        );
+    }
+
+    #[test]
+    fn bucket_allows_burst_then_holds_rate() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(100_000, now);
+        // 250 ms'lik patlama beklemeden geçer
+        assert_eq!(bucket.take(25_000, now), Duration::ZERO);
+        // Sonraki 10 KB ancak 100 ms sonra gönderilebilir
+        assert_eq!(bucket.take(10_000, now), Duration::from_millis(100));
+        // Uzun bekleme kapasiteyi aşacak kadar biriktirmez
+        let later = now + Duration::from_secs(60);
+        assert_eq!(bucket.take(25_000, later), Duration::ZERO);
+        assert!(bucket.take(1, later) > Duration::ZERO);
+    }
+
+    #[test]
+    fn shapes_client_to_its_own_rate() {
+        let clock = ManualClock::new();
+        let console: IpAddr = "192.168.1.30".parse().unwrap();
+        let phone: IpAddr = "192.168.1.20".parse().unwrap();
+        let settings = ShapingSettings {
+            clients: BTreeMap::from([(
+                console,
+                RateLimit {
+                    upload: None,
+                    download: Some(200_000),
+                },
+            )]),
+            ..Default::default()
+        };
+        let shaper = Shaper::with_clock(settings, clock.clone());
+
+        // 2 MB / 200 KB/s, ilk 50 KB patlamadan
+        let elapsed = transfer(
+            &shaper,
+            &clock,
+            console,
+            Direction::Download,
+            2_000_000,
+            8192,
+        );
+        assert_close(elapsed, Duration::from_secs_f64(1_950_000.0 / 200_000.0));
+
+        // Yükleme ve diğer cihaz sınırsız
+        assert_eq!(
+            transfer(&shaper, &clock, console, Direction::Upload, 2_000_000, 8192),
+            Duration::ZERO
+        );
+        assert_eq!(
+            transfer(&shaper, &clock, phone, Direction::Download, 2_000_000, 8192),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn global_limit_is_shared_and_updates_live() {
+        let clock = ManualClock::new();
+        let a: IpAddr = "192.168.1.20".parse().unwrap();
+        let b: IpAddr = "192.168.1.30".parse().unwrap();
+        let shaper = Shaper::with_clock(
+            ShapingSettings {
+                global: RateLimit {
+                    upload: Some(100_000),
+                    download: None,
+                },
+                ..Default::default()
+            },
+            clock.clone(),
+        );
+
+        // İki cihaz sırayla gönderince toplam hız sınırda kalır
+        let start = clock.now();
+        for _ in 0..100 {
+            clock.advance(shaper.delay(a, Direction::Upload, 5_000));
+            clock.advance(shaper.delay(b, Direction::Upload, 5_000));
+        }
+        assert_close(
+            clock.now() - start,
+            Duration::from_secs_f64((1_000_000.0 - MIN_CAPACITY.max(25_000.0)) / 100_000.0),
+        );
+
+        shaper.set_settings(ShapingSettings::default());
+        assert_eq!(
+            shaper.delay(a, Direction::Upload, 1_000_000),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn rejects_zero_rates() {
+        let settings = ShapingSettings {
+            global: RateLimit {
+                upload: Some(0),
+                download: None,
+            },
+            ..Default::default()
+        };
+        assert!(settings.validate().is_err());
+        assert!(ShapingSettings::default().validate().is_ok());
+    }
+}
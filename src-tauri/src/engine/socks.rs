@@ -0,0 +1,651 @@
+//! Yalnızca SOCKS konuşan uygulamalar için motorun önündeki köprü.
+//!
+//! vexar-proxy bir HTTP proxy'dir; SOCKS4, SOCKS4a ve SOCKS5 (kimlik
+//! doğrulamasız, yalnızca CONNECT) istekleri burada karşılanır ve her biri
+//! motora HTTP CONNECT olarak iletilir. Böylece DPI atlatma SOCKS trafiğine
+//! de uygulanır. WinINet'in `socks=` girdisi SOCKS4 konuştuğu için iki
+//! sürüm de desteklenir. LAN kimlik doğrulaması açıksa diğer cihazlardan
+//! yalnızca kullanıcı adı/parola sunan SOCKS5 istemcileri kabul edilir.
+
+use std::io::{self, Read, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use super::auth::ProxyCredentials;
+use super::lan::{Gate, Meter};
+use super::readiness::{self, ProbeFailure};
+use super::shaping::Direction;
+
+/// Port seçilmediğinde önce denenen standart SOCKS portu.
+pub const DEFAULT_PORT: u16 = 1080;
+/// Kabul döngüsünün durdurma bayrağını kontrol aralığı.
+const ACCEPT_POLL: Duration = Duration::from_millis(100);
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// SOCKS4 kullanıcı adı ve SOCKS4a alan adı için üst sınır.
+const MAX_FIELD: usize = 255;
+
+/// SOCKS dinleyicisini açar. `port` 0 ise önce `DEFAULT_PORT` denenir.
+pub fn bind(addr: IpAddr, port: u16) -> Result<TcpListener, String> {
+    if port != 0 {
+        return TcpListener::bind((addr, port))
+            .map_err(|_| format!("SOCKS portu {port} kullanımda."));
+    }
+    TcpListener::bind((addr, DEFAULT_PORT))
+        .or_else(|_| TcpListener::bind((addr, 0)))
+        .map_err(|e| format!("SOCKS dinleyicisi açılamadı: {e}"))
+}
+
+/// Çalışan köprü; bırakıldığında dinleyici kapanır ve kabul thread'i beklenir.
+/// Açık bağlantılar kendi thread'lerinde bitene kadar sürer.
+pub struct Bridge {
+    port: u16,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Bridge {
+    /// Gelen SOCKS bağlantılarını `upstream`'deki HTTP proxy'ye iletmeye başlar.
+    /// LAN paylaşımında `gate` izin listesinde olmayan istemcileri kapatır.
+    pub fn start(
+        listener: TcpListener,
+        upstream: SocketAddr,
+        gate: Option<Arc<Gate>>,
+    ) -> Result<Self, String> {
+        let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+        listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let flag = stop.clone();
+        let handle =
+            std::thread::spawn(move || accept_loop(listener, upstream, gate.as_deref(), &flag));
+        Ok(Self {
+            port,
+            stop,
+            handle: Some(handle),
+        })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+}
+
+impl Drop for Bridge {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn accept_loop(
+    listener: TcpListener,
+    upstream: SocketAddr,
+    gate: Option<&Gate>,
+    stop: &AtomicBool,
+) {
+    while !stop.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((_, peer)) if gate.is_some_and(|gate| !gate.admit(peer)) => {}
+            Ok((stream, peer)) => {
+                let (credentials, tunnel, meter) = match gate {
+                    Some(gate) => match gate.track(peer, &stream) {
+                        Ok(tunnel) => {
+                            let meter = gate.meter(peer, tunnel.as_ref());
+                            (gate.credentials_for(peer), tunnel, meter)
+                        }
+                        // Atılmış veya sınırına ulaşmış cihaz
+                        Err(_) => continue,
+                    },
+                    None => (None, None, None),
+                };
+                std::thread::spawn(move || {
+                    let _ = handle_client(stream, upstream, credentials.as_ref(), meter);
+                    drop(tunnel);
+                });
+            }
+            // WouldBlock veya geçici bir hata; bir sonraki turda yeniden denenir
+            Err(_) => std::thread::sleep(ACCEPT_POLL),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Version {
+    V4,
+    V5,
+}
+
+/// SOCKS isteğinin sonucu; sürüme göre yanıt koduna çevrilir.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Reply {
+    Granted,
+    Failure,
+    NotAllowed,
+    HostUnreachable,
+    CommandNotSupported,
+    AddressNotSupported,
+}
+
+impl Reply {
+    fn from_failure(failure: &ProbeFailure) -> Self {
+        match failure {
+            ProbeFailure::Rejected(403) => Reply::NotAllowed,
+            ProbeFailure::Rejected(_) => Reply::HostUnreachable,
+            _ => Reply::Failure,
+        }
+    }
+
+    fn encode(self, version: Version) -> Vec<u8> {
+        match version {
+            // VN=0, CD, DSTPORT, DSTIP; SOCKS4'te ayrıntılı hata kodu yok
+            Version::V4 => {
+                let code = if self == Reply::Granted { 90 } else { 91 };
+                vec![0, code, 0, 0, 0, 0, 0, 0]
+            }
+            Version::V5 => {
+                let code = match self {
+                    Reply::Granted => 0x00,
+                    Reply::Failure => 0x01,
+                    Reply::NotAllowed => 0x02,
+                    Reply::HostUnreachable => 0x04,
+                    Reply::CommandNotSupported => 0x07,
+                    Reply::AddressNotSupported => 0x08,
+                };
+                vec![5, code, 0, 1, 0, 0, 0, 0, 0, 0]
+            }
+        }
+    }
+}
+
+fn handle_client(
+    mut client: TcpStream,
+    upstream: SocketAddr,
+    credentials: Option<&ProxyCredentials>,
+    meter: Option<Meter>,
+) -> io::Result<()> {
+    client.set_nonblocking(false)?;
+    client.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    client.set_write_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    let _ = client.set_nodelay(true);
+
+    let (version, target) = match read_request(&mut client, credentials)? {
+        Ok(request) => request,
+        Err((version, reply)) => {
+            client.write_all(&reply.encode(version))?;
+            return Ok(());
+        }
+    };
+
+    let tunnel = match readiness::open_tunnel(upstream, &target, CONNECT_TIMEOUT) {
+        Ok(tunnel) => tunnel,
+        Err(failure) => {
+            client.write_all(&Reply::from_failure(&failure).encode(version))?;
+            return Ok(());
+        }
+    };
+    client.write_all(&Reply::Granted.encode(version))?;
+    if !tunnel.early_data.is_empty() {
+        client.write_all(&tunnel.early_data)?;
+    }
+    relay(client, tunnel.stream, meter)
+}
+
+/// El sıkışmayı okur ve `host:port` hedefini döndürür. İç `Err`, istemciye
+/// yazılacak ret yanıtıdır; dış `Err` bağlantının kendisinin bozulduğunu gösterir.
+/// `credentials` verilmişse istemcinin kimliğini doğrulaması gerekir.
+fn read_request<S: Read + Write>(
+    stream: &mut S,
+    credentials: Option<&ProxyCredentials>,
+) -> io::Result<Result<(Version, String), (Version, Reply)>> {
+    match read_u8(stream)? {
+        4 => read_v4(stream, credentials.is_some()),
+        5 => read_v5(stream, credentials),
+        other => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("desteklenmeyen SOCKS sürümü: {other}"),
+        )),
+    }
+}
+
+fn read_v4<S: Read>(
+    stream: &mut S,
+    needs_auth: bool,
+) -> io::Result<Result<(Version, String), (Version, Reply)>> {
+    let mut head = [0u8; 7];
+    stream.read_exact(&mut head)?;
+    let command = head[0];
+    let port = u16::from_be_bytes([head[1], head[2]]);
+    let ip = Ipv4Addr::new(head[3], head[4], head[5], head[6]);
+    // Kullanıcı adı yok sayılır
+    read_cstring(stream)?;
+
+    // SOCKS4a: 0.0.0.x (x != 0) adresi, alan adının ardından geldiğini belirtir
+    let octets = ip.octets();
+    let host = if octets[..3] == [0, 0, 0] && octets[3] != 0 {
+        match domain(read_cstring(stream)?) {
+            Some(name) => name,
+            None => return Ok(Err((Version::V4, Reply::AddressNotSupported))),
+        }
+    } else {
+        ip.to_string()
+    };
+    // SOCKS4'te parola yok
+    if needs_auth {
+        return Ok(Err((Version::V4, Reply::NotAllowed)));
+    }
+    if command != 1 {
+        return Ok(Err((Version::V4, Reply::CommandNotSupported)));
+    }
+    Ok(Ok((Version::V4, format_target(&host, port))))
+}
+
+fn read_v5<S: Read + Write>(
+    stream: &mut S,
+    credentials: Option<&ProxyCredentials>,
+) -> io::Result<Result<(Version, String), (Version, Reply)>> {
+    let count = read_u8(stream)? as usize;
+    let mut methods = vec![0u8; count];
+    stream.read_exact(&mut methods)?;
+    // 0x00: kimlik doğrulamasız, 0x02: kullanıcı adı/parola
+    let method = if credentials.is_some() { 0x02 } else { 0x00 };
+    if !methods.contains(&method) {
+        // Kabul edilebilir yöntem yok
+        stream.write_all(&[5, 0xFF])?;
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "uygun kimlik doğrulama yöntemi sunulmadı",
+        ));
+    }
+    stream.write_all(&[5, method])?;
+    if let Some(credentials) = credentials
+        && !read_password(stream, credentials)?
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "kimlik doğrulaması başarısız",
+        ));
+    }
+
+    let mut head = [0u8; 4];
+    stream.read_exact(&mut head)?;
+    if head[0] != 5 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "geçersiz SOCKS5 isteği",
+        ));
+    }
+    let host = match head[3] {
+        0x01 => {
+            let mut octets = [0u8; 4];
+            stream.read_exact(&mut octets)?;
+            Ipv4Addr::from(octets).to_string()
+        }
+        0x03 => {
+            let len = read_u8(stream)? as usize;
+            let mut name = vec![0u8; len];
+            stream.read_exact(&mut name)?;
+            match domain(name) {
+                Some(name) => name,
+                None => return Ok(Err((Version::V5, Reply::AddressNotSupported))),
+            }
+        }
+        0x04 => {
+            let mut octets = [0u8; 16];
+            stream.read_exact(&mut octets)?;
+            Ipv6Addr::from(octets).to_string()
+        }
+        _ => return Ok(Err((Version::V5, Reply::AddressNotSupported))),
+    };
+    let mut port = [0u8; 2];
+    stream.read_exact(&mut port)?;
+    if head[1] != 0x01 {
+        return Ok(Err((Version::V5, Reply::CommandNotSupported)));
+    }
+    Ok(Ok((
+        Version::V5,
+        format_target(&host, u16::from_be_bytes(port)),
+    )))
+}
+
+/// RFC 1929 alt görüşmesi; bilgiler eşleşirse true döner.
+fn read_password<S: Read + Write>(
+    stream: &mut S,
+    credentials: &ProxyCredentials,
+) -> io::Result<bool> {
+    if read_u8(stream)? != 1 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "geçersiz kimlik doğrulama isteği",
+        ));
+    }
+    let mut username = vec![0u8; read_u8(stream)? as usize];
+    stream.read_exact(&mut username)?;
+    let mut password = vec![0u8; read_u8(stream)? as usize];
+    stream.read_exact(&mut password)?;
+    let ok = credentials.matches(&username, &password);
+    stream.write_all(&[1, if ok { 0 } else { 1 }])?;
+    Ok(ok)
+}
+
+/// İstemcinin gönderdiği alan adı CONNECT satırına olduğu gibi yazılır; boş,
+/// çok uzun, UTF-8 olmayan ya da boşluk veya kontrol karakteri içeren adlar
+/// isteğe başlık enjekte edebileceği için reddedilir.
+fn domain(name: Vec<u8>) -> Option<String> {
+    let name = String::from_utf8(name).ok()?;
+    let valid = !name.is_empty()
+        && name.len() <= MAX_FIELD
+        && !name.contains(|c: char| c.is_whitespace() || c.is_control());
+    valid.then_some(name)
+}
+
+/// CONNECT hedefi; IPv6 adresleri köşeli parantezle yazılır.
+fn format_target(host: &str, port: u16) -> String {
+    if host.contains(':') {
+        format!("[{host}]:{port}")
+    } else {
+        format!("{host}:{port}")
+    }
+}
+
+fn read_u8<S: Read>(stream: &mut S) -> io::Result<u8> {
+    let mut byte = [0u8; 1];
+    stream.read_exact(&mut byte)?;
+    Ok(byte[0])
+}
+
+fn read_cstring<S: Read>(stream: &mut S) -> io::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    loop {
+        match read_u8(stream)? {
+            0 => return Ok(out),
+            _ if out.len() >= MAX_FIELD => {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "alan çok uzun"));
+            }
+            byte => out.push(byte),
+        }
+    }
+}
+
+/// İki yönde kopyalar; bir taraf kapanınca diğerinin yazma yönü kapatılır.
+/// `meter` verilmişse LAN cihazının bayt sayaçları artırılır ve hız sınırı uygulanır.
+pub(super) fn relay(
+    client: TcpStream,
+    upstream: TcpStream,
+    meter: Option<Meter>,
+) -> io::Result<()> {
+    for stream in [&client, &upstream] {
+        stream.set_read_timeout(None)?;
+        stream.set_write_timeout(None)?;
+    }
+    let mut client_read = Counted {
+        inner: client.try_clone()?,
+        meter: meter.clone(),
+        direction: Direction::Upload,
+    };
+    let mut upstream_write = upstream.try_clone()?;
+    let outbound = std::thread::spawn(move || {
+        let _ = io::copy(&mut client_read, &mut upstream_write);
+        let _ = upstream_write.shutdown(Shutdown::Write);
+    });
+
+    let mut upstream_read = Counted {
+        inner: upstream,
+        meter,
+        direction: Direction::Download,
+    };
+    let mut client_write = client;
+    let _ = io::copy(&mut upstream_read, &mut client_write);
+    let _ = client_write.shutdown(Shutdown::Write);
+    let _ = outbound.join();
+    Ok(())
+}
+
+/// Okunan baytları bildiren sarmalayıcı; hız sınırı aşıldıysa okunan veri
+/// beklemeden sonra döner.
+struct Counted {
+    inner: TcpStream,
+    meter: Option<Meter>,
+    direction: Direction,
+}
+
+impl Read for Counted {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        if let Some(meter) = &self.meter {
+            meter.record(self.direction, n);
+        }
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// CONNECT isteğini kaydeden, 200 dönen ve ardından yankı yapan sahte motor.
+    fn stand_in(status: &'static str) -> (SocketAddr, std::sync::mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = [0u8; 512];
+            let n = stream.read(&mut buf).unwrap();
+            let request = String::from_utf8_lossy(&buf[..n]).into_owned();
+            let _ = tx.send(request.lines().next().unwrap_or_default().to_string());
+            stream
+                .write_all(format!("HTTP/1.1 {status}\r\n\r\n").as_bytes())
+                .unwrap();
+            let _ = io::copy(&mut stream.try_clone().unwrap(), &mut stream);
+        });
+        (addr, rx)
+    }
+
+    fn bridge(upstream: SocketAddr) -> Bridge {
+        let listener = bind(IpAddr::from([127, 0, 0, 1]), 0).unwrap();
+        Bridge::start(listener, upstream, None).unwrap()
+    }
+
+    fn connect(bridge: &Bridge) -> TcpStream {
+        let stream = TcpStream::connect(("127.0.0.1", bridge.port())).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(2)))
+            .unwrap();
+        stream
+    }
+
+    fn echo(stream: &mut TcpStream) {
+        stream.write_all(b"ping").unwrap();
+        let mut reply = [0u8; 4];
+        stream.read_exact(&mut reply).unwrap();
+        assert_eq!(&reply, b"ping");
+    }
+
+    #[test]
+    fn socks5_domain_goes_through_connect() {
+        let (upstream, requests) = stand_in("200 Connection established");
+        let bridge = bridge(upstream);
+        let mut stream = connect(&bridge);
+
+        stream.write_all(&[5, 1, 0]).unwrap();
+        let mut method = [0u8; 2];
+        stream.read_exact(&mut method).unwrap();
+        assert_eq!(method, [5, 0]);
+
+        let mut request = vec![5, 1, 0, 3, 11];
+        request.extend_from_slice(b"example.com");
+        request.extend_from_slice(&443u16.to_be_bytes());
+        stream.write_all(&request).unwrap();
+        let mut reply = [0u8; 10];
+        stream.read_exact(&mut reply).unwrap();
+        assert_eq!(reply[..2], [5, 0]);
+        assert_eq!(requests.recv().unwrap(), "CONNECT example.com:443 HTTP/1.1");
+        echo(&mut stream);
+    }
+
+    #[test]
+    fn socks4a_and_ipv6_targets() {
+        let (upstream, requests) = stand_in("200 OK");
+        let bridge_v4 = bridge(upstream);
+        let mut stream = connect(&bridge_v4);
+        let mut request = vec![4, 1];
+        request.extend_from_slice(&443u16.to_be_bytes());
+        request.extend_from_slice(&[0, 0, 0, 1]);
+        request.extend_from_slice(b"user\0example.org\0");
+        stream.write_all(&request).unwrap();
+        let mut reply = [0u8; 8];
+        stream.read_exact(&mut reply).unwrap();
+        assert_eq!(reply[..2], [0, 90]);
+        assert_eq!(requests.recv().unwrap(), "CONNECT example.org:443 HTTP/1.1");
+        echo(&mut stream);
+
+        let (upstream, requests) = stand_in("200 OK");
+        let bridge_v6 = bridge(upstream);
+        let mut stream = connect(&bridge_v6);
+        stream.write_all(&[5, 1, 0]).unwrap();
+        stream.read_exact(&mut [0u8; 2]).unwrap();
+        let mut request = vec![5, 1, 0, 4];
+        request.extend_from_slice(&Ipv6Addr::LOCALHOST.octets());
+        request.extend_from_slice(&8443u16.to_be_bytes());
+        stream.write_all(&request).unwrap();
+        stream.read_exact(&mut [0u8; 10]).unwrap();
+        assert_eq!(requests.recv().unwrap(), "CONNECT [::1]:8443 HTTP/1.1");
+    }
+
+    #[test]
+    fn maps_refusals_to_socks_replies() {
+        let (upstream, _requests) = stand_in("502 Bad Gateway");
+        let bridge = bridge(upstream);
+        let mut stream = connect(&bridge);
+        stream.write_all(&[5, 1, 0]).unwrap();
+        stream.read_exact(&mut [0u8; 2]).unwrap();
+        stream.write_all(&[5, 1, 0, 1, 10, 0, 0, 1, 0, 80]).unwrap();
+        let mut reply = [0u8; 10];
+        stream.read_exact(&mut reply).unwrap();
+        assert_eq!(reply[1], 0x04);
+
+        // BIND desteklenmez; motora hiç gidilmez
+        let mut stream = connect(&bridge);
+        stream.write_all(&[4, 2, 0, 80, 10, 0, 0, 1, 0]).unwrap();
+        let mut reply = [0u8; 8];
+        stream.read_exact(&mut reply).unwrap();
+        assert_eq!(reply[..2], [0, 91]);
+    }
+
+    /// Okunacak baytları ve yazılan yanıtı tutan bellek içi akış.
+    struct Duplex<'a> {
+        input: &'a [u8],
+        output: Vec<u8>,
+    }
+
+    impl Read for Duplex<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.input.read(buf)
+        }
+    }
+
+    impl Write for Duplex<'_> {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.output.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn requires_password_when_credentials_are_set() {
+        let credentials = ProxyCredentials {
+            username: "vexar-abc".to_string(),
+            password: "s3cret".to_string(),
+        };
+        let handshake = |auth: &[u8]| {
+            let mut input = vec![5, 2, 0, 2];
+            input.extend_from_slice(auth);
+            input.extend_from_slice(&[5, 1, 0, 1, 10, 0, 0, 1, 0, 80]);
+            let mut stream = Duplex {
+                input: &input,
+                output: Vec::new(),
+            };
+            let result = read_request(&mut stream, Some(&credentials));
+            (result.ok(), stream.output)
+        };
+
+        let (request, output) = handshake(b"\x01\x09vexar-abc\x06s3cret");
+        assert_eq!(output, [5, 2, 1, 0]);
+        assert_eq!(request, Some(Ok((Version::V5, "10.0.0.1:80".to_string()))));
+
+        let (request, output) = handshake(b"\x01\x09vexar-abc\x05wrong");
+        assert_eq!(output, [5, 2, 1, 1]);
+        assert_eq!(request, None);
+
+        // Parolasız yöntem sunan istemci reddedilir
+        let mut stream = Duplex {
+            input: &[5, 1, 0],
+            output: Vec::new(),
+        };
+        assert!(read_request(&mut stream, Some(&credentials)).is_err());
+        assert_eq!(stream.output, [5, 0xFF]);
+
+        // SOCKS4'te parola gönderilemez
+        let mut stream = Duplex {
+            input: &[4, 1, 0, 80, 10, 0, 0, 1, 0],
+            output: Vec::new(),
+        };
+        assert_eq!(
+            read_request(&mut stream, Some(&credentials)).unwrap(),
+            Err((Version::V4, Reply::NotAllowed))
+        );
+    }
+
+    #[test]
+    fn rejects_domains_that_could_inject_headers() {
+        let request = |input: &[u8]| {
+            let mut stream = Duplex {
+                input,
+                output: Vec::new(),
+            };
+            read_request(&mut stream, None).unwrap()
+        };
+
+        let mut socks5 = vec![5, 1, 0, 5, 1, 0, 3, 20];
+        socks5.extend_from_slice(b"a.com\r\nX-Injected: 1");
+        socks5.extend_from_slice(&443u16.to_be_bytes());
+        assert_eq!(
+            request(&socks5),
+            Err((Version::V5, Reply::AddressNotSupported))
+        );
+
+        let mut socks4a = vec![4, 1, 1, 187, 0, 0, 0, 1, 0];
+        socks4a.extend_from_slice(b"a.com\r\nX-Injected: 1\0");
+        assert_eq!(
+            request(&socks4a),
+            Err((Version::V4, Reply::AddressNotSupported))
+        );
+        assert_eq!(Reply::AddressNotSupported.encode(Version::V4)[..2], [0, 91]);
+
+        // Boş ad da geçersizdir
+        assert_eq!(
+            request(&[5, 1, 0, 5, 1, 0, 3, 0, 1, 187]),
+            Err((Version::V5, Reply::AddressNotSupported))
+        );
+        assert_eq!(domain(vec![b'a'; 256]), None);
+        assert_eq!(
+            domain(b"example.com".to_vec()).as_deref(),
+            Some("example.com")
+        );
+    }
+
+    #[test]
+    fn fixed_port_in_use_is_reported() {
+        let taken = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = taken.local_addr().unwrap().port();
+        assert!(bind(IpAddr::from([127, 0, 0, 1]), port).is_err());
+    }
+}
@@ -0,0 +1,41 @@
+//! Ayar, kayıt ve yapılandırma dosyalarının ortak yazma yardımcısı.
+//!
+//! İçerik önce aynı dizindeki geçici bir dosyaya yazılır, sonra hedefin
+//! üzerine taşınır; yazma yarıda kalsa da hedefte yarım dosya kalmaz.
+
+use std::path::Path;
+
+/// Gerekirse dizini oluşturur ve `content`'i `path`'e bütün olarak yazar.
+pub fn write_atomic(path: &Path, content: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("{} oluşturulamadı: {e}", dir.display()))?;
+    }
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".vexar-tmp");
+    std::fs::write(&tmp, content).map_err(|e| format!("{} yazılamadı: {e}", path.display()))?;
+    std::fs::rename(&tmp, path).map_err(|e| format!("{} yazılamadı: {e}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn creates_directory_and_replaces_content() {
+        let dir = std::env::temp_dir().join(format!("vexar-files-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join("nested").join("settings.json");
+
+        write_atomic(&path, "ilk").unwrap();
+        write_atomic(&path, "ikinci").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "ikinci");
+        // Geçici dosya geride kalmaz
+        assert_eq!(
+            std::fs::read_dir(path.parent().unwrap()).unwrap().count(),
+            1
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
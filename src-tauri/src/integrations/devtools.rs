@@ -0,0 +1,152 @@
+//! git, npm, pip ve kabuk ortamı için proxy ayarları.
+//!
+//! Bu araçlar WinINet/masaüstü proxy'sini okumaz; bağlanınca kendi
+//! yapılandırma dosyalarına Vexar'ın adresi yazılır. `git config` veya
+//! `npm config` çalıştırılmaz, dosyalar doğrudan düzenlenir; araç kurulu
+//! olmasa da ayar hazır olur ve testler geçici bir ev dizininde çalışır.
+
+use std::path::{Path, PathBuf};
+
+use super::edit::{self, Format};
+use super::{FileChange, KeyChange, Tool, Undo};
+use crate::proxy::bypass::BypassList;
+use crate::proxy::endpoint::ProxyEndpoint;
+
+/// `. <config_dir>/dev-proxy.env` ile kabuğa yüklenen dosya.
+pub const ENV_FILE: &str = "dev-proxy.env";
+
+/// Araçların kullanıcı düzeyindeki yapılandırma dosyaları.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ToolPaths {
+    pub git: PathBuf,
+    pub npm: PathBuf,
+    pub pip: PathBuf,
+    pub shell_env: PathBuf,
+    /// Firefox'un `profiles.ini` arayabileceği dizinler.
+    pub firefox: Vec<PathBuf>,
+}
+
+impl ToolPaths {
+    /// Araç dosyaları `home` altında, üretilen ortam dosyası `config_dir`'de.
+    pub fn new(home: &Path, config_dir: &Path) -> Self {
+        Self {
+            git: home.join(".gitconfig"),
+            npm: home.join(".npmrc"),
+            pip: pip_config(home),
+            shell_env: config_dir.join(ENV_FILE),
+            firefox: super::firefox::profile_roots(home),
+        }
+    }
+}
+
+#[cfg(windows)]
+fn pip_config(home: &Path) -> PathBuf {
+    std::env::var_os("APPDATA")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join("AppData").join("Roaming"))
+        .join("pip")
+        .join("pip.ini")
+}
+
+#[cfg(not(windows))]
+fn pip_config(home: &Path) -> PathBuf {
+    home.join(".config").join("pip").join("pip.conf")
+}
+
+/// Araçlara yazılan değerler.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ToolProxy {
+    /// `http://host:port`
+    pub url: String,
+    pub endpoint: ProxyEndpoint,
+    /// Bypass listesinden türetilen `NO_PROXY`.
+    pub no_proxy: String,
+}
+
+impl ToolProxy {
+    pub fn new(endpoint: &ProxyEndpoint, bypass: &BypassList) -> Self {
+        Self {
+            url: format!("http://{endpoint}"),
+            endpoint: endpoint.clone(),
+            no_proxy: bypass.to_no_proxy(),
+        }
+    }
+}
+
+/// Bölüm, anahtar ve yazılacak değer.
+type KeyEdit<'a> = (Option<&'a str>, &'a str, &'a str);
+
+/// Aracın dosyasına yazılacak içeriği ve geri alma bilgisini hazırlar;
+/// dosyaya dokunmaz.
+pub fn plan(tool: Tool, paths: &ToolPaths, proxy: &ToolProxy) -> Result<FileChange, String> {
+    let (path, format, keys): (&Path, Format, Vec<KeyEdit>) = match tool {
+        Tool::ShellEnv => {
+            return Ok(FileChange {
+                tool,
+                path: paths.shell_env.clone(),
+                previous: super::read_optional(&paths.shell_env)?,
+                written: env_file(proxy),
+                undo: Undo::Replace,
+            });
+        }
+        Tool::Firefox => return Err("Firefox profilleri ayrı planlanır.".to_string()),
+        Tool::Git => (
+            &paths.git,
+            Format::GitConfig,
+            vec![(Some("http"), "proxy", &proxy.url)],
+        ),
+        Tool::Npm => {
+            let mut keys = vec![
+                (None, "proxy", proxy.url.as_str()),
+                (None, "https-proxy", &proxy.url),
+            ];
+            if !proxy.no_proxy.is_empty() {
+                keys.push((None, "noproxy", &proxy.no_proxy));
+            }
+            (&paths.npm, Format::Flat, keys)
+        }
+        Tool::Pip => (
+            &paths.pip,
+            Format::Ini,
+            vec![(Some("global"), "proxy", &proxy.url)],
+        ),
+    };
+    let path = path.to_path_buf();
+    let previous = super::read_optional(&path)?;
+
+    let mut content = previous.clone().unwrap_or_default();
+    let mut changes = Vec::new();
+    for (section, key, value) in keys {
+        changes.push(KeyChange {
+            section: section.map(ToString::to_string),
+            key: key.to_string(),
+            previous: edit::get(&content, section, key),
+        });
+        content = edit::set(&content, format, section, key, Some(value));
+    }
+    Ok(FileChange {
+        tool,
+        path,
+        previous,
+        written: content,
+        undo: Undo::Keys {
+            format,
+            keys: changes,
+        },
+    })
+}
+
+fn env_file(proxy: &ToolProxy) -> String {
+    let mut content =
+        String::from("# Vexar tarafından yönetilir; bağlantı kesilince geri alınır.\n");
+    for (names, value) in [
+        (["HTTP_PROXY", "http_proxy"], &proxy.url),
+        (["HTTPS_PROXY", "https_proxy"], &proxy.url),
+        (["NO_PROXY", "no_proxy"], &proxy.no_proxy),
+    ] {
+        for name in names {
+            content.push_str(&format!("export {name}=\"{value}\"\n"));
+        }
+    }
+    content
+}
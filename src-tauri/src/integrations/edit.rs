@@ -0,0 +1,197 @@
+//! Yapılandırma dosyalarında tek anahtar düzenleme.
+//!
+//! Dosyanın geri kalanı (yorumlar, sıralama, boş satırlar) olduğu gibi
+//! bırakılır; böylece yalnızca Vexar'ın değiştirdiği satırlar geri alınır.
+
+/// Dosyanın sözdizimi.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Format {
+    /// `~/.gitconfig`: `[http]` altında sekmeyle girintili `proxy = ...`.
+    GitConfig,
+    /// `pip.conf`: `[global]` altında `proxy = ...`.
+    Ini,
+    /// `.npmrc`: bölümsüz `proxy=...`.
+    Flat,
+}
+
+impl Format {
+    fn line(self, key: &str, value: &str) -> String {
+        match self {
+            Format::GitConfig => format!("\t{key} = {value}"),
+            Format::Ini => format!("{key} = {value}"),
+            Format::Flat => format!("{key}={value}"),
+        }
+    }
+}
+
+/// `section` altındaki `key` değerini döndürür; `None` bölümsüz anahtar demektir.
+pub fn get(content: &str, section: Option<&str>, key: &str) -> Option<String> {
+    let mut current: Option<String> = None;
+    for line in content.lines() {
+        if let Some(name) = section_name(line) {
+            current = Some(name);
+            continue;
+        }
+        if current.as_deref().map(str::to_ascii_lowercase) != section.map(str::to_ascii_lowercase) {
+            continue;
+        }
+        if let Some((k, v)) = key_value(line)
+            && k.eq_ignore_ascii_case(key)
+        {
+            return Some(v.to_string());
+        }
+    }
+    None
+}
+
+/// Anahtarı yazar (`value` `None` ise siler) ve yeni içeriği döndürür.
+/// Anahtar birden fazla kez geçiyorsa ilki güncellenir, diğerleri silinir.
+pub fn set(
+    content: &str,
+    format: Format,
+    section: Option<&str>,
+    key: &str,
+    value: Option<&str>,
+) -> String {
+    let mut lines: Vec<String> = content.lines().map(ToString::to_string).collect();
+    let wanted = section.map(str::to_ascii_lowercase);
+    let mut current: Option<String> = None;
+    let mut section_found = section.is_none();
+    // Bölümün son boş olmayan satırı; yeni anahtar bunun arkasına eklenir
+    let mut insert_at = None;
+    let mut written = false;
+    let mut i = 0;
+    while i < lines.len() {
+        if let Some(name) = section_name(&lines[i]) {
+            current = Some(name.to_ascii_lowercase());
+            if current == wanted {
+                section_found = true;
+                insert_at = Some(i + 1);
+            }
+            i += 1;
+            continue;
+        }
+        if current != wanted {
+            i += 1;
+            continue;
+        }
+        let matches = key_value(&lines[i]).is_some_and(|(k, _)| k.eq_ignore_ascii_case(key));
+        match (matches, value) {
+            (true, Some(value)) if !written => {
+                lines[i] = format.line(key, value);
+                written = true;
+            }
+            (true, _) => {
+                lines.remove(i);
+                continue;
+            }
+            (false, _) if !lines[i].trim().is_empty() => insert_at = Some(i + 1),
+            _ => {}
+        }
+        i += 1;
+    }
+
+    if let (Some(value), false) = (value, written) {
+        let line = format.line(key, value);
+        match (section, section_found) {
+            (Some(_), true) => lines.insert(insert_at.unwrap_or(lines.len()), line),
+            (None, _) => {
+                // Bölümsüz anahtarlar ilk bölüm başlığından önce olmalı
+                let at = lines
+                    .iter()
+                    .position(|l| section_name(l).is_some())
+                    .unwrap_or(lines.len());
+                lines.insert(at, line);
+            }
+            (Some(section), false) => {
+                if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+                    lines.push(String::new());
+                }
+                lines.push(format!("[{section}]"));
+                lines.push(line);
+            }
+        }
+    }
+
+    let mut out = lines.join("\n");
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out
+}
+
+fn section_name(line: &str) -> Option<String> {
+    let line = line.trim();
+    let inner = line.strip_prefix('[')?.strip_suffix(']')?;
+    Some(inner.trim().to_string())
+}
+
+fn key_value(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim();
+    if line.starts_with('#') || line.starts_with(';') {
+        return None;
+    }
+    let (key, value) = line.split_once('=')?;
+    Some((key.trim(), value.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn updates_existing_key_in_place() {
+        let content =
+            "[user]\n\tname = Ada\n[http]\n\tsslVerify = true\n\tProxy = http://corp:3128\n";
+        let updated = set(
+            content,
+            Format::GitConfig,
+            Some("http"),
+            "proxy",
+            Some("http://127.0.0.1:8080"),
+        );
+        assert_eq!(
+            updated,
+            "[user]\n\tname = Ada\n[http]\n\tsslVerify = true\n\tproxy = http://127.0.0.1:8080\n"
+        );
+        assert_eq!(
+            get(&updated, Some("HTTP"), "proxy").as_deref(),
+            Some("http://127.0.0.1:8080")
+        );
+    }
+
+    #[test]
+    fn adds_missing_section_and_removes_key() {
+        let content = "# pip\n[install]\nno-cache-dir = true\n";
+        let updated = set(
+            content,
+            Format::Ini,
+            Some("global"),
+            "proxy",
+            Some("http://h:1"),
+        );
+        assert_eq!(
+            updated,
+            "# pip\n[install]\nno-cache-dir = true\n\n[global]\nproxy = http://h:1\n"
+        );
+        let removed = set(&updated, Format::Ini, Some("global"), "proxy", None);
+        assert_eq!(get(&removed, Some("global"), "proxy"), None);
+        assert!(removed.starts_with(content));
+    }
+
+    #[test]
+    fn flat_keys_stay_before_sections() {
+        let content = "registry=https://registry.npmjs.org/\n; yorum\n";
+        let updated = set(content, Format::Flat, None, "proxy", Some("http://h:1"));
+        assert_eq!(
+            updated,
+            "registry=https://registry.npmjs.org/\n; yorum\nproxy=http://h:1\n"
+        );
+        assert_eq!(set("", Format::Flat, None, "proxy", None), "");
+        assert_eq!(
+            set("[scope]\na=b\n", Format::Flat, None, "proxy", Some("x")),
+            "proxy=x\n[scope]\na=b\n"
+        );
+    }
+}
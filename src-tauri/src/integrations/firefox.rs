@@ -0,0 +1,410 @@
+//! Firefox profillerinin proxy ayarı.
+//!
+//! Firefox "sistem proxy'sini kullan" seçili değilse kendi ayarlarıyla çalışır.
+//! Profiller `profiles.ini` üzerinden bulunur ve her profilin `user.js`
+//! dosyasının sonuna `network.proxy.*` ayarlarını içeren bir blok eklenir.
+//! Firefox `user.js`'i yalnızca açılışta okur ve değerleri `prefs.js`'e de
+//! kopyalar; bu yüzden bloktan önce `prefs.js`'teki proxy satırları yedeklenir
+//! ve geri almada blokla birlikte onlar da eski haline getirilir.
+
+use std::path::{Path, PathBuf};
+
+use super::devtools::ToolProxy;
+use super::{FileChange, Tool, Undo};
+
+pub const PROFILES_INI: &str = "profiles.ini";
+pub const USER_JS: &str = "user.js";
+pub const PREFS_JS: &str = "prefs.js";
+
+pub const BLOCK_BEGIN: &str = "// >>> Vexar proxy (bağlantı kesilince kaldırılır)";
+pub const BLOCK_END: &str = "// <<< Vexar proxy";
+
+const PROXY_PREF: &str = "user_pref(\"network.proxy.";
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct FirefoxProfile {
+    pub name: String,
+    pub path: PathBuf,
+    /// Firefox'un varsayılan olarak açtığı profil.
+    pub default: bool,
+    /// `user.js` şu anda Vexar bloğunu içeriyor.
+    pub managed: bool,
+}
+
+/// `profiles.ini`'nin aranacağı dizinler.
+#[cfg(windows)]
+pub fn profile_roots(home: &Path) -> Vec<PathBuf> {
+    let appdata = std::env::var_os("APPDATA")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join("AppData").join("Roaming"));
+    vec![appdata.join("Mozilla").join("Firefox")]
+}
+
+#[cfg(target_os = "macos")]
+pub fn profile_roots(home: &Path) -> Vec<PathBuf> {
+    vec![
+        home.join("Library")
+            .join("Application Support")
+            .join("Firefox"),
+    ]
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+pub fn profile_roots(home: &Path) -> Vec<PathBuf> {
+    vec![
+        home.join(".mozilla").join("firefox"),
+        // Snap ve Flatpak paketleri profili kendi dizinlerinde tutar
+        home.join("snap/firefox/common/.mozilla/firefox"),
+        home.join(".var/app/org.mozilla.firefox/.mozilla/firefox"),
+    ]
+}
+
+/// `profiles.ini` içeriğini ayrıştırır; göreli yollar `root`'a göre çözülür.
+/// Yeni Firefox sürümleri varsayılan profili `[Install...]` bölümünde tutar.
+pub fn parse_profiles_ini(content: &str, root: &Path) -> Vec<FirefoxProfile> {
+    let mut sections: Vec<(String, Vec<(String, String)>)> = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            sections.push((name.trim().to_string(), Vec::new()));
+        } else if let (Some((key, value)), Some((_, entries))) =
+            (line.split_once('='), sections.last_mut())
+        {
+            entries.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    let value = |entries: &[(String, String)], key: &str| {
+        entries
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.clone())
+    };
+    let resolve = |path: &str, relative: bool| {
+        let path = Path::new(path);
+        if relative || path.is_relative() {
+            root.join(path)
+        } else {
+            path.to_path_buf()
+        }
+    };
+
+    let mut profiles: Vec<FirefoxProfile> = Vec::new();
+    for (section, entries) in &sections {
+        let lower = section.to_ascii_lowercase();
+        if lower.starts_with("profile") {
+            let Some(path) = value(entries, "Path") else {
+                continue;
+            };
+            let relative = value(entries, "IsRelative").as_deref() == Some("1");
+            let path = resolve(&path, relative);
+            let name = value(entries, "Name").unwrap_or_else(|| dir_name(&path));
+            let default = value(entries, "Default").as_deref() == Some("1");
+            profiles.push(FirefoxProfile {
+                name,
+                path,
+                default,
+                managed: false,
+            });
+        }
+    }
+    for (section, entries) in &sections {
+        if !section.to_ascii_lowercase().starts_with("install") {
+            continue;
+        }
+        let Some(path) = value(entries, "Default") else {
+            continue;
+        };
+        let path = resolve(&path, true);
+        match profiles.iter_mut().find(|p| p.path == path) {
+            Some(profile) => profile.default = true,
+            None => profiles.push(FirefoxProfile {
+                name: dir_name(&path),
+                path,
+                default: true,
+                managed: false,
+            }),
+        }
+    }
+    profiles
+}
+
+fn dir_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Dizini gerçekten var olan profilleri döndürür.
+pub fn discover(roots: &[PathBuf]) -> Vec<FirefoxProfile> {
+    let mut profiles: Vec<FirefoxProfile> = Vec::new();
+    for root in roots {
+        let Ok(content) = std::fs::read_to_string(root.join(PROFILES_INI)) else {
+            continue;
+        };
+        for mut profile in parse_profiles_ini(&content, root) {
+            if !profile.path.is_dir() || profiles.iter().any(|p| p.path == profile.path) {
+                continue;
+            }
+            profile.managed = std::fs::read_to_string(profile.path.join(USER_JS))
+                .is_ok_and(|content| content.lines().any(|l| l.trim() == BLOCK_BEGIN));
+            profiles.push(profile);
+        }
+    }
+    profiles
+}
+
+/// Bulunan her profil için `user.js` değişikliğini hazırlar; dosyalara dokunmaz.
+pub fn plan(roots: &[PathBuf], proxy: &ToolProxy) -> Result<Vec<FileChange>, String> {
+    let profiles = discover(roots);
+    if profiles.is_empty() {
+        return Err("Firefox profili bulunamadı.".to_string());
+    }
+    let block = block(proxy);
+    profiles
+        .iter()
+        .map(|profile| {
+            let path = profile.path.join(USER_JS);
+            let previous = super::read_optional(&path)?;
+            let prefs = super::read_optional(&profile.path.join(PREFS_JS))?
+                .map(|content| proxy_prefs(&content))
+                .unwrap_or_default();
+            Ok(FileChange {
+                tool: Tool::Firefox,
+                written: with_block(previous.as_deref().unwrap_or_default(), &block),
+                path,
+                previous,
+                undo: Undo::Firefox { prefs },
+            })
+        })
+        .collect()
+}
+
+/// Bloğu kaldırır ve `prefs.js`'i yedekteki proxy satırlarına döndürür.
+pub(super) fn revert(change: &FileChange, prefs: &[String]) -> Result<(), String> {
+    let user_js = match super::read_optional(&change.path)? {
+        Some(content) if content == change.written => change.previous.clone(),
+        Some(content) => {
+            let stripped = strip_block(&content);
+            (change.previous.is_some() || !stripped.trim().is_empty()).then_some(stripped)
+        }
+        None => None,
+    };
+    super::write_or_remove(&change.path, user_js.as_deref())?;
+
+    // Firefox açıkken geri alınırsa kapanışta prefs.js'i bellekteki (Vexar'ın)
+    // değerleriyle yeniden yazar; Firefox kapalıyken geri alma her zaman tam olur
+    let prefs_path = change.path.with_file_name(PREFS_JS);
+    if let Some(content) = super::read_optional(&prefs_path)? {
+        let restored = restore_prefs(&content, prefs);
+        if restored != content {
+            super::write_or_remove(&prefs_path, Some(&restored))?;
+        }
+    }
+    Ok(())
+}
+
+fn block(proxy: &ToolProxy) -> String {
+    let host = proxy.endpoint.host();
+    let port = proxy.endpoint.port();
+    let prefs = [
+        ("type", "1".to_string()),
+        ("http", format!("\"{host}\"")),
+        ("http_port", port.to_string()),
+        ("ssl", format!("\"{host}\"")),
+        ("ssl_port", port.to_string()),
+        ("share_proxy_settings", "true".to_string()),
+        ("no_proxies_on", format!("\"{}\"", proxy.no_proxy)),
+    ];
+    let mut block = format!("{BLOCK_BEGIN}\n");
+    for (name, value) in prefs {
+        block.push_str(&format!("{PROXY_PREF}{name}\", {value});\n"));
+    }
+    block.push_str(BLOCK_END);
+    block.push('\n');
+    block
+}
+
+/// Önceki bloğu kaldırıp yenisini dosyanın sonuna ekler; sonraki satırlar
+/// öncekileri ezdiği için blok her zaman en sonda olmalı.
+fn with_block(content: &str, block: &str) -> String {
+    let mut out = strip_block(content);
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str(block);
+    out
+}
+
+/// Vexar bloğunu çıkarır; bitiş satırı yoksa içerik olduğu gibi bırakılır.
+fn strip_block(content: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let Some(begin) = lines.iter().position(|l| l.trim() == BLOCK_BEGIN) else {
+        return content.to_string();
+    };
+    let Some(len) = lines[begin..].iter().position(|l| l.trim() == BLOCK_END) else {
+        return content.to_string();
+    };
+    let mut kept = lines[..begin].to_vec();
+    kept.extend_from_slice(&lines[begin + len + 1..]);
+    let mut out = kept.join("\n");
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out
+}
+
+fn is_proxy_pref(line: &str) -> bool {
+    line.trim_start().starts_with(PROXY_PREF)
+}
+
+/// `prefs.js`'teki proxy satırlarının yedeği.
+fn proxy_prefs(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter(|l| is_proxy_pref(l))
+        .map(ToString::to_string)
+        .collect()
+}
+
+fn restore_prefs(content: &str, prefs: &[String]) -> String {
+    let mut lines: Vec<&str> = content.lines().filter(|l| !is_proxy_pref(l)).collect();
+    lines.extend(prefs.iter().map(String::as_str));
+    let mut out = lines.join("\n");
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proxy::bypass::BypassList;
+    use crate::proxy::endpoint::ProxyEndpoint;
+
+    const PROFILES: &str = "\
+[Install308046B0AF4A39CB]
+Default=Profiles/abcd.default-release
+Locked=1
+
+[Profile1]
+Name=default
+IsRelative=1
+Path=Profiles/wxyz.default
+Default=1
+
+[Profile0]
+Name=default-release
+IsRelative=1
+Path=Profiles/abcd.default-release
+
+[Profile2]
+Name=work
+IsRelative=0
+Path=/data/firefox/work
+
+[General]
+StartWithLastProfile=1
+Version=2
+";
+
+    fn tree(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("vexar-firefox-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("Profiles/abcd.default-release")).unwrap();
+        std::fs::write(root.join(PROFILES_INI), PROFILES).unwrap();
+        root
+    }
+
+    #[test]
+    fn parses_profiles_ini() {
+        let root = Path::new("/home/ada/.mozilla/firefox");
+        let profiles = parse_profiles_ini(PROFILES, root);
+        let summary: Vec<_> = profiles
+            .iter()
+            .map(|p| (p.name.as_str(), p.path.clone(), p.default))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("default", root.join("Profiles/wxyz.default"), true),
+                (
+                    "default-release",
+                    root.join("Profiles/abcd.default-release"),
+                    true
+                ),
+                ("work", PathBuf::from("/data/firefox/work"), false),
+            ]
+        );
+
+        // Eski sürümlerin bıraktığı, profil bölümü olmayan Install girdisi
+        let only_install = parse_profiles_ini("[InstallX]\nDefault=Profiles/solo\n", root);
+        assert_eq!(only_install[0].name, "solo");
+        assert!(only_install[0].default);
+    }
+
+    #[test]
+    fn discovers_only_existing_profiles() {
+        let root = tree("discover");
+        let profiles = discover(&[root.clone(), root.join("missing")]);
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].name, "default-release");
+        assert!(!profiles[0].managed);
+        assert!(discover(&[root.join("missing")]).is_empty());
+    }
+
+    #[test]
+    fn writes_block_and_restores_prefs() {
+        let root = tree("roundtrip");
+        let profile = root.join("Profiles/abcd.default-release");
+        let user_js = "user_pref(\"browser.startup.page\", 3);\n";
+        let prefs_js = "user_pref(\"browser.tabs.warnOnClose\", false);\nuser_pref(\"network.proxy.type\", 0);\n";
+        std::fs::write(profile.join(USER_JS), user_js).unwrap();
+        std::fs::write(profile.join(PREFS_JS), prefs_js).unwrap();
+
+        let proxy = ToolProxy::new(&ProxyEndpoint::local(8080), &BypassList::default());
+        let changes = plan(std::slice::from_ref(&root), &proxy).unwrap();
+        assert_eq!(changes.len(), 1);
+        changes[0].apply().unwrap();
+
+        let written = std::fs::read_to_string(profile.join(USER_JS)).unwrap();
+        assert!(written.starts_with(user_js));
+        assert!(written.contains("user_pref(\"network.proxy.http_port\", 8080);\n"));
+        assert!(written.contains("user_pref(\"network.proxy.ssl\", \"127.0.0.1\");\n"));
+        assert!(written.ends_with(&format!("{BLOCK_END}\n")));
+        assert!(discover(std::slice::from_ref(&root))[0].managed);
+
+        // Firefox açılışta bloğu prefs.js'e kopyalar, kullanıcı da user.js'e ekleme yapar
+        std::fs::write(
+            profile.join(PREFS_JS),
+            "user_pref(\"browser.tabs.warnOnClose\", false);\nuser_pref(\"network.proxy.type\", 1);\nuser_pref(\"network.proxy.http\", \"127.0.0.1\");\n",
+        )
+        .unwrap();
+        std::fs::write(
+            profile.join(USER_JS),
+            format!("{written}user_pref(\"browser.ctrlTab.sortByRecentlyUsed\", true);\n"),
+        )
+        .unwrap();
+        changes[0].revert().unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(profile.join(USER_JS)).unwrap(),
+            format!("{user_js}user_pref(\"browser.ctrlTab.sortByRecentlyUsed\", true);\n")
+        );
+        assert_eq!(
+            std::fs::read_to_string(profile.join(PREFS_JS)).unwrap(),
+            prefs_js
+        );
+    }
+
+    #[test]
+    fn created_user_js_is_removed() {
+        let root = tree("created");
+        let profile = root.join("Profiles/abcd.default-release");
+        let proxy = ToolProxy::new(&ProxyEndpoint::local(8080), &BypassList::default());
+        let changes = plan(&[root], &proxy).unwrap();
+        changes[0].apply().unwrap();
+        changes[0].revert().unwrap();
+        assert!(!profile.join(USER_JS).exists());
+        assert!(!profile.join(PREFS_JS).exists());
+    }
+}
@@ -0,0 +1,125 @@
+//! Entegrasyonların değiştirdiği dosyaların kaydı.
+//!
+//! Dosyalara yazılmadan önce her birinin önceki hali bu kayda alınır;
+//! bağlantı kesilince veya Vexar çöktükten sonraki ilk açılışta kayıttan
+//! geri yüklenir. Sistem proxy'sinin `ProxyJournal`'ı ile aynı yaklaşım.
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use super::FileChange;
+
+pub const JOURNAL_FILE: &str = "integrations-journal.json";
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct IntegrationEntry {
+    pub changes: Vec<FileChange>,
+    pub pid: u32,
+}
+
+pub struct IntegrationJournal {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl IntegrationJournal {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            lock: Mutex::new(()),
+        }
+    }
+
+    pub fn load(&self) -> Option<IntegrationEntry> {
+        let json = std::fs::read_to_string(&self.path).ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    /// Kaydı bu sürecin değişiklikleriyle değiştirir; liste boşsa kayıt silinir.
+    pub fn save(&self, changes: &[FileChange]) -> Result<(), String> {
+        if changes.is_empty() {
+            return self.clear();
+        }
+        let _guard = self.lock.lock().unwrap();
+        let entry = IntegrationEntry {
+            changes: changes.to_vec(),
+            pid: std::process::id(),
+        };
+        let json = serde_json::to_string_pretty(&entry).map_err(|e| e.to_string())?;
+        crate::files::write_atomic(&self.path, &json)
+    }
+
+    pub fn clear(&self) -> Result<(), String> {
+        let _guard = self.lock.lock().unwrap();
+        match std::fs::remove_file(&self.path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(format!("Entegrasyon kaydı silinemedi: {e}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::{Tool, Undo};
+
+    fn journal(name: &str) -> IntegrationJournal {
+        let dir = std::env::temp_dir().join(format!(
+            "vexar-integration-journal-{name}-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        IntegrationJournal::new(dir.join(JOURNAL_FILE))
+    }
+
+    fn npmrc() -> FileChange {
+        FileChange {
+            tool: Tool::Npm,
+            path: "/home/user/.npmrc".into(),
+            previous: Some("registry=https://registry.npmjs.org/\n".to_string()),
+            written: "proxy=http://127.0.0.1:8080\n".to_string(),
+            undo: Undo::Replace,
+        }
+    }
+
+    #[test]
+    fn round_trips_changes() {
+        let journal = journal("roundtrip");
+        assert_eq!(journal.load(), None);
+
+        journal.save(&[npmrc()]).unwrap();
+        assert_eq!(
+            journal.load(),
+            Some(IntegrationEntry {
+                changes: vec![npmrc()],
+                pid: std::process::id(),
+            })
+        );
+    }
+
+    #[test]
+    fn clear_and_empty_save_remove_the_record() {
+        let journal = journal("clear");
+        journal.save(&[npmrc()]).unwrap();
+        journal.clear().unwrap();
+        assert_eq!(journal.load(), None);
+        // Olmayan kaydı silmek hata değil
+        journal.clear().unwrap();
+
+        journal.save(&[npmrc()]).unwrap();
+        journal.save(&[]).unwrap();
+        assert!(!journal.path.exists());
+    }
+
+    #[test]
+    fn corrupt_record_is_ignored_and_replaced() {
+        let journal = journal("corrupt");
+        std::fs::create_dir_all(journal.path.parent().unwrap()).unwrap();
+        std::fs::write(&journal.path, "{\"changes\": [").unwrap();
+        assert_eq!(journal.load(), None);
+
+        journal.save(&[npmrc()]).unwrap();
+        assert_eq!(journal.load().unwrap().changes, vec![npmrc()]);
+    }
+}
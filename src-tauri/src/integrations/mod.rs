@@ -0,0 +1,475 @@
+//! Sistem proxy'sini izlemeyen araçlar için isteğe bağlı entegrasyonlar.
+//!
+//! Sistem proxy'si açılınca seçili araçların dosyalarına Vexar'ın adresi
+//! yazılır, kapanınca (duraklatma ve heartbeat dahil) önceki halleri geri
+//! yüklenir. Her değişiklik yazılmadan önce kayda alınır; Vexar çökse bile
+//! bir sonraki açılışta geri alınır.
+
+pub mod devtools;
+pub mod edit;
+pub mod firefox;
+pub mod journal;
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use devtools::{ToolPaths, ToolProxy};
+use edit::Format;
+use journal::IntegrationJournal;
+
+use crate::proxy::bypass::BypassList;
+use crate::proxy::endpoint::ProxyEndpoint;
+
+/// Bir entegrasyon uygulanamadığında veya geri alınamadığında yayınlanan event.
+pub const FAILED_EVENT: &str = "integrations://failed";
+
+/// Hangi araçların Vexar'a yönlendirileceği; hepsi varsayılan olarak kapalı.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct IntegrationSettings {
+    /// `~/.gitconfig` içinde `http.proxy`.
+    pub git: bool,
+    /// `~/.npmrc` içinde `proxy`, `https-proxy` ve `noproxy`.
+    pub npm: bool,
+    /// `pip.conf` içinde `global.proxy`.
+    pub pip: bool,
+    /// Kabuklar için `HTTP_PROXY`/`HTTPS_PROXY`/`NO_PROXY` dosyası.
+    pub shell_env: bool,
+    /// Firefox profillerinin `user.js` dosyasında Vexar bloğu.
+    pub firefox: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Tool {
+    Git,
+    Npm,
+    Pip,
+    ShellEnv,
+    Firefox,
+}
+
+impl Tool {
+    pub const ALL: [Tool; 5] = [
+        Tool::Git,
+        Tool::Npm,
+        Tool::Pip,
+        Tool::ShellEnv,
+        Tool::Firefox,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Tool::Git => "git",
+            Tool::Npm => "npm",
+            Tool::Pip => "pip",
+            Tool::ShellEnv => "kabuk ortamı",
+            Tool::Firefox => "Firefox",
+        }
+    }
+}
+
+impl IntegrationSettings {
+    pub fn tools(&self) -> Vec<Tool> {
+        Tool::ALL
+            .into_iter()
+            .filter(|tool| match tool {
+                Tool::Git => self.git,
+                Tool::Npm => self.npm,
+                Tool::Pip => self.pip,
+                Tool::ShellEnv => self.shell_env,
+                Tool::Firefox => self.firefox,
+            })
+            .collect()
+    }
+}
+
+/// Bir dosyada yapılan değişiklik ve nasıl geri alınacağı.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FileChange {
+    pub tool: Tool,
+    pub path: PathBuf,
+    /// Değişiklikten önceki içerik; dosya yoksa `None`.
+    pub previous: Option<String>,
+    /// Vexar'ın yazdığı içerik.
+    pub written: String,
+    pub undo: Undo,
+}
+
+/// Dosya bağlıyken başka biri tarafından da değiştirildiyse uygulanacak geri alma.
+/// Dosya hâlâ Vexar'ın yazdığı gibiyse her durumda önceki içerik aynen geri yazılır.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Undo {
+    /// Dosyanın tamamı Vexar'ın; önceki içerik geri yazılır.
+    Replace,
+    /// Yalnızca bu anahtarlar önceki değerlerine döndürülür, diğer düzenlemeler kalır.
+    Keys {
+        format: Format,
+        keys: Vec<KeyChange>,
+    },
+    /// `user.js`'teki Vexar bloğu kaldırılır; Firefox'un `prefs.js`'e kopyaladığı
+    /// `network.proxy.*` satırları yedekteki haline döndürülür.
+    Firefox { prefs: Vec<String> },
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct KeyChange {
+    pub section: Option<String>,
+    pub key: String,
+    /// Anahtar yoksa `None`.
+    pub previous: Option<String>,
+}
+
+impl FileChange {
+    fn apply(&self) -> Result<(), String> {
+        write_or_remove(&self.path, Some(&self.written))
+    }
+
+    fn revert(&self) -> Result<(), String> {
+        if let Undo::Firefox { prefs } = &self.undo {
+            return firefox::revert(self, prefs);
+        }
+        match (&self.undo, read_optional(&self.path)?) {
+            (Undo::Keys { format, keys }, Some(mut content)) if content != self.written => {
+                for key in keys {
+                    content = edit::set(
+                        &content,
+                        *format,
+                        key.section.as_deref(),
+                        &key.key,
+                        key.previous.as_deref(),
+                    );
+                }
+                let remove = self.previous.is_none() && content.trim().is_empty();
+                write_or_remove(&self.path, (!remove).then_some(content.as_str()))
+            }
+            // Kullanıcı bağlıyken dosyayı sildiyse geri getirilecek bir şey yok
+            (Undo::Keys { .. }, None) => Ok(()),
+            _ => write_or_remove(&self.path, self.previous.as_deref()),
+        }
+    }
+}
+
+/// Managed state olarak tutulan entegrasyon yöneticisi.
+pub struct Integrations {
+    paths: ToolPaths,
+    journal: IntegrationJournal,
+    /// Araçların yönlendirildiği adres; proxy kapalıyken `None`.
+    active: Mutex<Option<ProxyEndpoint>>,
+}
+
+impl Integrations {
+    pub fn new(paths: ToolPaths, journal: IntegrationJournal) -> Self {
+        Self {
+            paths,
+            journal,
+            active: Mutex::new(None),
+        }
+    }
+
+    /// Seçili araçları `endpoint`'e yönlendirir. Önceki bir uygulama varsa
+    /// önce o geri alınır ki Vexar'ın kendi değerleri "önceki" diye kaydedilmesin.
+    /// Bir aracın hatası diğerlerini engellemez; hatalar birlikte döner.
+    pub fn apply(
+        &self,
+        settings: &IntegrationSettings,
+        endpoint: &ProxyEndpoint,
+        bypass: &BypassList,
+    ) -> Result<(), String> {
+        let mut active = self.active.lock().unwrap();
+        *active = Some(endpoint.clone());
+        // Geri alınamayan bir dosyanın üzerine yeni değer yazılmaz
+        let errors = self.revert_locked();
+        if !errors.is_empty() {
+            return join_errors(errors);
+        }
+        let proxy = ToolProxy::new(endpoint, bypass);
+
+        let mut errors = Vec::new();
+        let mut planned = Vec::new();
+        for tool in settings.tools() {
+            let plan = match tool {
+                Tool::Firefox => firefox::plan(&self.paths.firefox, &proxy),
+                _ => devtools::plan(tool, &self.paths, &proxy).map(|change| vec![change]),
+            };
+            match plan {
+                Ok(changes) => planned.extend(changes),
+                Err(e) => errors.push(format!("{}: {e}", tool.label())),
+            }
+        }
+        // Dosyalara dokunmadan önce kayda al (çökme sonrası kurtarma)
+        self.journal.save(&planned)?;
+
+        let mut applied = Vec::new();
+        for change in planned {
+            match change.apply() {
+                Ok(()) => applied.push(change),
+                Err(e) => errors.push(format!("{}: {e}", change.tool.label())),
+            }
+        }
+        self.journal.save(&applied)?;
+        join_errors(errors)
+    }
+
+    /// Proxy açıksa ayar veya bypass değişikliğini araçlara yansıtır.
+    pub fn refresh(
+        &self,
+        settings: &IntegrationSettings,
+        bypass: &BypassList,
+    ) -> Result<(), String> {
+        let endpoint = self.active.lock().unwrap().clone();
+        match endpoint {
+            Some(endpoint) => self.apply(settings, &endpoint, bypass),
+            None => Ok(()),
+        }
+    }
+
+    /// Araçların dosyalarını Vexar'dan önceki haline döndürür.
+    pub fn restore(&self) -> Result<(), String> {
+        let mut active = self.active.lock().unwrap();
+        *active = None;
+        join_errors(self.revert_locked())
+    }
+
+    /// Bulunan Firefox profilleri ve Vexar bloğunu içerip içermedikleri.
+    pub fn firefox_profiles(&self) -> Vec<firefox::FirefoxProfile> {
+        firefox::discover(&self.paths.firefox)
+    }
+
+    /// Açılışta çağrılır; sahibi yaşamayan bir kayıt varsa değişiklikleri geri alır.
+    pub fn recover(&self, is_alive: impl FnOnce(u32) -> bool) -> Result<bool, String> {
+        let Some(entry) = self.journal.load() else {
+            return Ok(false);
+        };
+        if entry.pid == std::process::id() || is_alive(entry.pid) {
+            return Ok(false);
+        }
+        self.restore().map(|_| true)
+    }
+
+    /// Geri alınamayan değişiklikler bir sonraki denemede tekrar denensin diye kayıtta kalır.
+    fn revert_locked(&self) -> Vec<String> {
+        let Some(entry) = self.journal.load() else {
+            return Vec::new();
+        };
+        let mut errors = Vec::new();
+        let mut failed = Vec::new();
+        for change in entry.changes.into_iter().rev() {
+            if let Err(e) = change.revert() {
+                errors.push(format!("{}: {e}", change.tool.label()));
+                failed.push(change);
+            }
+        }
+        if let Err(e) = self.journal.save(&failed) {
+            errors.push(e);
+        }
+        errors
+    }
+}
+
+fn join_errors(errors: Vec<String>) -> Result<(), String> {
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+fn read_optional(path: &Path) -> Result<Option<String>, String> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("{} okunamadı: {e}", path.display())),
+    }
+}
+
+/// `None` dosyayı siler.
+fn write_or_remove(path: &Path, content: Option<&str>) -> Result<(), String> {
+    let Some(content) = content else {
+        return match std::fs::remove_file(path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(format!("{} silinemedi: {e}", path.display())),
+        };
+    };
+    crate::files::write_atomic(path, content)
+}
+
+#[tauri::command]
+pub fn update_integrations(
+    app: tauri::AppHandle,
+    integrations: IntegrationSettings,
+) -> Result<IntegrationSettings, String> {
+    use tauri::Manager;
+
+    let store = app.state::<crate::settings::SettingsStore>();
+    let mut settings = store.get();
+    settings.integrations = integrations;
+    let settings = store.replace(settings)?;
+    app.state::<Integrations>()
+        .refresh(&settings.integrations, &settings.bypass)?;
+    Ok(settings.integrations)
+}
+
+#[tauri::command]
+pub fn list_firefox_profiles(
+    integrations: tauri::State<'_, Integrations>,
+) -> Vec<firefox::FirefoxProfile> {
+    integrations.firefox_profiles()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fixture {
+        config: PathBuf,
+        integrations: Integrations,
+    }
+
+    fn fixture(name: &str) -> Fixture {
+        let root =
+            std::env::temp_dir().join(format!("vexar-integrations-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let home = root.join("home");
+        let config = root.join("config");
+        std::fs::create_dir_all(&home).unwrap();
+        let integrations = Integrations::new(
+            ToolPaths::new(&home, &config),
+            IntegrationJournal::new(config.join(journal::JOURNAL_FILE)),
+        );
+        Fixture {
+            config,
+            integrations,
+        }
+    }
+
+    fn all() -> IntegrationSettings {
+        IntegrationSettings {
+            git: true,
+            npm: true,
+            pip: true,
+            shell_env: true,
+            ..Default::default()
+        }
+    }
+
+    fn vexar() -> ProxyEndpoint {
+        ProxyEndpoint::local(8080)
+    }
+
+    fn read(path: &Path) -> Option<String> {
+        std::fs::read_to_string(path).ok()
+    }
+
+    const GITCONFIG: &str = "[user]\n\tname = Ada\n\temail = ada@example.com\n[http]\n\tproxy = http://corp.example:3128\n";
+
+    #[test]
+    fn applies_and_restores_exactly() {
+        let fx = fixture("exact");
+        let paths = fx.integrations.paths.clone();
+        std::fs::write(&paths.git, GITCONFIG).unwrap();
+
+        fx.integrations
+            .apply(&all(), &vexar(), &BypassList::default())
+            .unwrap();
+        let git = read(&paths.git).unwrap();
+        assert!(git.contains("\tproxy = http://127.0.0.1:8080\n"));
+        assert!(git.contains("email = ada@example.com"));
+        let npmrc = read(&paths.npm).unwrap();
+        assert!(npmrc.contains("https-proxy=http://127.0.0.1:8080"));
+        assert!(npmrc.contains("noproxy=localhost,127.0.0.0/8,::1"));
+        assert!(
+            read(&paths.pip)
+                .unwrap()
+                .contains("[global]\nproxy = http://127.0.0.1:8080")
+        );
+        assert!(
+            read(&paths.shell_env)
+                .unwrap()
+                .contains("export NO_PROXY=\"localhost,127.0.0.0/8,::1\"")
+        );
+
+        // Yeniden bağlanma Vexar'ın değerlerini "önceki" diye kaydetmemeli
+        fx.integrations
+            .apply(&all(), &ProxyEndpoint::local(8081), &BypassList::default())
+            .unwrap();
+        fx.integrations.restore().unwrap();
+
+        assert_eq!(read(&paths.git).as_deref(), Some(GITCONFIG));
+        assert_eq!(read(&paths.npm), None);
+        assert_eq!(read(&paths.pip), None);
+        assert_eq!(read(&paths.shell_env), None);
+        assert!(fx.integrations.journal.load().is_none());
+    }
+
+    #[test]
+    fn keeps_user_edits_made_while_connected() {
+        let fx = fixture("edits");
+        let paths = fx.integrations.paths.clone();
+        std::fs::write(&paths.git, GITCONFIG).unwrap();
+        let git_only = IntegrationSettings {
+            git: true,
+            ..Default::default()
+        };
+        fx.integrations
+            .apply(&git_only, &vexar(), &BypassList::default())
+            .unwrap();
+
+        let edited = format!("{}[alias]\n\tst = status\n", read(&paths.git).unwrap());
+        std::fs::write(&paths.git, &edited).unwrap();
+        fx.integrations.restore().unwrap();
+
+        let restored = read(&paths.git).unwrap();
+        assert!(restored.contains("\tproxy = http://corp.example:3128\n"));
+        assert!(restored.contains("st = status"));
+    }
+
+    #[test]
+    fn turning_a_tool_off_reverts_only_that_tool() {
+        let fx = fixture("refresh");
+        let paths = fx.integrations.paths.clone();
+        fx.integrations
+            .apply(&all(), &vexar(), &BypassList::default())
+            .unwrap();
+        let without_npm = IntegrationSettings {
+            npm: false,
+            ..all()
+        };
+        fx.integrations
+            .refresh(&without_npm, &BypassList::default())
+            .unwrap();
+        assert_eq!(read(&paths.npm), None);
+        assert!(read(&paths.git).is_some());
+
+        fx.integrations.restore().unwrap();
+        // Proxy kapalıyken ayar değişikliği dosyalara dokunmaz
+        fx.integrations
+            .refresh(&all(), &BypassList::default())
+            .unwrap();
+        assert_eq!(read(&paths.git), None);
+        assert_eq!(read(&paths.pip), None);
+    }
+
+    #[test]
+    fn recovers_changes_of_a_dead_process() {
+        let fx = fixture("recover");
+        let paths = fx.integrations.paths.clone();
+        fx.integrations
+            .apply(&all(), &vexar(), &BypassList::default())
+            .unwrap();
+        let mut entry = fx.integrations.journal.load().unwrap();
+        entry.pid = u32::MAX;
+        std::fs::write(
+            fx.config.join(journal::JOURNAL_FILE),
+            serde_json::to_string(&entry).unwrap(),
+        )
+        .unwrap();
+
+        assert!(!fx.integrations.recover(|_| true).unwrap());
+        assert!(read(&paths.git).is_some());
+        assert!(fx.integrations.recover(|_| false).unwrap());
+        assert_eq!(read(&paths.git), None);
+    }
+}
@@ -0,0 +1,70 @@
+//! Başlatılan uygulamaya verilen proxy bayrakları.
+
+use crate::proxy::bypass::BypassList;
+use crate::proxy::endpoint::ProxyEndpoint;
+
+pub trait ProxyArgs: Send + Sync {
+    /// Uygulamanın argümanlarının sonuna eklenecek bayraklar.
+    fn args(&self, proxy: &ProxyEndpoint, bypass: &BypassList) -> Vec<String>;
+    /// Kullanıcının argümanlarındaki bu bayraklar Vexar'ınkilerle çakışmasın diye çıkarılır.
+    fn is_proxy_arg(&self, arg: &str) -> bool;
+}
+
+/// Chromium ve Electron uygulamaları (Discord, Chrome, Edge, Brave...).
+pub struct ChromiumArgs;
+
+const PROXY_SERVER: &str = "--proxy-server";
+const PROXY_BYPASS: &str = "--proxy-bypass-list";
+
+impl ProxyArgs for ChromiumArgs {
+    fn args(&self, proxy: &ProxyEndpoint, bypass: &BypassList) -> Vec<String> {
+        let mut args = vec![format!("{PROXY_SERVER}={proxy}")];
+        // Chromium'un listesi WinINet ile aynı sözdizimini kullanır (`<local>`, `*.x`, CIDR)
+        let bypass = bypass.to_wininet();
+        if !bypass.is_empty() {
+            args.push(format!("{PROXY_BYPASS}={bypass}"));
+        }
+        args
+    }
+
+    fn is_proxy_arg(&self, arg: &str) -> bool {
+        [
+            PROXY_SERVER,
+            PROXY_BYPASS,
+            "--no-proxy-server",
+            "--proxy-pac-url",
+        ]
+        .iter()
+        .any(|flag| arg == *flag || arg.starts_with(&format!("{flag}=")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proxy::bypass::BypassEntry;
+
+    #[test]
+    fn builds_chromium_flags() {
+        let mut bypass = BypassList::default();
+        bypass.add(BypassEntry::parse("*.corp.example").unwrap());
+        assert_eq!(
+            ChromiumArgs.args(&ProxyEndpoint::local(8080), &bypass),
+            vec![
+                "--proxy-server=127.0.0.1:8080",
+                "--proxy-bypass-list=<local>;*.corp.example",
+            ]
+        );
+
+        let empty = BypassList::parse_wininet("").unwrap();
+        assert_eq!(
+            ChromiumArgs.args(&ProxyEndpoint::local(8080), &empty),
+            vec!["--proxy-server=127.0.0.1:8080"]
+        );
+
+        assert!(ChromiumArgs.is_proxy_arg("--proxy-server=socks5://h:1"));
+        assert!(ChromiumArgs.is_proxy_arg("--no-proxy-server"));
+        assert!(!ChromiumArgs.is_proxy_arg("--proxy-server-extra"));
+        assert!(!ChromiumArgs.is_proxy_arg("--start-minimized"));
+    }
+}
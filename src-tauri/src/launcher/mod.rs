@@ -0,0 +1,380 @@
+//! Chromium/Electron uygulamalarını doğrudan Vexar'a bağlı başlatma.
+//!
+//! Discord gibi uygulamalar sistem proxy'sini her bağlantıda dikkate almaz.
+//! Listeye eklenen uygulamalar `--proxy-server` ve bypass bayraklarıyla
+//! başlatılır; Chromium tek kopya çalıştığı için açık olan kopyalar istenirse
+//! önce kapatılır, yoksa yeni bayraklar yok sayılır.
+
+pub mod args;
+pub mod process;
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use args::{ChromiumArgs, ProxyArgs};
+use process::{Processes, SystemProcesses};
+
+use crate::proxy::bypass::BypassList;
+use crate::proxy::endpoint::ProxyEndpoint;
+
+/// Tepsiden başlatma sonucu.
+pub const LAUNCHED_EVENT: &str = "launcher://launched";
+pub const FAILED_EVENT: &str = "launcher://failed";
+
+/// Tepsi menüsündeki öğelerin id öneki.
+pub const MENU_PREFIX: &str = "launch:";
+
+/// Kapatılması istenen kopyalar bu süre içinde çıkmazsa zorla sonlandırılır.
+const STOP_TIMEOUT: Duration = Duration::from_secs(3);
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct LauncherSettings {
+    pub apps: Vec<LaunchApp>,
+}
+
+impl LauncherSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        for (i, app) in self.apps.iter().enumerate() {
+            app.validate()?;
+            if self.apps[..i].iter().any(|other| other.id == app.id) {
+                return Err(format!("Uygulama kimliği tekrar ediyor: {}", app.id));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn find(&self, id: &str) -> Result<&LaunchApp, String> {
+        self.apps
+            .iter()
+            .find(|app| app.id == id)
+            .ok_or_else(|| format!("Uygulama bulunamadı: {id}"))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LaunchApp {
+    /// Tepsi menüsünde de kullanılan kalıcı kimlik.
+    pub id: String,
+    pub name: String,
+    pub path: PathBuf,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Açık kopyalar kapatılıp proxy ile yeniden başlatılır.
+    #[serde(default)]
+    pub restart_running: bool,
+}
+
+impl LaunchApp {
+    fn validate(&self) -> Result<(), String> {
+        let id_ok = !self.id.is_empty()
+            && self
+                .id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !id_ok {
+            return Err(format!("Geçersiz uygulama kimliği: {}", self.id));
+        }
+        if self.name.trim().is_empty() {
+            return Err("Uygulama adı boş olamaz.".to_string());
+        }
+        if !self.path.is_absolute() {
+            return Err(format!(
+                "Uygulama yolu tam yol olmalı: {}",
+                self.path.display()
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct LaunchReport {
+    pub id: String,
+    pub name: String,
+    pub pid: u32,
+    /// Yeniden başlatma için kapatılan kopyalar.
+    pub restarted: Vec<u32>,
+    /// Açık bir kopya bırakıldı; Chromium yeni bayrakları ona uygulamaz.
+    pub already_running: bool,
+}
+
+/// Managed state olarak tutulan başlatıcı.
+pub struct Launcher {
+    args: Box<dyn ProxyArgs>,
+    processes: Box<dyn Processes>,
+    stop_timeout: Duration,
+}
+
+impl Default for Launcher {
+    fn default() -> Self {
+        Self::new(Box::new(ChromiumArgs), Box::new(SystemProcesses))
+    }
+}
+
+impl Launcher {
+    pub fn new(args: Box<dyn ProxyArgs>, processes: Box<dyn Processes>) -> Self {
+        Self {
+            args,
+            processes,
+            stop_timeout: STOP_TIMEOUT,
+        }
+    }
+
+    pub fn launch(
+        &self,
+        app: &LaunchApp,
+        proxy: &ProxyEndpoint,
+        bypass: &BypassList,
+    ) -> Result<LaunchReport, String> {
+        if !app.path.is_file() {
+            return Err(format!("{} bulunamadı: {}", app.name, app.path.display()));
+        }
+        let running = self.processes.running(&app.path);
+        let restarted = if app.restart_running && !running.is_empty() {
+            self.stop_all(&app.path, &running)?;
+            running.clone()
+        } else {
+            Vec::new()
+        };
+
+        let mut argv: Vec<String> = app
+            .args
+            .iter()
+            .filter(|arg| !self.args.is_proxy_arg(arg))
+            .cloned()
+            .collect();
+        argv.extend(self.args.args(proxy, bypass));
+        let pid = self.processes.spawn(&app.path, &argv)?;
+
+        Ok(LaunchReport {
+            id: app.id.clone(),
+            name: app.name.clone(),
+            pid,
+            already_running: !app.restart_running && !running.is_empty(),
+            restarted,
+        })
+    }
+
+    /// Önce kapanmalarını ister, süre dolunca zorla sonlandırır.
+    fn stop_all(&self, exe: &Path, pids: &[u32]) -> Result<(), String> {
+        for force in [false, true] {
+            for pid in pids {
+                self.processes.stop(*pid, force);
+            }
+            let started = Instant::now();
+            loop {
+                let alive = self.processes.running(exe);
+                if !pids.iter().any(|pid| alive.contains(pid)) {
+                    return Ok(());
+                }
+                if started.elapsed() >= self.stop_timeout {
+                    break;
+                }
+                std::thread::sleep(POLL_INTERVAL);
+            }
+        }
+        Err(format!("{} kapatılamadı.", exe.display()))
+    }
+}
+
+/// Uygulamayı çalışan oturumun proxy'siyle başlatır.
+fn launch_by_id(app: &tauri::AppHandle, id: &str) -> Result<LaunchReport, String> {
+    use tauri::Manager;
+
+    let settings = app.state::<crate::settings::SettingsStore>().get();
+    let entry = settings.launcher.find(id)?;
+    let proxy = app
+        .state::<crate::engine::EngineSupervisor>()
+        .http_endpoint()?;
+    app.state::<Launcher>()
+        .launch(entry, &proxy, &settings.bypass)
+}
+
+/// Tepsi menüsünden başlatır; kapatma beklemesi menüyü kilitlemesin diye
+/// ayrı thread'de çalışır ve sonuç arayüze event olarak bildirilir.
+pub fn launch_from_tray(app: &tauri::AppHandle, id: String) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        use tauri::Emitter;
+
+        let _ = match launch_by_id(&app, &id) {
+            Ok(report) => app.emit(LAUNCHED_EVENT, report),
+            Err(reason) => app.emit(FAILED_EVENT, reason),
+        };
+    });
+}
+
+#[tauri::command]
+pub async fn launch_app(app: tauri::AppHandle, id: String) -> Result<LaunchReport, String> {
+    launch_by_id(&app, &id)
+}
+
+#[tauri::command]
+pub fn update_launcher(
+    app: tauri::AppHandle,
+    apps: Vec<LaunchApp>,
+) -> Result<Vec<LaunchApp>, String> {
+    use tauri::Manager;
+
+    let store = app.state::<crate::settings::SettingsStore>();
+    let mut settings = store.get();
+    settings.launcher.apps = apps;
+    let settings = store.replace(settings)?;
+    crate::refresh_tray_menu(&app);
+    Ok(settings.launcher.apps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    struct FakeArgs;
+
+    impl ProxyArgs for FakeArgs {
+        fn args(&self, proxy: &ProxyEndpoint, _bypass: &BypassList) -> Vec<String> {
+            vec![format!("--via={proxy}")]
+        }
+
+        fn is_proxy_arg(&self, arg: &str) -> bool {
+            arg.starts_with("--via=")
+        }
+    }
+
+    #[derive(Default)]
+    struct FakeProcesses {
+        running: Mutex<Vec<u32>>,
+        /// Kapanma isteğini yok sayan süreçler gibi davranır.
+        ignores_term: bool,
+        stops: Mutex<Vec<(u32, bool)>>,
+        spawned: Mutex<Vec<Vec<String>>>,
+    }
+
+    impl Processes for Arc<FakeProcesses> {
+        fn running(&self, _exe: &Path) -> Vec<u32> {
+            self.running.lock().unwrap().clone()
+        }
+
+        fn stop(&self, pid: u32, force: bool) {
+            self.stops.lock().unwrap().push((pid, force));
+            if force || !self.ignores_term {
+                self.running.lock().unwrap().retain(|p| *p != pid);
+            }
+        }
+
+        fn spawn(&self, _exe: &Path, args: &[String]) -> Result<u32, String> {
+            self.spawned.lock().unwrap().push(args.to_vec());
+            Ok(4242)
+        }
+    }
+
+    struct Fixture {
+        launcher: Launcher,
+        processes: Arc<FakeProcesses>,
+    }
+
+    fn fixture(processes: FakeProcesses) -> Fixture {
+        let processes = Arc::new(processes);
+        let mut launcher = Launcher::new(Box::new(FakeArgs), Box::new(Arc::clone(&processes)));
+        launcher.stop_timeout = Duration::ZERO;
+        Fixture {
+            launcher,
+            processes,
+        }
+    }
+
+    fn discord(restart_running: bool) -> LaunchApp {
+        LaunchApp {
+            id: "discord".to_string(),
+            name: "Discord".to_string(),
+            // Var olan bir çalıştırılabilir dosya
+            path: std::env::current_exe().unwrap(),
+            args: vec!["--start-minimized".to_string(), "--via=old:1".to_string()],
+            restart_running,
+        }
+    }
+
+    fn launch(fx: &Fixture, app: &LaunchApp) -> Result<LaunchReport, String> {
+        fx.launcher
+            .launch(app, &ProxyEndpoint::local(8080), &BypassList::default())
+    }
+
+    #[test]
+    fn appends_proxy_args_for_current_port() {
+        let fx = fixture(FakeProcesses::default());
+        let report = launch(&fx, &discord(false)).unwrap();
+        assert_eq!(report.pid, 4242);
+        assert!(!report.already_running);
+        assert_eq!(
+            *fx.processes.spawned.lock().unwrap(),
+            vec![vec!["--start-minimized", "--via=127.0.0.1:8080"]]
+        );
+    }
+
+    #[test]
+    fn restarts_running_copies_when_asked() {
+        let fx = fixture(FakeProcesses {
+            running: Mutex::new(vec![10, 11]),
+            ..Default::default()
+        });
+        let report = launch(&fx, &discord(true)).unwrap();
+        assert_eq!(report.restarted, vec![10, 11]);
+        assert_eq!(
+            *fx.processes.stops.lock().unwrap(),
+            vec![(10, false), (11, false)]
+        );
+        assert_eq!(fx.processes.spawned.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn forces_copies_that_ignore_the_close_request() {
+        let fx = fixture(FakeProcesses {
+            running: Mutex::new(vec![10]),
+            ignores_term: true,
+            ..Default::default()
+        });
+        launch(&fx, &discord(true)).unwrap();
+        assert_eq!(
+            *fx.processes.stops.lock().unwrap(),
+            vec![(10, false), (10, true)]
+        );
+    }
+
+    #[test]
+    fn leaves_running_copies_alone_by_default() {
+        let fx = fixture(FakeProcesses {
+            running: Mutex::new(vec![10]),
+            ..Default::default()
+        });
+        let report = launch(&fx, &discord(false)).unwrap();
+        assert!(report.already_running);
+        assert!(report.restarted.is_empty());
+        assert!(fx.processes.stops.lock().unwrap().is_empty());
+
+        let missing = LaunchApp {
+            path: std::env::temp_dir().join("vexar-missing-app"),
+            ..discord(false)
+        };
+        assert!(launch(&fx, &missing).is_err());
+    }
+
+    #[test]
+    fn validates_app_list() {
+        let mut settings = LauncherSettings {
+            apps: vec![discord(false)],
+        };
+        assert!(settings.validate().is_ok());
+        assert_eq!(settings.find("discord").unwrap().name, "Discord");
+        assert!(settings.find("slack").is_err());
+
+        settings.apps.push(discord(true));
+        assert!(settings.validate().is_err());
+        settings.apps[1].id = "tray:menu".to_string();
+        assert!(settings.validate().is_err());
+        settings.apps[1].id = "chrome".to_string();
+        settings.apps[1].path = PathBuf::from("chrome.exe");
+        assert!(settings.validate().is_err());
+    }
+}
@@ -0,0 +1,112 @@
+//! Çalışan kopyaları bulma, kapatma ve uygulamayı başlatma.
+
+use std::path::Path;
+
+/// İşletim sistemi süreçlerine erişim; testlerde sahte bir uygulama kullanılır.
+pub trait Processes: Send + Sync {
+    /// `exe` dosyasından çalışan süreçlerin PID'leri.
+    fn running(&self, exe: &Path) -> Vec<u32>;
+    /// Sürecin kapanmasını ister; `force` ise zorla sonlandırır.
+    fn stop(&self, pid: u32, force: bool);
+    fn spawn(&self, exe: &Path, args: &[String]) -> Result<u32, String>;
+}
+
+pub struct SystemProcesses;
+
+impl Processes for SystemProcesses {
+    fn running(&self, exe: &Path) -> Vec<u32> {
+        use sysinfo::System;
+
+        let mut sys = System::new();
+        sys.refresh_processes();
+        let own_pid = std::process::id();
+        sys.processes()
+            .iter()
+            .filter(|(pid, process)| {
+                pid.as_u32() != own_pid && same_exe(process.exe(), process.name(), exe)
+            })
+            .map(|(pid, _)| pid.as_u32())
+            .collect()
+    }
+
+    fn stop(&self, pid: u32, force: bool) {
+        use sysinfo::{Pid, System};
+
+        let mut sys = System::new();
+        sys.refresh_processes();
+        let Some(process) = sys.process(Pid::from_u32(pid)) else {
+            return;
+        };
+        if force {
+            process.kill();
+            return;
+        }
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            // Windows'ta SIGTERM yok; /F olmadan taskkill pencerelere WM_CLOSE
+            // gönderir ve uygulama kaydedilmemiş işini sorabilir
+            let _ = std::process::Command::new("taskkill")
+                .args(["/PID", &pid.to_string()])
+                .creation_flags(CREATE_NO_WINDOW)
+                .output();
+        }
+        #[cfg(not(windows))]
+        {
+            let _ = process.kill_with(sysinfo::Signal::Term);
+        }
+    }
+
+    fn spawn(&self, exe: &Path, args: &[String]) -> Result<u32, String> {
+        let mut child = std::process::Command::new(exe)
+            .args(args)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn()
+            .map_err(|e| format!("{} başlatılamadı: {e}", exe.display()))?;
+        let pid = child.id();
+        // Uygulama Vexar'dan bağımsız yaşar; yalnızca zombi kalmasın diye beklenir
+        std::thread::spawn(move || {
+            let _ = child.wait();
+        });
+        Ok(pid)
+    }
+}
+
+/// Yol okunamıyorsa (başka kullanıcının süreci, izin yok) dosya adına bakılır.
+pub fn same_exe(candidate: Option<&Path>, name: &str, exe: &Path) -> bool {
+    match candidate {
+        Some(path) if cfg!(windows) => {
+            path.to_string_lossy().to_lowercase() == exe.to_string_lossy().to_lowercase()
+        }
+        Some(path) => path == exe,
+        None => exe.file_name().is_some_and(|file| {
+            let file = file.to_string_lossy();
+            if cfg!(windows) {
+                file.eq_ignore_ascii_case(name)
+            } else {
+                file == name
+            }
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_by_path_then_name() {
+        let exe = Path::new("/opt/discord/Discord");
+        assert!(same_exe(Some(exe), "Discord", exe));
+        assert!(!same_exe(
+            Some(Path::new("/opt/discord-canary/Discord")),
+            "Discord",
+            exe
+        ));
+        assert!(same_exe(None, "Discord", exe));
+        assert!(!same_exe(None, "chrome", exe));
+    }
+}
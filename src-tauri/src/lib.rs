@@ -1,4 +1,12 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
+mod engine;
+mod files;
+mod integrations;
+mod launcher;
+mod proxy;
+mod settings;
+
+use engine::config::EngineConfig;
 use local_ip_address::local_ip;
 use std::net::TcpListener;
 use std::sync::Arc;
@@ -10,38 +18,47 @@ struct ConfigResponse {
     port: u16,
     lan_ip: String,
     bind_address: String,
+    args: Vec<String>,
 }
 
 #[tauri::command]
-fn get_sidecar_config(allow_lan_sharing: bool) -> Result<ConfigResponse, String> {
-    let bind_addr = if allow_lan_sharing {
-        "0.0.0.0"
+fn get_sidecar_config(config: EngineConfig) -> Result<ConfigResponse, String> {
+    config.validate()?;
+    let bind_addr = config.listen_addr;
+
+    let selected_port = if config.listen_port != 0 {
+        // Sabit port istendiyse boşta olduğundan emin ol
+        if TcpListener::bind((bind_addr, config.listen_port)).is_err() {
+            return Err(format!("Port {} kullanımda.", config.listen_port));
+        }
+        config.listen_port
     } else {
-        "127.0.0.1"
-    };
-
-    // Öncelikli Portlar: 8080 - 8090 arası kontrol et
-    let mut selected_port = 0;
-    for port in 8080..=8090 {
-        if TcpListener::bind((bind_addr, port)).is_ok() {
-            selected_port = port;
-            break;
+        // Öncelikli Portlar: 8080 - 8090 arası kontrol et
+        let mut selected_port = 0;
+        for port in 8080..=8090 {
+            if TcpListener::bind((bind_addr, port)).is_ok() {
+                selected_port = port;
+                break;
+            }
         }
-    }
 
-    // Fallback: Eğer hepsi doluysa, sistemden rastgele bir port iste (Port 0)
-    if selected_port == 0 {
-        if let Ok(listener) = TcpListener::bind((bind_addr, 0)) {
-            if let Ok(addr) = listener.local_addr() {
-                selected_port = addr.port();
+        // Fallback: Eğer hepsi doluysa, sistemden rastgele bir port iste (Port 0)
+        if selected_port == 0 {
+            if let Ok(listener) = TcpListener::bind((bind_addr, 0)) {
+                if let Ok(addr) = listener.local_addr() {
+                    selected_port = addr.port();
+                }
             }
         }
-    }
+        selected_port
+    };
 
     if selected_port == 0 {
         return Err("Uygun port bulunamadı.".to_string());
     }
 
+    let args = config.with_port(selected_port).to_args()?;
+
     // Yerel IP Adresini Bul (LAN Paylaşımı için)
     let lan_ip = local_ip()
         .ok()
@@ -52,6 +69,7 @@ fn get_sidecar_config(allow_lan_sharing: bool) -> Result<ConfigResponse, String>
         port: selected_port,
         lan_ip,
         bind_address: bind_addr.to_string(),
+        args,
     })
 }
 
@@ -61,162 +79,126 @@ fn greet(name: &str) -> String {
 }
 
 #[tauri::command]
-fn clear_system_proxy() -> Result<(), String> {
-    #[cfg(target_os = "windows")]
-    {
-        use std::os::windows::process::CommandExt;
-        use std::process::Command;
-
-        const CREATE_NO_WINDOW: u32 = 0x08000000;
+fn clear_system_proxy(app: tauri::AppHandle) -> Result<(), String> {
+    let result = disable_system_proxy(&app);
+    restore_integrations(&app);
+    result
+}
 
-        let status = Command::new("reg")
-            .args(&[
-                "add",
-                "HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Internet Settings",
-                "/v",
-                "ProxyEnable",
-                "/t",
-                "REG_DWORD",
-                "/d",
-                "0",
-                "/f",
-            ])
-            .creation_flags(CREATE_NO_WINDOW)
-            .status()
-            .map_err(|e| e.to_string())?;
+/// Yalnızca sistem proxy'sini kapatır; araç entegrasyonları uygulanmış kalır.
+fn disable_system_proxy(app: &tauri::AppHandle) -> Result<(), String> {
+    use tauri::Manager;
 
-        if !status.success() {
-            return Err("Failed to clear proxy via registry".to_string());
-        }
+    // Kullanıcının Vexar'dan önceki ayarlarını (kurumsal proxy, PAC, bypass) geri yükle
+    app.state::<proxy::ProxyController>().restore()
+}
 
-        // Notify browsers about the change
-        notify_proxy_change();
+/// `servers`: `host:port` veya `http=host:port;https=host:port;socks=host:port`.
+#[tauri::command]
+fn set_system_proxy(
+    app: tauri::AppHandle,
+    servers: String,
+    mode: Option<proxy::ProxyMode>,
+) -> Result<(), String> {
+    use tauri::Manager;
+
+    let servers = proxy::servers::ProxyServers::parse(&servers)?;
+    enable_system_proxy(&app, &servers, mode.unwrap_or_default())?;
+    let settings = app.state::<settings::SettingsStore>().get();
+
+    // Araç entegrasyonlarının hatası bağlantıyı engellemez; arayüze bildirilir
+    if let Some(endpoint) = servers.primary() {
+        let result = app.state::<integrations::Integrations>().apply(
+            &settings.integrations,
+            endpoint,
+            &settings.bypass,
+        );
+        report_integrations(&app, result);
     }
     Ok(())
 }
 
-/// Notify Windows that internet settings have changed
-/// This forces browsers to immediately pick up the new proxy settings
-#[cfg(target_os = "windows")]
-fn notify_proxy_change() {
-    use std::ptr::null_mut;
-    use winapi::um::wininet::{
-        INTERNET_OPTION_REFRESH, INTERNET_OPTION_SETTINGS_CHANGED, InternetSetOptionW,
-    };
+/// Geliştirici araçlarını Vexar'dan önceki ayarlarına döndürür.
+/// Yalnızca sistem proxy'sini açar; araç entegrasyonlarına dokunmaz.
+fn enable_system_proxy(
+    app: &tauri::AppHandle,
+    servers: &proxy::servers::ProxyServers,
+    mode: proxy::ProxyMode,
+) -> Result<(), String> {
+    use tauri::Manager;
+
+    let settings = app.state::<settings::SettingsStore>().get();
+    app.state::<proxy::ProxyController>().enable(
+        servers,
+        &settings.bypass,
+        mode,
+        &settings.split_tunnel,
+    )
+}
 
-    unsafe {
-        // Notify that settings have changed
-        InternetSetOptionW(null_mut(), INTERNET_OPTION_SETTINGS_CHANGED, null_mut(), 0);
-        // Refresh the settings
-        InternetSetOptionW(null_mut(), INTERNET_OPTION_REFRESH, null_mut(), 0);
-    }
+fn restore_integrations(app: &tauri::AppHandle) {
+    use tauri::Manager;
+
+    let result = app.state::<integrations::Integrations>().restore();
+    report_integrations(app, result);
 }
 
-#[tauri::command]
-fn set_system_proxy(port: u16) -> Result<(), String> {
-    // ✅ Port aralığı validasyonu
-    if port < 1024 {
-        return Err("Geçersiz port numarası (1024-65535 arası olmalı)".to_string());
+fn report_integrations(app: &tauri::AppHandle, result: Result<(), String>) {
+    use tauri::Emitter;
+
+    if let Err(reason) = result {
+        let _ = app.emit(integrations::FAILED_EVENT, reason);
     }
+}
 
-    #[cfg(target_os = "windows")]
-    {
-        use std::os::windows::process::CommandExt;
-        use std::process::Command;
+#[tauri::command]
+fn get_system_proxy(app: tauri::AppHandle) -> Result<proxy::SystemProxyStatus, String> {
+    use tauri::Manager;
 
-        const CREATE_NO_WINDOW: u32 = 0x08000000;
-        let proxy_address = format!("127.0.0.1:{}", port);
-
-        // ✅ Registry yazma iznini kontrol et
-        let test_status = Command::new("reg")
-            .args(&[
-                "query",
-                "HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Internet Settings",
-            ])
-            .creation_flags(CREATE_NO_WINDOW)
-            .output()
-            .map_err(|e| format!("Registry erişim hatası: {e}"))?;
+    app.state::<proxy::ProxyController>().status()
+}
 
-        if !test_status.status.success() {
-            return Err(
-                "Registry yazma izni yok. Uygulamayı yönetici olarak çalıştırın.".to_string(),
-            );
-        }
+#[tauri::command]
+fn list_bypass(app: tauri::AppHandle) -> Vec<String> {
+    use tauri::Manager;
 
-        // ✅ ProxyOverride ekle (localhost bypass)
-        let _ = Command::new("reg")
-            .args(&[
-                "add",
-                "HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Internet Settings",
-                "/v",
-                "ProxyOverride",
-                "/t",
-                "REG_SZ",
-                "/d",
-                "<local>",
-                "/f",
-            ])
-            .creation_flags(CREATE_NO_WINDOW)
-            .status();
+    app.state::<settings::SettingsStore>().get().bypass.into()
+}
 
-        // 1. Set Proxy Server Address
-        let status_server = Command::new("reg")
-            .args(&[
-                "add",
-                "HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Internet Settings",
-                "/v",
-                "ProxyServer",
-                "/t",
-                "REG_SZ",
-                "/d",
-                &proxy_address,
-                "/f",
-            ])
-            .creation_flags(CREATE_NO_WINDOW)
-            .status()
-            .map_err(|e| format!("ProxyServer ayarlanamadı: {e}"))?;
-
-        // 2. Enable Proxy
-        let status_enable = Command::new("reg")
-            .args(&[
-                "add",
-                "HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Internet Settings",
-                "/v",
-                "ProxyEnable",
-                "/t",
-                "REG_DWORD",
-                "/d",
-                "1",
-                "/f",
-            ])
-            .creation_flags(CREATE_NO_WINDOW)
-            .status()
-            .map_err(|e| format!("ProxyEnable ayarlanamadı: {e}"))?;
-
-        if !status_server.success() || !status_enable.success() {
-            // ✅ Rollback yap
-            let _ = Command::new("reg")
-                .args(&[
-                    "add",
-                    "HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Internet Settings",
-                    "/v",
-                    "ProxyEnable",
-                    "/t",
-                    "REG_DWORD",
-                    "/d",
-                    "0",
-                    "/f",
-                ])
-                .creation_flags(CREATE_NO_WINDOW)
-                .status();
+#[tauri::command]
+fn add_bypass(app: tauri::AppHandle, entry: String) -> Result<Vec<String>, String> {
+    let entry = proxy::bypass::BypassEntry::parse(&entry)?;
+    update_bypass(&app, |list| {
+        list.add(entry);
+    })
+}
 
-            return Err("Registry güncelleme başarısız, geri alındı.".to_string());
-        }
+#[tauri::command]
+fn remove_bypass(app: tauri::AppHandle, entry: String) -> Result<Vec<String>, String> {
+    let entry = proxy::bypass::BypassEntry::parse(&entry)?;
+    update_bypass(&app, |list| {
+        list.remove(&entry);
+    })
+}
 
-        // 3. CRITICAL: Notify Windows about the change so browsers pick it up immediately
-        notify_proxy_change();
-    }
-    Ok(())
+/// Listeyi kaydeder ve proxy açıksa canlı olarak uygular.
+fn update_bypass(
+    app: &tauri::AppHandle,
+    change: impl FnOnce(&mut proxy::bypass::BypassList),
+) -> Result<Vec<String>, String> {
+    use tauri::Manager;
+
+    let store = app.state::<settings::SettingsStore>();
+    let mut settings = store.get();
+    change(&mut settings.bypass);
+    let settings = store.replace(settings)?;
+    app.state::<proxy::ProxyController>()
+        .update_bypass(&settings.bypass)?;
+    let result = app
+        .state::<integrations::Integrations>()
+        .refresh(&settings.integrations, &settings.bypass);
+    report_integrations(app, result);
+    Ok(settings.bypass.into())
 }
 
 #[tauri::command]
@@ -227,6 +209,62 @@ fn update_tray_tooltip(app: tauri::AppHandle, tooltip: String) -> Result<(), Str
     Ok(())
 }
 
+/// Tepsi menüsü; başlatıcıya eklenen uygulamalar alt menüde listelenir.
+#[cfg(desktop)]
+fn build_tray_menu(app: &tauri::AppHandle) -> tauri::Result<tauri::menu::Menu<tauri::Wry>> {
+    use tauri::Manager;
+    use tauri::menu::{IsMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu};
+
+    let show_i = MenuItem::with_id(app, "show", "Uygulamayı Aç", true, None::<&str>)?;
+    let pause_i = MenuItem::with_id(app, "pause", "5 dk Duraklat", true, None::<&str>)?;
+    let resume_i = MenuItem::with_id(app, "resume", "Devam Et", true, None::<&str>)?;
+    let support_i = MenuItem::with_id(app, "support", "Destekle ❤", true, None::<&str>)?;
+    let quit_i = MenuItem::with_id(app, "quit", "Çıkış", true, None::<&str>)?;
+
+    let apps = app.state::<settings::SettingsStore>().get().launcher.apps;
+    let app_items = apps
+        .iter()
+        .map(|entry| {
+            let id = format!("{}{}", launcher::MENU_PREFIX, entry.id);
+            MenuItem::with_id(app, id, &entry.name, true, None::<&str>)
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+    let app_refs: Vec<&dyn IsMenuItem<tauri::Wry>> = app_items
+        .iter()
+        .map(|item| item as &dyn IsMenuItem<tauri::Wry>)
+        .collect();
+    let launch_i = Submenu::with_id_and_items(
+        app,
+        "launcher",
+        "Proxy ile Başlat",
+        !app_refs.is_empty(),
+        &app_refs,
+    )?;
+
+    let s1 = PredefinedMenuItem::separator(app)?;
+    let s2 = PredefinedMenuItem::separator(app)?;
+    let s3 = PredefinedMenuItem::separator(app)?;
+
+    Menu::with_items(
+        app,
+        &[
+            &show_i, &s1, &pause_i, &resume_i, &launch_i, &s2, &support_i, &s3, &quit_i,
+        ],
+    )
+}
+
+/// Başlatıcı listesi değişince tepsi menüsünü yeniden kurar.
+fn refresh_tray_menu(app: &tauri::AppHandle) {
+    #[cfg(desktop)]
+    {
+        if let (Some(tray), Ok(menu)) = (app.tray_by_id("tray"), build_tray_menu(app)) {
+            let _ = tray.set_menu(Some(menu));
+        }
+    }
+    #[cfg(not(desktop))]
+    let _ = app;
+}
+
 #[derive(serde::Serialize)]
 struct SystemSpecs {
     cpu_model: String,
@@ -472,23 +510,42 @@ fn check_admin() -> bool {
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        .manage(engine::EngineSupervisor::default())
         .setup(|app| {
+            use tauri::Manager;
+
+            // Uygulama öldürüldüğünde veya çöktüğünde geride kalan motorları temizle
+            engine::sweep_stale_engines();
+
+            let config_dir = app.path().app_config_dir()?;
+
+            // Önceki oturum proxy açıkken öldüyse kullanıcının ayarlarını geri yükle
+            let proxy_controller = proxy::ProxyController::new(
+                proxy::platform_backend(&config_dir),
+                proxy::journal::ProxyJournal::new(config_dir.join(proxy::journal::JOURNAL_FILE)),
+            );
+            let _ = proxy_controller.recover(proxy::journal::owner_is_alive);
+            app.manage(proxy_controller);
+
+            let integrations = integrations::Integrations::new(
+                integrations::devtools::ToolPaths::new(&app.path().home_dir()?, &config_dir),
+                integrations::journal::IntegrationJournal::new(
+                    config_dir.join(integrations::journal::JOURNAL_FILE),
+                ),
+            );
+            let _ = integrations.recover(proxy::journal::process_is_alive);
+            app.manage(integrations);
+
+            app.manage(settings::SettingsStore::load(
+                config_dir.join(settings::SETTINGS_FILE),
+            ));
+            app.manage(launcher::Launcher::default());
+
             #[cfg(desktop)]
             {
-                use tauri::Manager;
-                use tauri::menu::{Menu, MenuItem};
                 use tauri::tray::TrayIconBuilder;
 
-                let show_i = MenuItem::with_id(app, "show", "Uygulamayı Aç", true, None::<&str>)?;
-                let support_i =
-                    MenuItem::with_id(app, "support", "Destekle ❤", true, None::<&str>)?;
-                let quit_i = MenuItem::with_id(app, "quit", "Çıkış", true, None::<&str>)?;
-
-                use tauri::menu::PredefinedMenuItem;
-                let s1 = PredefinedMenuItem::separator(app)?;
-                let s2 = PredefinedMenuItem::separator(app)?;
-
-                let menu = Menu::with_items(app, &[&show_i, &s1, &support_i, &s2, &quit_i])?;
+                let menu = build_tray_menu(app.handle())?;
 
                 // ✅ Debounce için flag
                 let is_showing = Arc::new(AtomicBool::new(false));
@@ -499,10 +556,19 @@ pub fn run() {
                     .tooltip("Vexar - Kapalı")
                     .on_menu_event(|app, event| match event.id.as_ref() {
                         "quit" => {
-                            let _ = clear_system_proxy();
+                            app.state::<engine::EngineSupervisor>().shutdown(app);
                             std::thread::sleep(std::time::Duration::from_millis(200));
                             app.exit(0);
                         }
+                        "pause" => {
+                            // Bağlı değilse yapılacak bir şey yok
+                            let _ = app
+                                .state::<engine::EngineSupervisor>()
+                                .pause(app, engine::pause::TRAY_PAUSE);
+                        }
+                        "resume" => {
+                            let _ = app.state::<engine::EngineSupervisor>().resume(app);
+                        }
                         "show" => {
                             if let Some(window) = app.get_webview_window("main") {
                                 let _ = window.show();
@@ -515,7 +581,11 @@ pub fn run() {
                                 .open_url("https://www.patreon.com/join/ConsolAktif", None::<&str>)
                                 .unwrap_or(());
                         }
-                        _ => {}
+                        id => {
+                            if let Some(entry) = id.strip_prefix(launcher::MENU_PREFIX) {
+                                launcher::launch_from_tray(app, entry.to_string());
+                            }
+                        }
                     })
                     .on_tray_icon_event({
                         let is_showing = Arc::clone(&is_showing);
@@ -556,9 +626,12 @@ pub fn run() {
 
                 // LAYER 2: Window close cleanup
                 if let Some(window) = app.get_webview_window("main") {
-                    window.on_window_event(|event| {
+                    let app_handle = app.handle().clone();
+                    window.on_window_event(move |event| {
                         if let tauri::WindowEvent::Destroyed = event {
-                            let _ = clear_system_proxy();
+                            app_handle
+                                .state::<engine::EngineSupervisor>()
+                                .shutdown(&app_handle);
                         }
                     });
                 }
@@ -574,17 +647,49 @@ pub fn run() {
             greet,
             clear_system_proxy,
             set_system_proxy,
+            get_system_proxy,
+            list_bypass,
+            add_bypass,
+            remove_bypass,
             update_tray_tooltip,
             get_system_specs,
             check_admin,
-            get_sidecar_config
+            get_sidecar_config,
+            engine::start_engine,
+            engine::stop_engine,
+            engine::connect_remote,
+            engine::discover_remote_engines,
+            engine::pause_proxy,
+            engine::resume_proxy,
+            engine::engine_status,
+            engine::lan::list_lan_acl,
+            engine::lan::add_lan_acl,
+            engine::lan::remove_lan_acl,
+            engine::lan::seed_lan_acl,
+            engine::lan::get_lan_auth,
+            engine::lan::set_lan_auth,
+            engine::lan::rotate_lan_credentials,
+            engine::lan::list_lan_clients,
+            engine::lan::disconnect_lan_client,
+            engine::lan::set_lan_client_limit,
+            engine::lan::get_lan_shaping,
+            engine::lan::update_lan_shaping,
+            settings::get_settings,
+            settings::update_settings,
+            integrations::update_integrations,
+            integrations::list_firefox_profiles,
+            launcher::update_launcher,
+            launcher::launch_app
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
-        .run(|_app_handle, event| {
+        .run(|app_handle, event| {
             // LAYER 3: App exit cleanup (fallback)
             if let tauri::RunEvent::ExitRequested { .. } = event {
-                let _ = clear_system_proxy();
+                use tauri::Manager;
+                app_handle
+                    .state::<engine::EngineSupervisor>()
+                    .shutdown(app_handle);
                 std::thread::sleep(std::time::Duration::from_millis(200));
             }
         });
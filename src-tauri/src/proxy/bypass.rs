@@ -0,0 +1,275 @@
+//! Sistem proxy'sinden muaf tutulan adresler.
+//!
+//! Liste `settings.json` içinde saklanır ve WinINet'in `ProxyOverride`
+//! biçimine (`;` ile ayrılmış) dönüştürülerek yazılır. Girdiler joker
+//! karakterli host kalıpları (`*.bank.example`), CIDR aralıkları
+//! (`10.0.0.0/8`) veya yerel adresleri kapsayan `<local>` olabilir.
+
+use std::net::IpAddr;
+
+/// Yerel (noktasız) adları ve loopback'i kapsayan WinINet belirteci.
+pub const LOCAL_TOKEN: &str = "<local>";
+/// `<local>`'in `NO_PROXY` karşılığı; noktasız adlar için eşdeğer bir kalıp yok.
+const NO_PROXY_LOCAL: [&str; 3] = ["localhost", "127.0.0.0/8", "::1"];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BypassEntry {
+    Local,
+    /// Küçük harfe çevrilmiş host kalıbı veya IP adresi.
+    Host(String),
+    /// Ağ adresine indirgenmiş aralık.
+    Cidr(IpAddr, u8),
+}
+
+impl BypassEntry {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err("Boş bypass girdisi".to_string());
+        }
+        if input.eq_ignore_ascii_case(LOCAL_TOKEN) {
+            return Ok(BypassEntry::Local);
+        }
+        if let Some((addr, prefix)) = input.split_once('/') {
+            let addr: IpAddr = addr
+                .parse()
+                .map_err(|_| format!("Geçersiz CIDR adresi: {input}"))?;
+            let max = if addr.is_ipv4() { 32 } else { 128 };
+            let prefix = prefix
+                .parse::<u8>()
+                .ok()
+                .filter(|p| *p <= max)
+                .ok_or_else(|| format!("Geçersiz CIDR önek uzunluğu: {input}"))?;
+            return Ok(BypassEntry::Cidr(network(addr, prefix), prefix));
+        }
+        if let Ok(addr) = input.parse::<IpAddr>() {
+            return Ok(BypassEntry::Host(addr.to_string()));
+        }
+        validate_host_pattern(input)?;
+        Ok(BypassEntry::Host(input.to_ascii_lowercase()))
+    }
+}
+
+impl std::fmt::Display for BypassEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BypassEntry::Local => f.write_str(LOCAL_TOKEN),
+            BypassEntry::Host(host) => f.write_str(host),
+            BypassEntry::Cidr(addr, prefix) => write!(f, "{addr}/{prefix}"),
+        }
+    }
+}
+
+/// Joker karakter yalnızca tam bir etiket olarak kullanılabilir
+/// (`*.example.com`, `192.168.*`); tek başına `*` her şeyi muaf tutacağından reddedilir.
+fn validate_host_pattern(pattern: &str) -> Result<(), String> {
+    let invalid = || format!("Geçersiz host kalıbı: {pattern}");
+    if pattern.len() > 253 || pattern == "*" {
+        return Err(invalid());
+    }
+    for label in pattern.split('.') {
+        let valid = label == "*"
+            || (!label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        if !valid {
+            return Err(invalid());
+        }
+    }
+    Ok(())
+}
+
+/// Adresin önek dışındaki bitlerini sıfırlar.
+pub(crate) fn network(addr: IpAddr, prefix: u8) -> IpAddr {
+    match addr {
+        IpAddr::V4(v4) => {
+            let mask = u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0);
+            IpAddr::V4((u32::from(v4) & mask).into())
+        }
+        IpAddr::V6(v6) => {
+            let mask = u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0);
+            IpAddr::V6((u128::from(v6) & mask).into())
+        }
+    }
+}
+
+/// Sıralı ve tekrarsız bypass listesi.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "Vec<String>", into = "Vec<String>")]
+pub struct BypassList(Vec<BypassEntry>);
+
+impl Default for BypassList {
+    /// Önceki sabit `ProxyOverride = <local>` davranışı.
+    fn default() -> Self {
+        BypassList(vec![BypassEntry::Local])
+    }
+}
+
+impl BypassList {
+    pub fn entries(&self) -> &[BypassEntry] {
+        &self.0
+    }
+
+    /// Girdiyi ekler; zaten listedeyse false döner.
+    pub fn add(&mut self, entry: BypassEntry) -> bool {
+        if self.0.contains(&entry) {
+            return false;
+        }
+        self.0.push(entry);
+        true
+    }
+
+    /// Girdiyi çıkarır; listede yoksa false döner.
+    pub fn remove(&mut self, entry: &BypassEntry) -> bool {
+        let before = self.0.len();
+        self.0.retain(|e| e != entry);
+        self.0.len() != before
+    }
+
+    /// `ProxyOverride` değeri.
+    pub fn to_wininet(&self) -> String {
+        self.0
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+
+    /// CLI araçlarının anladığı `NO_PROXY` değeri. `*.example` alt alan
+    /// adlarını kapsayan `.example` olur; ortadaki jokerlerin karşılığı
+    /// olmadığından bu girdiler atlanır.
+    pub fn to_no_proxy(&self) -> String {
+        let mut hosts: Vec<String> = Vec::new();
+        for entry in &self.0 {
+            let expanded = match entry {
+                BypassEntry::Local => NO_PROXY_LOCAL.iter().map(ToString::to_string).collect(),
+                BypassEntry::Host(host) => match host.strip_prefix('*') {
+                    Some(rest) if !rest.contains('*') => vec![rest.to_string()],
+                    _ if host.contains('*') => Vec::new(),
+                    _ => vec![host.clone()],
+                },
+                BypassEntry::Cidr(..) => vec![entry.to_string()],
+            };
+            for host in expanded {
+                if !hosts.contains(&host) {
+                    hosts.push(host);
+                }
+            }
+        }
+        hosts.join(",")
+    }
+
+    /// `ProxyOverride` değerini okur; boş parçalar atlanır, tekrarlar birleştirilir.
+    pub fn parse_wininet(value: &str) -> Result<Self, String> {
+        let mut list = BypassList(Vec::new());
+        for part in value.split(';').filter(|p| !p.trim().is_empty()) {
+            list.add(BypassEntry::parse(part)?);
+        }
+        Ok(list)
+    }
+}
+
+impl TryFrom<Vec<String>> for BypassList {
+    type Error = String;
+
+    fn try_from(entries: Vec<String>) -> Result<Self, String> {
+        let mut list = BypassList(Vec::new());
+        for entry in entries {
+            list.add(BypassEntry::parse(&entry)?);
+        }
+        Ok(list)
+    }
+}
+
+impl From<BypassList> for Vec<String> {
+    fn from(list: BypassList) -> Self {
+        list.0.iter().map(ToString::to_string).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_entry_kinds() {
+        assert_eq!(BypassEntry::parse(" <LOCAL> ").unwrap(), BypassEntry::Local);
+        assert_eq!(
+            BypassEntry::parse("*.Bank.Example").unwrap(),
+            BypassEntry::Host("*.bank.example".to_string())
+        );
+        assert_eq!(
+            BypassEntry::parse("192.168.*").unwrap(),
+            BypassEntry::Host("192.168.*".to_string())
+        );
+        assert_eq!(
+            BypassEntry::parse("10.1.2.3/8").unwrap(),
+            BypassEntry::Cidr("10.0.0.0".parse().unwrap(), 8)
+        );
+        assert_eq!(
+            BypassEntry::parse("fd12:3456::1/16").unwrap().to_string(),
+            "fd12::/16"
+        );
+        assert_eq!(BypassEntry::parse("::1").unwrap().to_string(), "::1");
+    }
+
+    #[test]
+    fn rejects_invalid_entries() {
+        for input in [
+            "",
+            "*",
+            "bad host",
+            "a..b",
+            "-x.example",
+            "10.0.0.0/33",
+            "::/129",
+            "host/8",
+            "http://x",
+        ] {
+            assert!(BypassEntry::parse(input).is_err(), "{input:?} kabul edildi");
+        }
+    }
+
+    #[test]
+    fn round_trips_wininet_format() {
+        let list =
+            BypassList::parse_wininet("*.corp.example;<local>;;10.0.0.0/8;*.CORP.example").unwrap();
+        assert_eq!(list.entries().len(), 3);
+        assert_eq!(list.to_wininet(), "*.corp.example;<local>;10.0.0.0/8");
+        assert_eq!(BypassList::parse_wininet(&list.to_wininet()).unwrap(), list);
+    }
+
+    #[test]
+    fn converts_to_no_proxy() {
+        let list =
+            BypassList::parse_wininet("<local>;*.corp.example;192.168.*;10.0.0.0/8;::1").unwrap();
+        assert_eq!(
+            list.to_no_proxy(),
+            "localhost,127.0.0.0/8,::1,.corp.example,10.0.0.0/8"
+        );
+    }
+
+    #[test]
+    fn add_and_remove_deduplicate() {
+        let mut list = BypassList::default();
+        assert!(!list.add(BypassEntry::parse("<local>").unwrap()));
+        assert!(list.add(BypassEntry::parse("172.16.5.0/12").unwrap()));
+        assert!(!list.add(BypassEntry::parse("172.16.0.0/12").unwrap()));
+        assert!(list.remove(&BypassEntry::parse("172.31.0.0/12").unwrap()));
+        assert!(!list.remove(&BypassEntry::parse("*.example").unwrap()));
+        assert_eq!(list, BypassList::default());
+    }
+
+    #[test]
+    fn serializes_as_strings() {
+        let list = BypassList::parse_wininet("<local>;*.bank.example").unwrap();
+        let json = serde_json::to_string(&list).unwrap();
+        assert_eq!(json, r#"["<local>","*.bank.example"]"#);
+        assert_eq!(serde_json::from_str::<BypassList>(&json).unwrap(), list);
+        assert!(serde_json::from_str::<BypassList>(r#"["*"]"#).is_err());
+    }
+}
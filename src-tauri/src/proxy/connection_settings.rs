@@ -0,0 +1,323 @@
+//! WinINet `Connections\DefaultConnectionSettings` ikili değeri.
+//!
+//! Windows proxy ayarlarının asıl kaynağı bu değerdir; `ProxyEnable` ve
+//! `ProxyServer` gibi metin değerleri yalnızca eski uygulamalar için tutulur
+//! ve bazı uygulamalar yalnızca bu değeri okur. Biçim (tüm sayılar little-endian):
+//!
+//! | alan            | boyut          |
+//! |-----------------|----------------|
+//! | sürüm           | u32            |
+//! | değişiklik sayacı | u32          |
+//! | bayraklar       | u32            |
+//! | proxy sunucusu  | u32 uzunluk + ANSI |
+//! | bypass listesi  | u32 uzunluk + ANSI |
+//! | PAC adresi      | u32 uzunluk + ANSI |
+//! | geri kalan      | WPAD önbelleği vb.; olduğu gibi korunur |
+//!
+//! Metinler sistemin ANSI kod sayfasındadır ve çözülmeden bayt olarak
+//! tutulur; okunan değer, içinde ne olursa olsun aynen geri yazılabilir.
+
+use super::ProxySnapshot;
+
+pub const VALUE_NAME: &str = "DefaultConnectionSettings";
+
+/// Windows 7 ve sonrasının yazdığı sürüm.
+const DEFAULT_VERSION: u32 = 0x46;
+
+pub const FLAG_DIRECT: u32 = 0x01;
+pub const FLAG_PROXY: u32 = 0x02;
+pub const FLAG_AUTO_CONFIG_URL: u32 = 0x04;
+pub const FLAG_AUTO_DETECT: u32 = 0x08;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConnectionSettings {
+    pub version: u32,
+    /// Her yazmada artar; WinINet değişikliği bununla fark eder.
+    pub counter: u32,
+    pub flags: u32,
+    /// ANSI baytları.
+    pub proxy_server: Vec<u8>,
+    pub bypass: Vec<u8>,
+    pub auto_config_url: Vec<u8>,
+    /// PAC adresinden sonraki baytlar (WPAD sonuçları vb.).
+    pub trailer: Vec<u8>,
+}
+
+impl Default for ConnectionSettings {
+    /// Hiç proxy ayarlanmamış bir sistemin değeri.
+    fn default() -> Self {
+        Self {
+            version: DEFAULT_VERSION,
+            counter: 0,
+            flags: FLAG_DIRECT,
+            proxy_server: Vec::new(),
+            bypass: Vec::new(),
+            auto_config_url: Vec::new(),
+            trailer: vec![0; 32],
+        }
+    }
+}
+
+impl ConnectionSettings {
+    pub fn decode(bytes: &[u8]) -> Result<Self, String> {
+        let mut reader = Reader { bytes, offset: 0 };
+        Ok(Self {
+            version: reader.u32()?,
+            counter: reader.u32()?,
+            flags: reader.u32()?,
+            proxy_server: reader.string()?.to_vec(),
+            bypass: reader.string()?.to_vec(),
+            auto_config_url: reader.string()?.to_vec(),
+            trailer: bytes[reader.offset..].to_vec(),
+        })
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(
+            24 + self.proxy_server.len()
+                + self.bypass.len()
+                + self.auto_config_url.len()
+                + self.trailer.len(),
+        );
+        out.extend_from_slice(&self.version.to_le_bytes());
+        out.extend_from_slice(&self.counter.to_le_bytes());
+        out.extend_from_slice(&self.flags.to_le_bytes());
+        for text in [&self.proxy_server, &self.bypass, &self.auto_config_url] {
+            out.extend_from_slice(&(text.len() as u32).to_le_bytes());
+            out.extend_from_slice(text);
+        }
+        out.extend_from_slice(&self.trailer);
+        out
+    }
+
+    /// Metin değerlerine yazılan görüntüyü ikili değere yansıtır ve sayacı artırır.
+    /// Görüntüde olmayan alanlar boşaltılır; sürüm ve ek baytlar korunur.
+    /// ANSI'ye çevrilemeyen bir metin varsa hiçbir alan değişmez.
+    pub fn apply_snapshot(&mut self, snapshot: &ProxySnapshot) -> Result<(), String> {
+        let text = |value: &Option<String>| to_ansi(value.as_deref().unwrap_or_default());
+        let proxy_server = text(&snapshot.server)?;
+        let bypass = text(&snapshot.bypass)?;
+        let auto_config_url = text(&snapshot.auto_config_url)?;
+        let mut flags = FLAG_DIRECT;
+        if snapshot.enabled {
+            flags |= FLAG_PROXY;
+        }
+        if snapshot.auto_config_url.is_some() {
+            flags |= FLAG_AUTO_CONFIG_URL;
+        }
+        if snapshot.auto_detect == Some(true) {
+            flags |= FLAG_AUTO_DETECT;
+        }
+        self.flags = flags;
+        self.proxy_server = proxy_server;
+        self.bypass = bypass;
+        self.auto_config_url = auto_config_url;
+        self.counter = self.counter.wrapping_add(1);
+        Ok(())
+    }
+
+    /// İkili değerdeki ayarların metin değerleriyle aynı olup olmadığı.
+    pub fn matches(&self, snapshot: &ProxySnapshot) -> bool {
+        let mut expected = self.clone();
+        if expected.apply_snapshot(snapshot).is_err() {
+            return false;
+        }
+        expected.counter = self.counter;
+        expected == *self
+    }
+}
+
+/// Metni sistemin ANSI kod sayfasına çevirir; karşılığı olmayan karakter
+/// varsa benzerini yazmak yerine hata döner.
+#[cfg(windows)]
+fn to_ansi(text: &str) -> Result<Vec<u8>, String> {
+    use std::ptr::{null, null_mut};
+    use winapi::um::stringapiset::WideCharToMultiByte;
+    use winapi::um::winnls::{CP_ACP, WC_NO_BEST_FIT_CHARS};
+
+    if text.is_ascii() {
+        return Ok(text.as_bytes().to_vec());
+    }
+    let wide: Vec<u16> = text.encode_utf16().collect();
+    let invalid = || format!("{VALUE_NAME} için ANSI'ye çevrilemeyen metin: {text}");
+    let mut used_default = 0;
+    // SAFETY: tamponlar bildirilen uzunluklarla geçerli
+    unsafe {
+        let len = WideCharToMultiByte(
+            CP_ACP,
+            WC_NO_BEST_FIT_CHARS,
+            wide.as_ptr(),
+            wide.len() as i32,
+            null_mut(),
+            0,
+            null(),
+            null_mut(),
+        );
+        if len <= 0 {
+            return Err(invalid());
+        }
+        let mut out = vec![0u8; len as usize];
+        let written = WideCharToMultiByte(
+            CP_ACP,
+            WC_NO_BEST_FIT_CHARS,
+            wide.as_ptr(),
+            wide.len() as i32,
+            out.as_mut_ptr().cast(),
+            len,
+            null(),
+            &mut used_default,
+        );
+        if written != len || used_default != 0 {
+            return Err(invalid());
+        }
+        Ok(out)
+    }
+}
+
+/// Windows dışında (testlerde) Latin-1 eşlemesi kullanılır.
+#[cfg(not(windows))]
+fn to_ansi(text: &str) -> Result<Vec<u8>, String> {
+    text.chars()
+        .map(|c| u8::try_from(u32::from(c)).ok())
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(|| format!("{VALUE_NAME} için ANSI'ye çevrilemeyen metin: {text}"))
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8], String> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| format!("{VALUE_NAME} eksik: {} bayt", self.bytes.len()))?;
+        let slice = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(slice)
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn string(&mut self) -> Result<&[u8], String> {
+        let len = self.u32()? as usize;
+        self.take(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Örnekler yukarıdaki düzene göre elle hazırlandı, Windows'tan alınmadı.
+    // Windows 10/11'den gerçek dışa aktarımlar (proxy açık, PAC, otomatik
+    // algılama, hepsi kapalı) eklenmeli; değer şuradan alınır:
+    // HKCU\Software\Microsoft\Windows\CurrentVersion\Internet Settings\Connections
+    /// Proxy yok, otomatik algılama açık.
+    const DIRECT: &[u8] = include_bytes!("testdata/dcs_direct.bin");
+    /// Kurumsal manuel proxy, bypass listesi ve PAC betiği.
+    const CORPORATE: &[u8] = include_bytes!("testdata/dcs_corporate.bin");
+    /// Windows-1254 metinli proxy ve bypass, dolu WPAD baytları.
+    const ANSI: &[u8] = include_bytes!("testdata/dcs_ansi.bin");
+
+    #[test]
+    fn decodes_direct_blob() {
+        let settings = ConnectionSettings::decode(DIRECT).unwrap();
+        assert_eq!(settings.version, 0x46);
+        assert_eq!(settings.counter, 3);
+        assert_eq!(settings.flags, FLAG_DIRECT | FLAG_AUTO_DETECT);
+        assert!(settings.proxy_server.is_empty());
+        assert!(settings.auto_config_url.is_empty());
+        assert_eq!(settings.trailer.len(), 32);
+    }
+
+    #[test]
+    fn decodes_corporate_blob() {
+        let settings = ConnectionSettings::decode(CORPORATE).unwrap();
+        assert_eq!(settings.counter, 0x1f);
+        assert_eq!(
+            settings.flags,
+            FLAG_DIRECT | FLAG_PROXY | FLAG_AUTO_CONFIG_URL
+        );
+        assert_eq!(settings.proxy_server, b"corp.example:3128");
+        assert_eq!(settings.bypass, b"*.corp.example;<local>");
+        assert_eq!(
+            settings.auto_config_url,
+            b"http://wpad.corp.example/proxy.pac"
+        );
+    }
+
+    #[test]
+    fn round_trips_sample_blobs() {
+        for blob in [DIRECT, CORPORATE, ANSI] {
+            assert_eq!(ConnectionSettings::decode(blob).unwrap().encode(), blob);
+        }
+    }
+
+    #[test]
+    fn applies_snapshot_and_bumps_counter() {
+        let mut settings = ConnectionSettings::decode(CORPORATE).unwrap();
+        let snapshot = ProxySnapshot {
+            enabled: true,
+            server: Some("127.0.0.1:8080".to_string()),
+            bypass: Some("<local>".to_string()),
+            auto_config_url: None,
+            auto_detect: Some(false),
+        };
+        assert!(!settings.matches(&snapshot));
+        settings.apply_snapshot(&snapshot).unwrap();
+        assert_eq!(settings.counter, 0x20);
+        assert_eq!(settings.flags, FLAG_DIRECT | FLAG_PROXY);
+        assert_eq!(settings.proxy_server, b"127.0.0.1:8080");
+        assert!(settings.auto_config_url.is_empty());
+        assert!(settings.matches(&snapshot));
+
+        let decoded = ConnectionSettings::decode(&settings.encode()).unwrap();
+        assert_eq!(decoded, settings);
+    }
+
+    #[test]
+    fn keeps_non_ascii_text_and_trailer() {
+        let mut settings = ConnectionSettings::decode(ANSI).unwrap();
+        assert_eq!(settings.proxy_server, b"proxy.\xfeirket.local:8080");
+        assert_eq!(settings.bypass, b"*.intranet;<local>;caf\xe9.example");
+        let trailer = settings.trailer.clone();
+
+        let snapshot = ProxySnapshot {
+            enabled: true,
+            server: Some("127.0.0.1:8080".to_string()),
+            bypass: Some("*.intranet;<local>;café.example".to_string()),
+            ..ProxySnapshot::default()
+        };
+        settings.apply_snapshot(&snapshot).unwrap();
+        assert_eq!(settings.version, 0x46);
+        assert_eq!(settings.counter, 0x2b);
+        assert_eq!(settings.bypass, b"*.intranet;<local>;caf\xe9.example");
+        assert_eq!(settings.trailer, trailer);
+
+        // Kod sayfasında karşılığı olmayan metin yazılmaz, değer değişmez
+        let before = settings.clone();
+        let unmappable = ProxySnapshot {
+            bypass: Some("*.例え.jp".to_string()),
+            ..snapshot
+        };
+        assert!(settings.apply_snapshot(&unmappable).is_err());
+        assert!(!settings.matches(&unmappable));
+        assert_eq!(settings, before);
+    }
+
+    #[test]
+    fn rejects_truncated_blobs() {
+        assert!(ConnectionSettings::decode(&DIRECT[..10]).is_err());
+        // Uzunluk alanı verinin ötesini gösteriyor
+        let mut blob = CORPORATE[..20].to_vec();
+        blob.extend_from_slice(&[0xff; 4]);
+        assert!(ConnectionSettings::decode(&blob).is_err());
+    }
+}
@@ -0,0 +1,175 @@
+//! Bağlıyken sistem proxy'sinin başka bir araç tarafından değiştirilmesini izler.
+//!
+//! VPN istemcileri, kurumsal ajanlar veya başka DPI araçları Internet
+//! Settings'i Vexar bağlıyken yeniden yazabilir; bu durumda arayüz
+//! "bağlı" görünür ama trafik motordan geçmez. Uygulanan ayarlar düzenli
+//! olarak sistemdekiyle karşılaştırılır ve ayara göre ayarlar yeniden
+//! yazılır, kullanıcı uyarılır veya bağlantı kesilir.
+
+use std::time::Duration;
+
+use super::ProxySnapshot;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DriftAction {
+    /// Vexar'ın ayarlarını yeniden yaz.
+    #[default]
+    Reassert,
+    /// Yalnızca kullanıcıyı uyar.
+    Notify,
+    /// Bağlantıyı kes; sistem proxy'sine artık dokunma.
+    Disconnect,
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct DriftPolicy {
+    pub enabled: bool,
+    pub interval_ms: u64,
+    pub action: DriftAction,
+}
+
+impl Default for DriftPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_ms: 3000,
+            action: DriftAction::Reassert,
+        }
+    }
+}
+
+impl DriftPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1000..=60_000).contains(&self.interval_ms) {
+            return Err("Proxy izleme aralığı 1-60 saniye arasında olmalı.".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn interval(&self) -> Duration {
+        Duration::from_millis(self.interval_ms)
+    }
+}
+
+/// Değişen tek bir ayar.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct DriftChange {
+    pub field: &'static str,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+}
+
+/// Uygulanan görüntü ile sistemdekini alan alan karşılaştırır.
+pub fn diff(expected: &ProxySnapshot, actual: &ProxySnapshot) -> Vec<DriftChange> {
+    let flag = |value: bool| Some(value.to_string());
+    let fields = [
+        ("enabled", flag(expected.enabled), flag(actual.enabled)),
+        ("server", expected.server.clone(), actual.server.clone()),
+        ("bypass", expected.bypass.clone(), actual.bypass.clone()),
+        (
+            "auto_config_url",
+            expected.auto_config_url.clone(),
+            actual.auto_config_url.clone(),
+        ),
+        (
+            "auto_detect",
+            expected.auto_detect.map(|v| v.to_string()),
+            actual.auto_detect.map(|v| v.to_string()),
+        ),
+    ];
+    fields
+        .into_iter()
+        .filter(|(_, expected, actual)| expected != actual)
+        .map(|(field, expected, actual)| DriftChange {
+            field,
+            expected,
+            actual,
+        })
+        .collect()
+}
+
+/// Aynı sapmanın her kontrolde yeniden bildirilmesini önler.
+#[derive(Default)]
+pub struct DriftWatch {
+    last: Vec<DriftChange>,
+}
+
+impl DriftWatch {
+    /// Sapma yeniyse (bir önceki kontrolden farklıysa) true döner. Başka bir
+    /// araç ayarları sürekli geri yazarken yalnızca ilk sapma bildirilir.
+    pub fn observe(&mut self, changes: &[DriftChange]) -> bool {
+        if changes == self.last.as_slice() {
+            return false;
+        }
+        self.last = changes.to_vec();
+        !changes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ours() -> ProxySnapshot {
+        ProxySnapshot {
+            enabled: true,
+            server: Some("127.0.0.1:8080".to_string()),
+            bypass: Some("<local>".to_string()),
+            auto_config_url: None,
+            auto_detect: Some(false),
+        }
+    }
+
+    #[test]
+    fn reports_changed_fields() {
+        assert!(diff(&ours(), &ours()).is_empty());
+
+        let vpn = ProxySnapshot {
+            server: Some("10.8.0.1:3128".to_string()),
+            auto_config_url: Some("http://vpn.example/pac".to_string()),
+            ..ours()
+        };
+        assert_eq!(
+            diff(&ours(), &vpn),
+            [
+                DriftChange {
+                    field: "server",
+                    expected: Some("127.0.0.1:8080".to_string()),
+                    actual: Some("10.8.0.1:3128".to_string()),
+                },
+                DriftChange {
+                    field: "auto_config_url",
+                    expected: None,
+                    actual: Some("http://vpn.example/pac".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_each_drift_once() {
+        let mut watch = DriftWatch::default();
+        let disabled = ProxySnapshot {
+            enabled: false,
+            ..ours()
+        };
+        assert!(!watch.observe(&diff(&ours(), &ours())));
+        assert!(watch.observe(&diff(&ours(), &disabled)));
+        assert!(!watch.observe(&diff(&ours(), &disabled)));
+        // Ayarlar düzeldi, sonra yeniden bozuldu
+        assert!(!watch.observe(&diff(&ours(), &ours())));
+        assert!(watch.observe(&diff(&ours(), &disabled)));
+    }
+
+    #[test]
+    fn validates_interval() {
+        assert!(DriftPolicy::default().validate().is_ok());
+        let policy = DriftPolicy {
+            interval_ms: 100,
+            ..DriftPolicy::default()
+        };
+        assert!(policy.validate().is_err());
+    }
+}
@@ -0,0 +1,154 @@
+//! Sistem proxy'sinin yönlendirileceği adres.
+//!
+//! Normalde bu, loopback üzerindeki yerel motordur. İstemci modunda ise
+//! LAN paylaşımı açık başka bir Vexar'a (veya herhangi bir HTTP CONNECT
+//! proxy'sine) yönlendirilir; adres IPv4, köşeli parantezli IPv6 veya host
+//! adı olabilir.
+
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, ToSocketAddrs};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProxyEndpoint {
+    host: String,
+    port: u16,
+}
+
+impl ProxyEndpoint {
+    /// Bu makinede çalışan motor.
+    pub fn local(port: u16) -> Self {
+        Self {
+            host: Ipv4Addr::LOCALHOST.to_string(),
+            port,
+        }
+    }
+
+    /// Host ve portu doğrular. IPv6 adresleri köşeli parantezle veya
+    /// parantezsiz verilebilir.
+    pub fn new(host: &str, port: u16) -> Result<Self, String> {
+        let host = host.trim();
+        let bare = host
+            .strip_prefix('[')
+            .and_then(|h| h.strip_suffix(']'))
+            .unwrap_or(host);
+        let host = match bare.parse::<IpAddr>() {
+            Ok(ip) if ip.is_unspecified() || ip.is_multicast() => {
+                return Err(format!("Geçersiz proxy adresi: {host}"));
+            }
+            Ok(ip) => ip.to_string(),
+            Err(_) if is_valid_hostname(bare) => bare.to_ascii_lowercase(),
+            Err(_) => return Err(format!("Geçersiz proxy adresi: {host}")),
+        };
+        if port == 0 {
+            return Err("Geçersiz port numarası: 0".to_string());
+        }
+        Ok(Self { host, port })
+    }
+
+    /// `host:port` veya `[ipv6]:port` biçimini ayrıştırır.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        let invalid = || format!("Geçersiz proxy adresi: {input} (host:port olmalı)");
+        let (host, port) = input.rsplit_once(':').ok_or_else(invalid)?;
+        // Parantezsiz IPv6'da port ayrılamaz
+        if host.contains(':') && !host.starts_with('[') {
+            return Err(invalid());
+        }
+        let port = port.parse::<u16>().map_err(|_| invalid())?;
+        Self::new(host, port)
+    }
+
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    pub fn is_local(&self) -> bool {
+        self.host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+    }
+
+    /// Host adını çözer; probe ve heartbeat bu adrese bağlanır.
+    pub fn resolve(&self) -> Result<SocketAddr, String> {
+        (self.host.as_str(), self.port)
+            .to_socket_addrs()
+            .map_err(|e| format!("{} çözümlenemedi: {e}", self.host))?
+            .next()
+            .ok_or_else(|| format!("{} çözümlenemedi.", self.host))
+    }
+}
+
+/// `ProxyServer` ve PAC betiğindeki biçim.
+impl fmt::Display for ProxyEndpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.host.contains(':') {
+            write!(f, "[{}]:{}", self.host, self.port)
+        } else {
+            write!(f, "{}:{}", self.host, self.port)
+        }
+    }
+}
+
+fn is_valid_hostname(host: &str) -> bool {
+    !host.is_empty()
+        && host.len() <= 253
+        && host.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_formats_addresses() {
+        for (input, expected) in [
+            ("192.168.1.20:8080", "192.168.1.20:8080"),
+            ("Vexar-PC.local:8081", "vexar-pc.local:8081"),
+            ("[fe80::1]:8080", "[fe80::1]:8080"),
+            (" 10.0.0.2:3128 ", "10.0.0.2:3128"),
+        ] {
+            assert_eq!(ProxyEndpoint::parse(input).unwrap().to_string(), expected);
+        }
+        assert_eq!(ProxyEndpoint::local(8080).to_string(), "127.0.0.1:8080");
+        assert_eq!(
+            ProxyEndpoint::new("[::1]", 8080).unwrap(),
+            ProxyEndpoint::new("::1", 8080).unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_addresses() {
+        for input in [
+            "192.168.1.20",
+            "fe80::1:8080",
+            "0.0.0.0:8080",
+            "host:0",
+            "host:99999",
+            "bad host:8080",
+            "-vexar:8080",
+            ":8080",
+        ] {
+            assert!(ProxyEndpoint::parse(input).is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn detects_loopback() {
+        assert!(ProxyEndpoint::local(8080).is_local());
+        assert!(ProxyEndpoint::parse("[::1]:8080").unwrap().is_local());
+        assert!(
+            !ProxyEndpoint::parse("192.168.1.20:8080")
+                .unwrap()
+                .is_local()
+        );
+        assert!(!ProxyEndpoint::parse("localhost:8080").unwrap().is_local());
+    }
+}
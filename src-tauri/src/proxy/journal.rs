@@ -0,0 +1,297 @@
+//! Elektrik kesintisi veya zorla kapatma sonrası sistem proxy'sini kurtarır.
+//!
+//! `set_system_proxy` registry'ye dokunmadan önce önceki ayarları, motor
+//! portunu ve uygulamanın PID'ini bu dosyaya yazar; temiz bir kapanışta dosya
+//! silinir. Açılışta geride kalmış ve sahibi artık yaşamayan bir kayıt
+//! bulunursa önceki ayarlar geri yüklenir.
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use super::ProxySnapshot;
+use super::endpoint::ProxyEndpoint;
+
+pub const JOURNAL_FILE: &str = "proxy-journal.json";
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct JournalEntry {
+    /// Vexar proxy'yi açmadan önceki ayarlar.
+    pub previous: ProxySnapshot,
+    pub port: u16,
+    /// İstemci modunda proxy'nin yönlendirildiği uzak host; yerel motorda `None`.
+    #[serde(default)]
+    pub host: Option<String>,
+    pub pid: u32,
+    /// Unix zamanı (saniye).
+    pub written_at: u64,
+}
+
+pub struct ProxyJournal {
+    path: PathBuf,
+    /// Yazma ve silme işlemlerinin birbirine karışmaması için.
+    lock: Mutex<()>,
+}
+
+impl ProxyJournal {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            lock: Mutex::new(()),
+        }
+    }
+
+    pub fn load(&self) -> Option<JournalEntry> {
+        let json = std::fs::read_to_string(&self.path).ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    /// Proxy açılmadan önce çağrılır. Bu süreç zaten bir kayıt yazdıysa
+    /// (yeniden bağlanma gibi) önceki ayarlar korunur, yalnızca adres güncellenir;
+    /// aksi halde kendi proxy'mizi "önceki ayar" diye kaydederdik.
+    pub fn begin(
+        &self,
+        endpoint: &ProxyEndpoint,
+        read_previous: impl FnOnce() -> Result<ProxySnapshot, String>,
+    ) -> Result<JournalEntry, String> {
+        let _guard = self.lock.lock().unwrap();
+        let pid = std::process::id();
+        let previous = match self.load() {
+            Some(entry) if entry.pid == pid => entry.previous,
+            _ => read_previous()?,
+        };
+        let entry = JournalEntry {
+            previous,
+            port: endpoint.port(),
+            host: (!endpoint.is_local()).then(|| endpoint.host().to_string()),
+            pid,
+            written_at: unix_now(),
+        };
+        self.write(&entry)?;
+        Ok(entry)
+    }
+
+    /// Proxy temizlendikten sonra çağrılır.
+    pub fn clear(&self) -> Result<(), String> {
+        let _guard = self.lock.lock().unwrap();
+        match std::fs::remove_file(&self.path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(format!("Proxy kaydı silinemedi: {e}")),
+        }
+    }
+
+    /// Açılışta çağrılır. Sahibi yaşamayan bir kayıt varsa önceki ayarları
+    /// `restore` ile geri yükler, kaydı siler ve döndürür.
+    pub fn recover(
+        &self,
+        is_alive: impl FnOnce(&JournalEntry) -> bool,
+        restore: impl FnOnce(&ProxySnapshot) -> Result<(), String>,
+    ) -> Result<Option<JournalEntry>, String> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+        let Some(entry) = self.load() else {
+            // Okunamayan kaydın işe yarar bilgisi yok
+            self.clear()?;
+            return Ok(None);
+        };
+        if is_alive(&entry) {
+            return Ok(None);
+        }
+        restore(&entry.previous)?;
+        self.clear()?;
+        Ok(Some(entry))
+    }
+
+    fn write(&self, entry: &JournalEntry) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(entry).map_err(|e| e.to_string())?;
+        crate::files::write_atomic(&self.path, &json)
+    }
+}
+
+/// Kaydı yazan Vexar hâlâ çalışıyor ve motoru hâlâ dinliyorsa kayıt canlıdır.
+/// İstemci modundaki bir kayıtta yerel motor yoktur; sürecin yaşaması yeterlidir.
+pub fn owner_is_alive(entry: &JournalEntry) -> bool {
+    use std::net::{Ipv4Addr, SocketAddr, TcpStream};
+    use std::time::Duration;
+
+    if entry.pid == std::process::id() {
+        return false;
+    }
+
+    process_is_alive(entry.pid)
+        && (entry.host.is_some()
+            || TcpStream::connect_timeout(
+                &SocketAddr::from((Ipv4Addr::LOCALHOST, entry.port)),
+                Duration::from_millis(500),
+            )
+            .is_ok())
+}
+
+/// `pid` başka bir Vexar sürecine mi ait? PID yeniden kullanılmış olabileceği
+/// için yalnızca aynı adlı süreçler sayılır.
+pub fn process_is_alive(pid: u32) -> bool {
+    use sysinfo::{Pid, System};
+
+    let mut sys = System::new();
+    sys.refresh_processes();
+    let own_name = sys
+        .process(Pid::from_u32(std::process::id()))
+        .map(|p| p.name().to_string());
+    sys.process(Pid::from_u32(pid))
+        .is_some_and(|p| Some(p.name()) == own_name.as_deref())
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn journal(name: &str) -> ProxyJournal {
+        let dir = std::env::temp_dir().join(format!("vexar-journal-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        ProxyJournal::new(dir.join(JOURNAL_FILE))
+    }
+
+    fn corporate() -> ProxySnapshot {
+        ProxySnapshot {
+            enabled: true,
+            server: Some("corp.example:3128".to_string()),
+            bypass: Some("*.corp.example;<local>".to_string()),
+            auto_config_url: Some("http://wpad.corp.example/proxy.pac".to_string()),
+            auto_detect: Some(true),
+        }
+    }
+
+    #[test]
+    fn begin_records_previous_settings() {
+        let journal = journal("begin");
+        let entry = journal
+            .begin(&ProxyEndpoint::local(8080), || Ok(corporate()))
+            .unwrap();
+        assert_eq!(entry.previous, corporate());
+        assert_eq!(entry.pid, std::process::id());
+        assert_eq!(journal.load(), Some(entry));
+    }
+
+    #[test]
+    fn repeated_begin_keeps_original_previous() {
+        let journal = journal("repeat");
+        journal
+            .begin(&ProxyEndpoint::local(8080), || Ok(corporate()))
+            .unwrap();
+        // İkinci okuma Vexar'ın kendi proxy'sini görürdü
+        let entry = journal
+            .begin(&ProxyEndpoint::local(8081), || {
+                Ok(ProxySnapshot {
+                    enabled: true,
+                    server: Some("127.0.0.1:8080".to_string()),
+                    bypass: Some("<local>".to_string()),
+                    ..ProxySnapshot::default()
+                })
+            })
+            .unwrap();
+        assert_eq!(entry.previous, corporate());
+        assert_eq!(entry.port, 8081);
+    }
+
+    #[test]
+    fn failed_read_does_not_write() {
+        let journal = journal("failed");
+        assert!(
+            journal
+                .begin(&ProxyEndpoint::local(8080), || Err("okunamadı".to_string()))
+                .is_err()
+        );
+        assert_eq!(journal.load(), None);
+    }
+
+    #[test]
+    fn recovers_dead_owner() {
+        let journal = journal("dead");
+        journal
+            .begin(&ProxyEndpoint::local(8080), || Ok(corporate()))
+            .unwrap();
+        let mut restored = None;
+        let entry = journal
+            .recover(
+                |_| false,
+                |previous| {
+                    restored = Some(previous.clone());
+                    Ok(())
+                },
+            )
+            .unwrap();
+        assert!(entry.is_some());
+        assert_eq!(restored, Some(corporate()));
+        assert_eq!(journal.load(), None);
+    }
+
+    #[test]
+    fn leaves_live_owner_alone() {
+        let journal = journal("alive");
+        journal
+            .begin(&ProxyEndpoint::local(8080), || Ok(corporate()))
+            .unwrap();
+        let entry = journal
+            .recover(|_| true, |_| panic!("canlı kayıt geri yüklenmemeli"))
+            .unwrap();
+        assert_eq!(entry, None);
+        assert!(journal.load().is_some());
+    }
+
+    #[test]
+    fn keeps_journal_when_restore_fails() {
+        let journal = journal("restore-fail");
+        journal
+            .begin(&ProxyEndpoint::local(8080), || Ok(corporate()))
+            .unwrap();
+        assert!(
+            journal
+                .recover(|_| false, |_| Err("registry".to_string()))
+                .is_err()
+        );
+        assert!(journal.load().is_some());
+    }
+
+    #[test]
+    fn discards_corrupt_journal() {
+        let journal = journal("corrupt");
+        std::fs::create_dir_all(journal.path.parent().unwrap()).unwrap();
+        std::fs::write(&journal.path, "{yarım").unwrap();
+        assert_eq!(journal.recover(|_| false, |_| Ok(())).unwrap(), None);
+        assert!(!journal.path.exists());
+    }
+
+    #[test]
+    fn reads_journals_without_newer_fields() {
+        let journal = journal("old-format");
+        std::fs::create_dir_all(journal.path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &journal.path,
+            r#"{"previous":{"enabled":true,"server":"corp.example:3128","bypass":null},"port":8080,"pid":1,"written_at":0}"#,
+        )
+        .unwrap();
+        let entry = journal.load().unwrap();
+        assert_eq!(entry.previous.server.as_deref(), Some("corp.example:3128"));
+        assert_eq!(entry.previous.auto_config_url, None);
+    }
+
+    #[test]
+    fn own_process_is_never_a_live_owner() {
+        let entry = JournalEntry {
+            previous: ProxySnapshot::default(),
+            port: 8080,
+            host: None,
+            pid: std::process::id(),
+            written_at: 0,
+        };
+        assert!(!owner_is_alive(&entry));
+    }
+}
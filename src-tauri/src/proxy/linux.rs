@@ -0,0 +1,764 @@
+//! Linux masaüstü backend'i.
+//!
+//! GNOME tabanlı masaüstlerinde `org.gnome.system.proxy` gsettings
+//! anahtarları, KDE'de `kioslaverc` kullanılır. Her iki durumda da kabuklar
+//! için sistem proxy'sini izleyen bir ortam değişkeni dosyası yazılır;
+//! masaüstü tanınmazsa yalnızca bu dosya kullanılır.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::endpoint::ProxyEndpoint;
+use super::servers::{ProxyServers, Scheme};
+use super::{ProxySnapshot, SystemProxyBackend};
+use crate::files::write_atomic;
+
+/// Kabukların `. ~/.config/<app>/proxy.env` ile okuyabileceği dosya.
+pub const ENV_FILE: &str = "proxy.env";
+
+const GNOME_SCHEMA: &str = "org.gnome.system.proxy";
+const KDE_SECTION: &str = "Proxy Settings";
+/// Masaüstü ayarlarında karşılığı olan şemalar; FTP yok sayılır.
+const SCHEMES: [Scheme; 3] = [Scheme::Http, Scheme::Https, Scheme::Socks];
+/// Windows'taki `<local>` karşılığı.
+const LOCAL_HOSTS: [&str; 3] = ["localhost", "127.0.0.0/8", "::1"];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Desktop {
+    Gnome,
+    Kde,
+    Other,
+}
+
+impl Desktop {
+    pub fn detect() -> Self {
+        let value = std::env::var("XDG_CURRENT_DESKTOP")
+            .or_else(|_| std::env::var("DESKTOP_SESSION"))
+            .unwrap_or_default();
+        Self::from_env_value(&value)
+    }
+
+    /// `XDG_CURRENT_DESKTOP` iki nokta ile ayrılmış bir liste olabilir ("ubuntu:GNOME").
+    fn from_env_value(value: &str) -> Self {
+        for name in value.split(':').map(|s| s.trim().to_ascii_lowercase()) {
+            match name.as_str() {
+                "kde" | "plasma" => return Desktop::Kde,
+                "gnome" | "unity" | "budgie" | "pantheon" | "cinnamon" | "x-cinnamon" => {
+                    return Desktop::Gnome;
+                }
+                _ => {}
+            }
+        }
+        Desktop::Other
+    }
+}
+
+/// gsettings erişimi; testlerde bellekte tutulan sahte bir uygulama kullanılır.
+pub trait GSettings: Send + Sync {
+    /// Değeri GVariant metni olarak döndürür (ör. `'manual'`, `8080`).
+    fn get(&self, schema: &str, key: &str) -> Result<String, String>;
+    fn set(&self, schema: &str, key: &str, value: &str) -> Result<(), String>;
+}
+
+struct GsettingsCli;
+
+impl GSettings for GsettingsCli {
+    fn get(&self, schema: &str, key: &str) -> Result<String, String> {
+        let output = Command::new("gsettings")
+            .args(["get", schema, key])
+            .output()
+            .map_err(|e| format!("gsettings çalıştırılamadı: {e}"))?;
+        if !output.status.success() {
+            return Err(format!("{schema} {key} okunamadı."));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    fn set(&self, schema: &str, key: &str, value: &str) -> Result<(), String> {
+        let status = Command::new("gsettings")
+            .args(["set", schema, key, value])
+            .status()
+            .map_err(|e| format!("gsettings çalıştırılamadı: {e}"))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(format!("{schema} {key} ayarlanamadı."))
+        }
+    }
+}
+
+pub struct LinuxBackend {
+    desktop: Desktop,
+    gsettings: Box<dyn GSettings>,
+    kioslaverc: PathBuf,
+    env_file: PathBuf,
+}
+
+impl LinuxBackend {
+    /// Masaüstünü ortam değişkenlerinden tanır; ortam dosyası `config_dir` altına yazılır.
+    pub fn detect(config_dir: &Path) -> Self {
+        let config_home = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .unwrap_or_default();
+        Self {
+            desktop: Desktop::detect(),
+            gsettings: Box::new(GsettingsCli),
+            kioslaverc: config_home.join("kioslaverc"),
+            env_file: config_dir.join(ENV_FILE),
+        }
+    }
+
+    fn read_gnome(&self) -> Result<ProxySnapshot, String> {
+        let get = |schema: &str, key: &str| self.gsettings.get(schema, key);
+
+        let mode = parse_gvariant_string(&get(GNOME_SCHEMA, "mode")?);
+        let url = parse_gvariant_string(&get(GNOME_SCHEMA, "autoconfig-url")?);
+        let mut endpoints = Vec::new();
+        for scheme in SCHEMES {
+            let schema = format!("{GNOME_SCHEMA}.{}", scheme.as_str());
+            let host = parse_gvariant_string(&get(&schema, "host")?);
+            let port = get(&schema, "port")?.trim().parse::<u16>().unwrap_or(0);
+            if let Ok(endpoint) = ProxyEndpoint::new(&host, port) {
+                endpoints.push((scheme, endpoint));
+            }
+        }
+
+        Ok(ProxySnapshot {
+            enabled: mode == "manual",
+            server: format_server(endpoints),
+            bypass: Some(parse_gvariant_list(&get(GNOME_SCHEMA, "ignore-hosts")?).join(";")),
+            // Kullanılmayan bir PAC adresi korunur; yalnızca mod değiştirilir
+            auto_config_url: (mode == "auto" && !url.is_empty()).then_some(url.clone()),
+            auto_detect: Some(mode == "auto" && url.is_empty()),
+        })
+    }
+
+    fn apply_gnome(&self, snapshot: &ProxySnapshot) -> Result<(), String> {
+        let set = |schema: &str, key: &str, value: String| self.gsettings.set(schema, key, &value);
+
+        // Görüntüde sunucu yoksa adresler de silinir
+        let servers = parse_server(snapshot);
+        for scheme in SCHEMES {
+            let schema = format!("{GNOME_SCHEMA}.{}", scheme.as_str());
+            let (host, port) = servers
+                .as_ref()
+                .and_then(|servers| servers.get(scheme))
+                .map(|endpoint| (endpoint.host().to_string(), endpoint.port()))
+                .unwrap_or_default();
+            set(&schema, "host", gvariant_string(&host))?;
+            set(&schema, "port", port.to_string())?;
+        }
+        if let Some(bypass) = &snapshot.bypass {
+            set(
+                GNOME_SCHEMA,
+                "ignore-hosts",
+                gvariant_list(&bypass_hosts(bypass)),
+            )?;
+        }
+        if let Some(url) = &snapshot.auto_config_url {
+            set(GNOME_SCHEMA, "autoconfig-url", gvariant_string(url))?;
+        }
+        // Mod en son yazılır ki yarım kalan yazma eksik ayarlarla proxy açmasın
+        set(GNOME_SCHEMA, "mode", gvariant_string(mode_of(snapshot)))
+    }
+
+    fn read_kde(&self) -> Result<ProxySnapshot, String> {
+        let content = read_optional(&self.kioslaverc)?;
+        let get = |key: &str| ini_get(&content, KDE_SECTION, key);
+
+        let proxy_type = get("ProxyType")
+            .and_then(|t| t.parse::<u8>().ok())
+            .unwrap_or(0);
+        let endpoints = SCHEMES
+            .into_iter()
+            .filter_map(|scheme| {
+                let value = get(kde_key(scheme))?;
+                Some((scheme, parse_kde_proxy(&value)?))
+            })
+            .collect();
+        Ok(ProxySnapshot {
+            enabled: proxy_type == 1,
+            server: format_server(endpoints),
+            bypass: get("NoProxyFor").map(|hosts| hosts.replace(',', ";")),
+            auto_config_url: get("Proxy Config Script").filter(|_| proxy_type == 2),
+            auto_detect: Some(proxy_type == 3),
+        })
+    }
+
+    fn apply_kde(&self, snapshot: &ProxySnapshot) -> Result<(), String> {
+        let mut content = read_optional(&self.kioslaverc)?;
+
+        let servers = parse_server(snapshot);
+        for scheme in SCHEMES {
+            let value = servers
+                .as_ref()
+                .and_then(|servers| servers.get(scheme))
+                .map(|endpoint| {
+                    let prefix = if scheme == Scheme::Socks {
+                        "socks"
+                    } else {
+                        "http"
+                    };
+                    format!("{prefix}://{} {}", endpoint.host(), endpoint.port())
+                })
+                .unwrap_or_default();
+            content = ini_set(&content, KDE_SECTION, kde_key(scheme), &value);
+        }
+        if let Some(bypass) = &snapshot.bypass {
+            content = ini_set(
+                &content,
+                KDE_SECTION,
+                "NoProxyFor",
+                &bypass_hosts(bypass).join(","),
+            );
+        }
+        if let Some(url) = &snapshot.auto_config_url {
+            content = ini_set(&content, KDE_SECTION, "Proxy Config Script", url);
+        }
+        let proxy_type = match mode_of(snapshot) {
+            "manual" => "1",
+            "auto" if snapshot.auto_config_url.is_some() => "2",
+            "auto" => "3",
+            _ => "0",
+        };
+        content = ini_set(&content, KDE_SECTION, "ProxyType", proxy_type);
+        write_atomic(&self.kioslaverc, &content)
+    }
+
+    fn read_env(&self) -> Result<ProxySnapshot, String> {
+        let content = read_optional(&self.env_file)?;
+        let value = |name: &str| {
+            content.lines().find_map(|line| {
+                line.trim()
+                    .strip_prefix(&format!("export {name}="))
+                    .map(|v| v.trim_matches('"').to_string())
+            })
+        };
+        let endpoints = SCHEMES
+            .into_iter()
+            .filter_map(|scheme| Some((scheme, url_endpoint(&value(env_name(scheme))?)?)))
+            .collect();
+        let server = format_server(endpoints);
+        Ok(ProxySnapshot {
+            enabled: server.is_some(),
+            server,
+            bypass: value("no_proxy").map(|hosts| hosts.replace(',', ";")),
+            auto_config_url: None,
+            auto_detect: None,
+        })
+    }
+
+    /// Ortam dosyası sistemdeki manuel proxy'yi izler; proxy kapalıysa
+    /// değişkenleri temizleyen bir dosya yazılır ki kaynak gösteren kabuklar hata vermesin.
+    fn apply_env(&self, snapshot: &ProxySnapshot) -> Result<(), String> {
+        let servers = parse_server(snapshot).filter(|_| snapshot.enabled);
+        let urls: Vec<(Scheme, Option<String>)> = SCHEMES
+            .into_iter()
+            .map(|scheme| {
+                let url = servers
+                    .as_ref()
+                    .and_then(|servers| servers.get(scheme))
+                    .map(|endpoint| {
+                        let prefix = if scheme == Scheme::Socks {
+                            "socks5"
+                        } else {
+                            "http"
+                        };
+                        format!("{prefix}://{endpoint}")
+                    });
+                (scheme, url)
+            })
+            .collect();
+
+        let mut content = String::from("# Vexar tarafından yönetilir; sistem proxy'sini izler.\n");
+        if urls.iter().all(|(_, url)| url.is_none()) {
+            content.push_str(
+                "unset http_proxy https_proxy all_proxy HTTP_PROXY HTTPS_PROXY ALL_PROXY no_proxy NO_PROXY\n",
+            );
+        } else {
+            for (scheme, value) in &urls {
+                let name = env_name(*scheme);
+                for name in [name.to_string(), name.to_ascii_uppercase()] {
+                    match value {
+                        Some(value) => content.push_str(&format!("export {name}=\"{value}\"\n")),
+                        None => content.push_str(&format!("unset {name}\n")),
+                    }
+                }
+            }
+            let no_proxy = bypass_hosts(snapshot.bypass.as_deref().unwrap_or("<local>")).join(",");
+            content.push_str(&format!("export no_proxy=\"{no_proxy}\"\n"));
+            content.push_str(&format!("export NO_PROXY=\"{no_proxy}\"\n"));
+        }
+        write_atomic(&self.env_file, &content)
+    }
+}
+
+impl SystemProxyBackend for LinuxBackend {
+    fn read(&self) -> Result<ProxySnapshot, String> {
+        match self.desktop {
+            Desktop::Gnome => self.read_gnome(),
+            Desktop::Kde => self.read_kde(),
+            Desktop::Other => self.read_env(),
+        }
+    }
+
+    fn apply(&self, snapshot: &ProxySnapshot) -> Result<(), String> {
+        match self.desktop {
+            Desktop::Gnome => self.apply_gnome(snapshot)?,
+            Desktop::Kde => self.apply_kde(snapshot)?,
+            Desktop::Other => {}
+        }
+        self.apply_env(snapshot)
+    }
+
+    fn notify(&self) {
+        // gsettings değişiklikleri anında yayılır; KIO'ya yeniden okumasını söylemek gerekir
+        if self.desktop == Desktop::Kde {
+            let _ = Command::new("dbus-send")
+                .args([
+                    "--type=signal",
+                    "/KIO/Scheduler",
+                    "org.kde.KIO.Scheduler.reparseSlaveConfiguration",
+                    "string:",
+                ])
+                .status();
+        }
+    }
+}
+
+fn mode_of(snapshot: &ProxySnapshot) -> &'static str {
+    if snapshot.enabled && snapshot.server.is_some() {
+        "manual"
+    } else if snapshot.auto_config_url.is_some() || snapshot.auto_detect == Some(true) {
+        "auto"
+    } else {
+        "none"
+    }
+}
+
+/// Görüntüdeki `ProxyServer` değeri; bozuk bir değer sunucu yokmuş gibi ele alınır.
+fn parse_server(snapshot: &ProxySnapshot) -> Option<ProxyServers> {
+    snapshot
+        .server
+        .as_deref()
+        .and_then(|server| ProxyServers::parse(server).ok())
+}
+
+fn format_server(endpoints: Vec<(Scheme, ProxyEndpoint)>) -> Option<String> {
+    ProxyServers::from_schemes(endpoints).map(|servers| servers.to_string())
+}
+
+fn kde_key(scheme: Scheme) -> &'static str {
+    match scheme {
+        Scheme::Http => "httpProxy",
+        Scheme::Https => "httpsProxy",
+        Scheme::Ftp => "ftpProxy",
+        Scheme::Socks => "socksProxy",
+    }
+}
+
+fn env_name(scheme: Scheme) -> &'static str {
+    match scheme {
+        Scheme::Http => "http_proxy",
+        Scheme::Https => "https_proxy",
+        Scheme::Ftp => "ftp_proxy",
+        Scheme::Socks => "all_proxy",
+    }
+}
+
+/// `http://host:port/` veya `socks5://host:port` biçimindeki ortam değerini ayrıştırır.
+fn url_endpoint(url: &str) -> Option<ProxyEndpoint> {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    ProxyEndpoint::parse(rest.trim_end_matches('/')).ok()
+}
+
+/// `<local>` girdisini Linux karşılıklarına açar.
+fn bypass_hosts(bypass: &str) -> Vec<String> {
+    let mut hosts: Vec<String> = Vec::new();
+    for entry in bypass
+        .split([';', ','])
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        let expanded: Vec<&str> = if entry == "<local>" {
+            LOCAL_HOSTS.to_vec()
+        } else {
+            vec![entry]
+        };
+        for host in expanded {
+            if !hosts.iter().any(|h| h == host) {
+                hosts.push(host.to_string());
+            }
+        }
+    }
+    hosts
+}
+
+/// KDE `http://host port` veya `http://host:port` biçimini ayrıştırır.
+fn parse_kde_proxy(value: &str) -> Option<ProxyEndpoint> {
+    let value = value.trim();
+    let rest = value.split_once("://").map_or(value, |(_, rest)| rest);
+    let (host, port) = rest.rsplit_once(' ').or_else(|| rest.rsplit_once(':'))?;
+    let port = port.trim().parse::<u16>().ok()?;
+    ProxyEndpoint::new(host.trim_end_matches('/'), port).ok()
+}
+
+fn parse_gvariant_string(value: &str) -> String {
+    let value = value.trim();
+    let inner = value
+        .strip_prefix('\'')
+        .and_then(|v| v.strip_suffix('\''))
+        .unwrap_or(value);
+    inner.replace("\\'", "'").replace("\\\\", "\\")
+}
+
+fn parse_gvariant_list(value: &str) -> Vec<String> {
+    let value = value.trim().trim_start_matches("@as ");
+    let Some(inner) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) else {
+        return Vec::new();
+    };
+    inner
+        .split(',')
+        .map(parse_gvariant_string)
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn gvariant_string(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+fn gvariant_list(values: &[String]) -> String {
+    let items: Vec<String> = values.iter().map(|v| gvariant_string(v)).collect();
+    format!("[{}]", items.join(", "))
+}
+
+fn ini_get(content: &str, section: &str, key: &str) -> Option<String> {
+    let mut in_section = false;
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_section = line == format!("[{section}]");
+        } else if in_section
+            && let Some((k, v)) = line.split_once('=')
+            && k.trim() == key
+        {
+            return Some(v.trim().to_string());
+        }
+    }
+    None
+}
+
+/// Diğer bölüm ve anahtarlara dokunmadan tek bir anahtarı yazar.
+fn ini_set(content: &str, section: &str, key: &str, value: &str) -> String {
+    let header = format!("[{section}]");
+    let mut out: Vec<String> = Vec::new();
+    let mut in_section = false;
+    let mut section_seen = false;
+    let mut written = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            if in_section && !written {
+                out.push(format!("{key}={value}"));
+                written = true;
+            }
+            in_section = trimmed == header;
+            section_seen |= in_section;
+        } else if in_section
+            && !written
+            && trimmed
+                .split_once('=')
+                .is_some_and(|(k, _)| k.trim() == key)
+        {
+            out.push(format!("{key}={value}"));
+            written = true;
+            continue;
+        }
+        out.push(line.to_string());
+    }
+    if !written {
+        if !section_seen {
+            if out.last().is_some_and(|l| !l.trim().is_empty()) {
+                out.push(String::new());
+            }
+            out.push(header);
+        }
+        out.push(format!("{key}={value}"));
+    }
+    let mut result = out.join("\n");
+    result.push('\n');
+    result
+}
+
+fn read_optional(path: &Path) -> Result<String, String> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(format!("{} okunamadı: {e}", path.display())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// GNOME'un varsayılan değerleriyle başlayan sahte gsettings.
+    struct FakeGsettings(Mutex<HashMap<(String, String), String>>);
+
+    impl FakeGsettings {
+        fn new() -> Self {
+            let mut values = HashMap::new();
+            let mut put = |schema: &str, key: &str, value: &str| {
+                values.insert((schema.to_string(), key.to_string()), value.to_string());
+            };
+            put(GNOME_SCHEMA, "mode", "'none'");
+            put(GNOME_SCHEMA, "autoconfig-url", "''");
+            put(
+                GNOME_SCHEMA,
+                "ignore-hosts",
+                "['localhost', '127.0.0.0/8', '::1']",
+            );
+            for scheme in ["http", "https", "socks"] {
+                put(&format!("{GNOME_SCHEMA}.{scheme}"), "host", "''");
+                put(&format!("{GNOME_SCHEMA}.{scheme}"), "port", "0");
+            }
+            Self(Mutex::new(values))
+        }
+    }
+
+    impl GSettings for FakeGsettings {
+        fn get(&self, schema: &str, key: &str) -> Result<String, String> {
+            self.0
+                .lock()
+                .unwrap()
+                .get(&(schema.to_string(), key.to_string()))
+                .cloned()
+                .ok_or_else(|| format!("{schema} {key} yok"))
+        }
+
+        fn set(&self, schema: &str, key: &str, value: &str) -> Result<(), String> {
+            self.0
+                .lock()
+                .unwrap()
+                .insert((schema.to_string(), key.to_string()), value.to_string());
+            Ok(())
+        }
+    }
+
+    fn backend(name: &str, desktop: Desktop) -> LinuxBackend {
+        let dir = std::env::temp_dir().join(format!("vexar-linux-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        LinuxBackend {
+            desktop,
+            gsettings: Box::new(FakeGsettings::new()),
+            kioslaverc: dir.join("kioslaverc"),
+            env_file: dir.join(ENV_FILE),
+        }
+    }
+
+    fn vexar(port: u16) -> ProxySnapshot {
+        ProxySnapshot {
+            enabled: true,
+            server: Some(format!("127.0.0.1:{port}")),
+            bypass: Some("<local>".to_string()),
+            auto_config_url: None,
+            auto_detect: Some(false),
+        }
+    }
+
+    #[test]
+    fn detects_desktops() {
+        assert_eq!(Desktop::from_env_value("ubuntu:GNOME"), Desktop::Gnome);
+        assert_eq!(Desktop::from_env_value("X-Cinnamon"), Desktop::Gnome);
+        assert_eq!(Desktop::from_env_value("KDE"), Desktop::Kde);
+        assert_eq!(Desktop::from_env_value("sway"), Desktop::Other);
+        assert_eq!(Desktop::from_env_value(""), Desktop::Other);
+    }
+
+    #[test]
+    fn gnome_enable_and_restore() {
+        let backend = backend("gnome", Desktop::Gnome);
+        let previous = backend.read().unwrap();
+        assert!(!previous.enabled);
+
+        backend.apply(&vexar(8080)).unwrap();
+        let gs = &backend.gsettings;
+        assert_eq!(gs.get(GNOME_SCHEMA, "mode").unwrap(), "'manual'");
+        assert_eq!(
+            gs.get("org.gnome.system.proxy.https", "host").unwrap(),
+            "'127.0.0.1'"
+        );
+        assert_eq!(
+            gs.get("org.gnome.system.proxy.https", "port").unwrap(),
+            "8080"
+        );
+        assert_eq!(
+            backend.read().unwrap().server.as_deref(),
+            Some("127.0.0.1:8080")
+        );
+
+        backend.apply(&previous).unwrap();
+        assert_eq!(backend.read().unwrap(), previous);
+        assert_eq!(gs.get(GNOME_SCHEMA, "mode").unwrap(), "'none'");
+    }
+
+    #[test]
+    fn gnome_keeps_pac_configuration() {
+        let backend = backend("gnome-pac", Desktop::Gnome);
+        let gs = &backend.gsettings;
+        gs.set(GNOME_SCHEMA, "mode", "'auto'").unwrap();
+        gs.set(
+            GNOME_SCHEMA,
+            "autoconfig-url",
+            "'http://wpad.corp/proxy.pac'",
+        )
+        .unwrap();
+        let previous = backend.read().unwrap();
+        assert_eq!(
+            previous.auto_config_url.as_deref(),
+            Some("http://wpad.corp/proxy.pac")
+        );
+
+        backend.apply(&vexar(8080)).unwrap();
+        backend.apply(&previous).unwrap();
+        assert_eq!(gs.get(GNOME_SCHEMA, "mode").unwrap(), "'auto'");
+        assert_eq!(
+            gs.get(GNOME_SCHEMA, "autoconfig-url").unwrap(),
+            "'http://wpad.corp/proxy.pac'"
+        );
+    }
+
+    #[test]
+    fn kde_enable_and_restore_preserves_other_keys() {
+        let backend = backend("kde", Desktop::Kde);
+        std::fs::create_dir_all(backend.kioslaverc.parent().unwrap()).unwrap();
+        std::fs::write(
+            &backend.kioslaverc,
+            "[Cache Settings]\nMaxCacheSize=51200\n\n[Proxy Settings]\nProxyType=1\nhttpProxy=http://corp.example 3128\nhttpsProxy=http://corp.example 3128\nNoProxyFor=.corp.example\nAuthMode=0\n",
+        )
+        .unwrap();
+        let previous = backend.read().unwrap();
+        assert_eq!(
+            previous,
+            ProxySnapshot {
+                enabled: true,
+                server: Some("corp.example:3128".to_string()),
+                bypass: Some(".corp.example".to_string()),
+                auto_config_url: None,
+                auto_detect: Some(false),
+            }
+        );
+
+        backend.apply(&vexar(8080)).unwrap();
+        let content = std::fs::read_to_string(&backend.kioslaverc).unwrap();
+        assert!(content.contains("httpProxy=http://127.0.0.1 8080"));
+        assert!(content.contains("NoProxyFor=localhost,127.0.0.0/8,::1"));
+        assert!(content.contains("MaxCacheSize=51200"));
+        assert!(content.contains("AuthMode=0"));
+
+        backend.apply(&previous).unwrap();
+        assert_eq!(backend.read().unwrap(), previous);
+    }
+
+    #[test]
+    fn kde_creates_missing_section() {
+        let content = ini_set("[General]\nfoo=bar\n", KDE_SECTION, "ProxyType", "1");
+        assert_eq!(
+            content,
+            "[General]\nfoo=bar\n\n[Proxy Settings]\nProxyType=1\n"
+        );
+        assert_eq!(
+            ini_get(&content, KDE_SECTION, "ProxyType").as_deref(),
+            Some("1")
+        );
+    }
+
+    #[test]
+    fn env_snippet_follows_proxy() {
+        let backend = backend("env", Desktop::Other);
+        backend.apply(&vexar(8080)).unwrap();
+        let content = std::fs::read_to_string(&backend.env_file).unwrap();
+        assert!(content.contains("export https_proxy=\"http://127.0.0.1:8080\""));
+        assert!(content.contains("export no_proxy=\"localhost,127.0.0.0/8,::1\""));
+        assert_eq!(
+            backend.read().unwrap().server.as_deref(),
+            Some("127.0.0.1:8080")
+        );
+
+        backend.apply(&ProxySnapshot::default()).unwrap();
+        let content = std::fs::read_to_string(&backend.env_file).unwrap();
+        assert!(content.contains("unset http_proxy"));
+        assert!(!backend.read().unwrap().enabled);
+    }
+
+    #[test]
+    fn socks_entry_reaches_every_desktop() {
+        let snapshot = ProxySnapshot {
+            server: Some(
+                "http=127.0.0.1:8080;https=127.0.0.1:8080;socks=127.0.0.1:1080".to_string(),
+            ),
+            ..vexar(8080)
+        };
+
+        let gnome = backend("gnome-socks", Desktop::Gnome);
+        gnome.apply(&snapshot).unwrap();
+        assert_eq!(
+            gnome
+                .gsettings
+                .get("org.gnome.system.proxy.socks", "port")
+                .unwrap(),
+            "1080"
+        );
+        assert_eq!(gnome.read().unwrap().server, snapshot.server);
+
+        let kde = backend("kde-socks", Desktop::Kde);
+        kde.apply(&snapshot).unwrap();
+        let content = std::fs::read_to_string(&kde.kioslaverc).unwrap();
+        assert!(content.contains("socksProxy=socks://127.0.0.1 1080"));
+        assert_eq!(kde.read().unwrap().server, snapshot.server);
+
+        let env = backend("env-socks", Desktop::Other);
+        env.apply(&snapshot).unwrap();
+        let content = std::fs::read_to_string(&env.env_file).unwrap();
+        assert!(content.contains("export ALL_PROXY=\"socks5://127.0.0.1:1080\""));
+        assert_eq!(env.read().unwrap().server, snapshot.server);
+    }
+
+    #[test]
+    fn parses_per_scheme_servers() {
+        assert_eq!(
+            format_server(vec![
+                (Scheme::Http, ProxyEndpoint::parse("a.example:80").unwrap()),
+                (
+                    Scheme::Https,
+                    ProxyEndpoint::parse("b.example:443").unwrap()
+                ),
+            ])
+            .as_deref(),
+            Some("http=a.example:80;https=b.example:443")
+        );
+        assert_eq!(
+            parse_kde_proxy("http://host.example:3128"),
+            ProxyEndpoint::parse("host.example:3128").ok()
+        );
+        assert_eq!(
+            url_endpoint("socks5://[::1]:1080").map(|e| e.to_string()),
+            Some("[::1]:1080".to_string())
+        );
+    }
+
+    #[test]
+    fn gvariant_round_trip() {
+        assert_eq!(parse_gvariant_string(&gvariant_string("it's")), "it's");
+        assert_eq!(
+            parse_gvariant_list("['localhost', '*.corp']"),
+            ["localhost", "*.corp"]
+        );
+        assert!(parse_gvariant_list("@as []").is_empty());
+    }
+}
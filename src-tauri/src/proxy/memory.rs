@@ -0,0 +1,72 @@
+//! Bellekte tutulan backend.
+//!
+//! Gerçek bir sistem proxy'si olmayan platformlarda ve testlerde kullanılır.
+//! Yazmalar registry ile aynı sırayla alan alan yapılır; böylece yarım kalan
+//! yazma ve geri alma davranışı her makinede denenebilir.
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::{ProxySnapshot, SystemProxyBackend};
+
+#[derive(Default)]
+pub struct MemoryBackend {
+    state: Mutex<ProxySnapshot>,
+    /// `Some(n)`: bir sonraki `apply` n alan yazdıktan sonra başarısız olur.
+    fail_after: Mutex<Option<usize>>,
+    notifications: AtomicUsize,
+}
+
+impl MemoryBackend {
+    #[cfg(test)]
+    pub fn with_state(state: ProxySnapshot) -> Self {
+        Self {
+            state: Mutex::new(state),
+            ..Self::default()
+        }
+    }
+
+    pub fn state(&self) -> ProxySnapshot {
+        self.state.lock().unwrap().clone()
+    }
+
+    /// Bir sonraki `apply` çağrısını `fields` alan yazdıktan sonra başarısız kılar.
+    #[cfg(test)]
+    pub fn fail_next_apply_after(&self, fields: usize) {
+        *self.fail_after.lock().unwrap() = Some(fields);
+    }
+
+    #[cfg(test)]
+    pub fn notifications(&self) -> usize {
+        self.notifications.load(Ordering::SeqCst)
+    }
+}
+
+impl SystemProxyBackend for MemoryBackend {
+    fn read(&self) -> Result<ProxySnapshot, String> {
+        Ok(self.state())
+    }
+
+    fn apply(&self, snapshot: &ProxySnapshot) -> Result<(), String> {
+        let fail_after = self.fail_after.lock().unwrap().take();
+        let mut state = self.state.lock().unwrap();
+        let steps: [&dyn Fn(&mut ProxySnapshot); 5] = [
+            &|s| s.server = snapshot.server.clone(),
+            &|s| s.bypass = snapshot.bypass.clone(),
+            &|s| s.auto_config_url = snapshot.auto_config_url.clone(),
+            &|s| s.auto_detect = snapshot.auto_detect,
+            &|s| s.enabled = snapshot.enabled,
+        ];
+        for (written, step) in steps.iter().enumerate() {
+            if fail_after == Some(written) {
+                return Err("Yazma başarısız (test)".to_string());
+            }
+            step(&mut state);
+        }
+        Ok(())
+    }
+
+    fn notify(&self) {
+        self.notifications.fetch_add(1, Ordering::SeqCst);
+    }
+}
@@ -0,0 +1,598 @@
+//! Sistem proxy ayarlarının açılması, kapatılması ve geri yüklenmesi.
+//!
+//! Platforma özgü okuma/yazma `SystemProxyBackend` arkasındadır;
+//! `ProxyController` önceki ayarları kayda alma, geri alma ve kurtarma
+//! mantığını her platformda aynı şekilde uygular.
+
+pub mod bypass;
+#[cfg(any(windows, test))]
+pub mod connection_settings;
+pub mod drift;
+pub mod endpoint;
+pub mod journal;
+#[cfg(any(target_os = "linux", test))]
+pub mod linux;
+#[cfg(any(not(any(windows, target_os = "linux")), test))]
+pub mod memory;
+pub mod pac;
+pub mod servers;
+#[cfg(any(windows, test))]
+pub mod windows;
+
+use std::sync::Mutex;
+
+use bypass::BypassList;
+use drift::DriftChange;
+use journal::{JournalEntry, ProxyJournal};
+use pac::{PacServer, SplitTunnel};
+use servers::ProxyServers;
+
+/// Sistem proxy ayarlarının bir anlık görüntüsü.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ProxySnapshot {
+    pub enabled: bool,
+    /// `ProxyServer`; değer hiç yoksa `None`.
+    pub server: Option<String>,
+    /// `ProxyOverride` (bypass listesi); değer hiç yoksa `None`.
+    pub bypass: Option<String>,
+    /// `AutoConfigURL` (PAC betiği); değer hiç yoksa `None`.
+    pub auto_config_url: Option<String>,
+    /// `AutoDetect` (WPAD); değer hiç yoksa `None`.
+    pub auto_detect: Option<bool>,
+}
+
+/// Bir oturumda sistem trafiğinin motora nasıl yönlendirileceği.
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProxyMode {
+    /// Tüm trafik `ProxyServer` ile motordan geçer.
+    #[default]
+    Global,
+    /// Yalnızca bölünmüş tünel listesindeki alan adları PAC ile motordan geçer.
+    Pac,
+}
+
+/// `get_system_proxy` yanıtı.
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct SystemProxyStatus {
+    pub current: ProxySnapshot,
+    /// Vexar bağlıyken geri yüklenmek üzere saklanan ayarlar.
+    pub saved: Option<ProxySnapshot>,
+}
+
+/// İşletim sisteminin proxy ayarlarına erişim.
+pub trait SystemProxyBackend: Send + Sync {
+    fn read(&self) -> Result<ProxySnapshot, String>;
+    /// Görüntüyü olduğu gibi yazar; görüntüde olmayan değerler silinir.
+    fn apply(&self, snapshot: &ProxySnapshot) -> Result<(), String>;
+    /// Tarayıcıların yeni ayarları hemen alması için sistemi bilgilendirir.
+    fn notify(&self);
+}
+
+/// Bu platformun gerçek backend'i. `config_dir` yalnızca Linux'ta kabuk
+/// ortam dosyası için kullanılır.
+pub fn platform_backend(config_dir: &std::path::Path) -> Box<dyn SystemProxyBackend> {
+    #[cfg(windows)]
+    {
+        let _ = config_dir;
+        Box::new(windows::RegistryBackend)
+    }
+    #[cfg(target_os = "linux")]
+    {
+        Box::new(linux::LinuxBackend::detect(config_dir))
+    }
+    #[cfg(not(any(windows, target_os = "linux")))]
+    {
+        let _ = config_dir;
+        Box::new(memory::MemoryBackend::default())
+    }
+}
+
+/// Managed state olarak tutulan sistem proxy denetleyicisi.
+pub struct ProxyController {
+    backend: Box<dyn SystemProxyBackend>,
+    journal: ProxyJournal,
+    pac: PacServer,
+    /// Vexar'ın son yazdığı ayarlar, backend'den geri okunmuş haliyle.
+    /// Sapma kontrolü bununla yapılır; proxy bizde değilse `None`.
+    applied: Mutex<Option<ProxySnapshot>>,
+}
+
+impl ProxyController {
+    pub fn new(backend: Box<dyn SystemProxyBackend>, journal: ProxyJournal) -> Self {
+        Self {
+            backend,
+            journal,
+            pac: PacServer::default(),
+            applied: Mutex::new(None),
+        }
+    }
+
+    /// Önceki ayarları kayda alır ve sistem proxy'sini yerel motora veya
+    /// uzak bir Vexar'a yönlendirir. Yazma yarıda kalırsa önceki ayarlar geri yüklenir.
+    pub fn enable(
+        &self,
+        servers: &ProxyServers,
+        bypass: &BypassList,
+        mode: ProxyMode,
+        split: &SplitTunnel,
+    ) -> Result<(), String> {
+        // ✅ Port aralığı validasyonu: yalnızca yerel motor ayrıcalıksız porta bağlanır,
+        // uzak Vexar 80 veya 443 gibi herhangi bir portta dinleyebilir
+        if servers
+            .endpoints()
+            .any(|endpoint| endpoint.is_local() && endpoint.port() < 1024)
+        {
+            return Err("Geçersiz port numarası (1024-65535 arası olmalı)".to_string());
+        }
+        let endpoint = servers.primary().ok_or("Proxy sunucusu boş.")?;
+
+        // PAC sunucusu sisteme dokunmadan önce hazır olmalı
+        let pac_url = match mode {
+            ProxyMode::Global => None,
+            ProxyMode::Pac => Some(self.pac.publish(pac::generate(&split.domains, endpoint))?),
+        };
+
+        // ✅ Sisteme dokunmadan önce önceki ayarları kaydet (çökme sonrası kurtarma)
+        let entry = self.journal.begin(endpoint, || self.backend.read())?;
+
+        let target = ProxySnapshot {
+            // PAC modunda yönlendirmeyi betik yapar; manuel proxy kapalı kalır
+            enabled: pac_url.is_none(),
+            server: Some(servers.to_string()),
+            bypass: Some(bypass.to_wininet()),
+            // Kullanıcının kendi PAC betiği tanımlıysa Windows manuel proxy'yi yok
+            // sayar; bağlantı süresince kaldırılır ve kesilince geri yüklenir
+            auto_config_url: pac_url,
+            auto_detect: entry.previous.auto_detect,
+        };
+
+        if self.backend.apply(&target).is_err() {
+            // ✅ Rollback yap
+            let _ = self.restore();
+            return Err("Registry güncelleme başarısız, geri alındı.".to_string());
+        }
+
+        // CRITICAL: Notify the system about the change so browsers pick it up immediately
+        self.backend.notify();
+        self.remember_applied();
+        Ok(())
+    }
+
+    /// Kayıttaki önceki ayarları geri yükler ve kaydı siler. Kayıt yoksa
+    /// (ör. "İnterneti Onar" ile çağrıldığında) yalnızca proxy kapatılır.
+    pub fn restore(&self) -> Result<(), String> {
+        *self.applied.lock().unwrap() = None;
+        let target = match self.journal.load() {
+            Some(entry) => entry.previous,
+            None => ProxySnapshot {
+                enabled: false,
+                ..self.backend.read()?
+            },
+        };
+        let result = self.backend.apply(&target);
+        // Yarım kalan yazmada da tarayıcılar gerçek durumu görsün
+        self.backend.notify();
+        result.map_err(|e| format!("Proxy ayarları geri yüklenemedi: {e}"))?;
+
+        // Proxy kapandı; bir sonraki açılışta kurtarılacak bir şey kalmadı
+        self.journal.clear()
+    }
+
+    /// Proxy Vexar tarafından açıksa bypass listesini motoru yeniden
+    /// başlatmadan günceller; kapalıysa bir sonraki `enable`'da kullanılır.
+    pub fn update_bypass(&self, bypass: &BypassList) -> Result<(), String> {
+        if self.journal.load().is_none() {
+            return Ok(());
+        }
+        // Başka bir araç ayarları değiştirdiyse onun ayarlarına yazılmamalı
+        let base = match self.applied.lock().unwrap().clone() {
+            Some(applied) => applied,
+            None => self.backend.read()?,
+        };
+        let target = ProxySnapshot {
+            bypass: Some(bypass.to_wininet()),
+            ..base
+        };
+        self.backend.apply(&target)?;
+        self.backend.notify();
+        self.remember_applied();
+        Ok(())
+    }
+
+    /// Sistemdeki ayarları Vexar'ın yazdıklarıyla karşılaştırır; proxy
+    /// Vexar'da değilse boş döner.
+    pub fn check_drift(&self) -> Result<Vec<DriftChange>, String> {
+        let Some(applied) = self.applied.lock().unwrap().clone() else {
+            return Ok(Vec::new());
+        };
+        Ok(drift::diff(&applied, &self.backend.read()?))
+    }
+
+    /// Vexar'ın ayarlarını yeniden yazar.
+    pub fn reassert(&self) -> Result<(), String> {
+        let Some(applied) = self.applied.lock().unwrap().clone() else {
+            return Ok(());
+        };
+        self.backend.apply(&applied)?;
+        self.backend.notify();
+        Ok(())
+    }
+
+    /// Proxy'yi artık başka bir araç yönetiyor: sisteme dokunmadan kaydı
+    /// bırakır ki bağlantı kesilirken onun ayarları ezilmesin.
+    pub fn release(&self) -> Result<(), String> {
+        *self.applied.lock().unwrap() = None;
+        self.journal.clear()
+    }
+
+    /// Bazı backend'ler yazılanı farklı biçimde geri okur (ör. GNOME'da
+    /// `<local>`); sapma kontrolü yanlış alarm vermesin diye okunan hali saklanır.
+    fn remember_applied(&self) {
+        *self.applied.lock().unwrap() = self.backend.read().ok();
+    }
+
+    pub fn status(&self) -> Result<SystemProxyStatus, String> {
+        Ok(SystemProxyStatus {
+            current: self.backend.read()?,
+            saved: self.journal.load().map(|entry| entry.previous),
+        })
+    }
+
+    /// Açılışta çağrılır; sahibi ölmüş bir kayıt varsa önceki ayarları geri yükler.
+    pub fn recover(
+        &self,
+        is_alive: impl FnOnce(&JournalEntry) -> bool,
+    ) -> Result<Option<JournalEntry>, String> {
+        self.journal.recover(is_alive, |previous| {
+            self.backend.apply(previous)?;
+            self.backend.notify();
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use endpoint::ProxyEndpoint;
+    use memory::MemoryBackend;
+    use std::sync::Arc;
+
+    impl SystemProxyBackend for Arc<MemoryBackend> {
+        fn read(&self) -> Result<ProxySnapshot, String> {
+            self.as_ref().read()
+        }
+
+        fn apply(&self, snapshot: &ProxySnapshot) -> Result<(), String> {
+            self.as_ref().apply(snapshot)
+        }
+
+        fn notify(&self) {
+            self.as_ref().notify()
+        }
+    }
+
+    fn corporate() -> ProxySnapshot {
+        ProxySnapshot {
+            enabled: true,
+            server: Some("corp.example:3128".to_string()),
+            bypass: Some("*.corp.example;<local>".to_string()),
+            auto_config_url: Some("http://wpad.corp.example/proxy.pac".to_string()),
+            auto_detect: Some(true),
+        }
+    }
+
+    fn local(port: u16) -> ProxyServers {
+        ProxyServers::All(ProxyEndpoint::local(port))
+    }
+
+    fn controller(name: &str, state: ProxySnapshot) -> (ProxyController, Arc<MemoryBackend>) {
+        let dir =
+            std::env::temp_dir().join(format!("vexar-controller-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let backend = Arc::new(MemoryBackend::with_state(state));
+        let controller = ProxyController::new(
+            Box::new(backend.clone()),
+            ProxyJournal::new(dir.join(journal::JOURNAL_FILE)),
+        );
+        (controller, backend)
+    }
+
+    #[test]
+    fn enable_points_system_at_engine() {
+        let (controller, backend) = controller("enable", corporate());
+        controller
+            .enable(
+                &local(8080),
+                &BypassList::default(),
+                ProxyMode::Global,
+                &SplitTunnel::default(),
+            )
+            .unwrap();
+        assert_eq!(
+            backend.state(),
+            ProxySnapshot {
+                enabled: true,
+                server: Some("127.0.0.1:8080".to_string()),
+                bypass: Some("<local>".to_string()),
+                auto_config_url: None,
+                auto_detect: Some(true),
+            }
+        );
+        assert_eq!(backend.notifications(), 1);
+        assert_eq!(controller.status().unwrap().saved, Some(corporate()));
+    }
+
+    #[test]
+    fn enable_points_system_at_remote_vexar() {
+        let (controller, backend) = controller("remote", corporate());
+        let remote = ProxyServers::All(ProxyEndpoint::parse("[fe80::20]:8081").unwrap());
+        controller
+            .enable(
+                &remote,
+                &BypassList::default(),
+                ProxyMode::Global,
+                &SplitTunnel::default(),
+            )
+            .unwrap();
+        assert_eq!(backend.state().server.as_deref(), Some("[fe80::20]:8081"));
+        let entry = controller.journal.load().unwrap();
+        assert_eq!(entry.host.as_deref(), Some("fe80::20"));
+        assert_eq!(entry.port, 8081);
+
+        controller.restore().unwrap();
+        assert_eq!(backend.state(), corporate());
+    }
+
+    #[test]
+    fn enable_writes_per_scheme_servers() {
+        let (controller, backend) = controller("schemes", corporate());
+        let servers =
+            ProxyServers::parse("http=127.0.0.1:8080;https=127.0.0.1:8080;socks=127.0.0.1:1080")
+                .unwrap();
+        controller
+            .enable(
+                &servers,
+                &BypassList::default(),
+                ProxyMode::Global,
+                &SplitTunnel::default(),
+            )
+            .unwrap();
+        assert_eq!(
+            backend.state().server.as_deref(),
+            Some("http=127.0.0.1:8080;https=127.0.0.1:8080;socks=127.0.0.1:1080")
+        );
+        assert_eq!(controller.journal.load().unwrap().port, 8080);
+
+        let privileged = ProxyServers::parse("http=127.0.0.1:8080;socks=127.0.0.1:1000").unwrap();
+        assert!(
+            controller
+                .enable(
+                    &privileged,
+                    &BypassList::default(),
+                    ProxyMode::Global,
+                    &SplitTunnel::default(),
+                )
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn restore_brings_back_exact_previous_state() {
+        let (controller, backend) = controller("restore", corporate());
+        controller
+            .enable(
+                &local(8080),
+                &BypassList::default(),
+                ProxyMode::Global,
+                &SplitTunnel::default(),
+            )
+            .unwrap();
+        // Yeniden bağlanma: ikinci enable önceki ayarların üstüne yazmamalı
+        controller
+            .enable(
+                &local(8081),
+                &BypassList::default(),
+                ProxyMode::Global,
+                &SplitTunnel::default(),
+            )
+            .unwrap();
+        controller.restore().unwrap();
+        assert_eq!(backend.state(), corporate());
+        assert_eq!(controller.status().unwrap().saved, None);
+    }
+
+    #[test]
+    fn restore_without_journal_only_disables() {
+        let (controller, backend) = controller("disable", corporate());
+        controller.restore().unwrap();
+        assert_eq!(
+            backend.state(),
+            ProxySnapshot {
+                enabled: false,
+                ..corporate()
+            }
+        );
+    }
+
+    #[test]
+    fn partial_failure_rolls_back() {
+        let (controller, backend) = controller("partial", corporate());
+        // ProxyServer yazıldıktan sonra başarısız ol
+        backend.fail_next_apply_after(1);
+        assert!(
+            controller
+                .enable(
+                    &local(8080),
+                    &BypassList::default(),
+                    ProxyMode::Global,
+                    &SplitTunnel::default()
+                )
+                .is_err()
+        );
+        assert_eq!(backend.state(), corporate());
+        assert_eq!(controller.status().unwrap().saved, None);
+    }
+
+    #[test]
+    fn failed_rollback_keeps_journal_for_next_launch() {
+        let (controller, backend) = controller("rollback-fail", corporate());
+        controller
+            .enable(
+                &local(8080),
+                &BypassList::default(),
+                ProxyMode::Global,
+                &SplitTunnel::default(),
+            )
+            .unwrap();
+        backend.fail_next_apply_after(2);
+        assert!(controller.restore().is_err());
+        assert_eq!(controller.status().unwrap().saved, Some(corporate()));
+        // Açılışta kurtarma tamamlar
+        assert!(controller.recover(|_| false).unwrap().is_some());
+        assert_eq!(backend.state(), corporate());
+    }
+
+    #[test]
+    fn bypass_updates_only_while_enabled() {
+        let (controller, backend) = controller("bypass", corporate());
+        let list = BypassList::parse_wininet("<local>;*.bank.example").unwrap();
+        controller.update_bypass(&list).unwrap();
+        assert_eq!(backend.state(), corporate());
+
+        controller
+            .enable(
+                &local(8080),
+                &BypassList::default(),
+                ProxyMode::Global,
+                &SplitTunnel::default(),
+            )
+            .unwrap();
+        controller.update_bypass(&list).unwrap();
+        let state = backend.state();
+        assert_eq!(state.bypass.as_deref(), Some("<local>;*.bank.example"));
+        assert_eq!(state.server.as_deref(), Some("127.0.0.1:8080"));
+
+        // Geri yüklemede kullanıcının kendi bypass listesi döner
+        controller.restore().unwrap();
+        assert_eq!(backend.state(), corporate());
+    }
+
+    #[test]
+    fn pac_mode_sets_auto_config_url() {
+        let (controller, backend) = controller("pac", corporate());
+        let split = SplitTunnel {
+            domains: vec!["discord.com".to_string()],
+        };
+        controller
+            .enable(&local(8080), &BypassList::default(), ProxyMode::Pac, &split)
+            .unwrap();
+        let state = backend.state();
+        assert!(!state.enabled);
+        let url = state.auto_config_url.unwrap();
+        assert!(url.starts_with("http://127.0.0.1:"));
+
+        // Sistem betiği loopback sunucusundan alabilmeli
+        use std::io::{Read, Write};
+        let addr = url.trim_start_matches("http://").split('/').next().unwrap();
+        let mut stream = std::net::TcpStream::connect(addr).unwrap();
+        write!(stream, "GET /proxy.pac HTTP/1.1\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.contains("\"discord.com\""));
+        assert!(response.contains("PROXY 127.0.0.1:8080"));
+
+        controller.restore().unwrap();
+        assert_eq!(backend.state(), corporate());
+    }
+
+    #[test]
+    fn detects_and_reasserts_drift() {
+        let (controller, backend) = controller("drift", corporate());
+        assert!(controller.check_drift().unwrap().is_empty());
+
+        controller
+            .enable(
+                &local(8080),
+                &BypassList::default(),
+                ProxyMode::Global,
+                &SplitTunnel::default(),
+            )
+            .unwrap();
+        assert!(controller.check_drift().unwrap().is_empty());
+
+        // Bir VPN istemcisi ayarları alttan değiştiriyor
+        let vpn = ProxySnapshot {
+            server: Some("10.8.0.1:3128".to_string()),
+            ..backend.state()
+        };
+        backend.apply(&vpn).unwrap();
+        let changes = controller.check_drift().unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].field, "server");
+        assert_eq!(changes[0].actual.as_deref(), Some("10.8.0.1:3128"));
+
+        controller.reassert().unwrap();
+        assert!(controller.check_drift().unwrap().is_empty());
+        assert_eq!(backend.state().server.as_deref(), Some("127.0.0.1:8080"));
+
+        controller.restore().unwrap();
+        assert!(controller.check_drift().unwrap().is_empty());
+    }
+
+    #[test]
+    fn release_leaves_other_tools_settings() {
+        let (controller, backend) = controller("release", corporate());
+        controller
+            .enable(
+                &local(8080),
+                &BypassList::default(),
+                ProxyMode::Global,
+                &SplitTunnel::default(),
+            )
+            .unwrap();
+        let vpn = ProxySnapshot {
+            server: Some("10.8.0.1:3128".to_string()),
+            ..backend.state()
+        };
+        backend.apply(&vpn).unwrap();
+
+        controller.release().unwrap();
+        assert!(controller.check_drift().unwrap().is_empty());
+        assert_eq!(controller.status().unwrap().saved, None);
+        assert_eq!(backend.state(), vpn);
+    }
+
+    #[test]
+    fn rejects_privileged_ports() {
+        let (controller, backend) = controller("port", ProxySnapshot::default());
+        assert!(
+            controller
+                .enable(
+                    &local(80),
+                    &BypassList::default(),
+                    ProxyMode::Global,
+                    &SplitTunnel::default()
+                )
+                .is_err()
+        );
+        assert_eq!(backend.state(), ProxySnapshot::default());
+    }
+
+    #[test]
+    fn accepts_privileged_ports_on_remote_vexar() {
+        let (controller, backend) = controller("remote-port", ProxySnapshot::default());
+        let remote = ProxyServers::All(ProxyEndpoint::parse("vexar.lan:443").unwrap());
+        controller
+            .enable(
+                &remote,
+                &BypassList::default(),
+                ProxyMode::Global,
+                &SplitTunnel::default(),
+            )
+            .unwrap();
+        assert_eq!(backend.state().server, Some("vexar.lan:443".to_string()));
+    }
+}
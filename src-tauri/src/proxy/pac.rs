@@ -0,0 +1,315 @@
+//! PAC ile bölünmüş tünel.
+//!
+//! Global modda tüm trafik motordan geçer. PAC modunda yalnızca listedeki
+//! alan adları (ve alt alan adları) `PROXY 127.0.0.1:PORT` ile motora
+//! (istemci modunda uzak Vexar'a) gönderilir, geri kalan her şey `DIRECT`
+//! çıkar. Betik loopback üzerinde küçük bir HTTP sunucusundan verilir ve
+//! sistemde `AutoConfigURL` olarak ayarlanır. LAN paylaşımında diğer
+//! cihazlar için üretilen betik ise paylaşılan porttan verilir.
+
+use std::io::{Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use super::endpoint::ProxyEndpoint;
+
+pub const PAC_PATH: &str = "/proxy.pac";
+/// WPAD istemcilerinin aradığı ad; içerik aynıdır.
+const WPAD_PATH: &str = "/wpad.dat";
+const PAC_CONTENT_TYPE: &str = "application/x-ns-proxy-autoconfig";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// PAC modunda motordan geçirilecek alan adları.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct SplitTunnel {
+    pub domains: Vec<String>,
+}
+
+impl Default for SplitTunnel {
+    fn default() -> Self {
+        Self {
+            domains: [
+                "discord.com",
+                "discord.gg",
+                "discord.media",
+                "discordapp.com",
+                "discordapp.net",
+            ]
+            .map(String::from)
+            .to_vec(),
+        }
+    }
+}
+
+impl SplitTunnel {
+    pub fn validate(&self) -> Result<(), String> {
+        for domain in &self.domains {
+            normalize_domain(domain)?;
+        }
+        Ok(())
+    }
+}
+
+/// `*.example.com`, `.example.com` ve `Example.com` aynı girdidir; alt alan
+/// adları her zaman kapsandığından önekler atılır.
+pub fn normalize_domain(input: &str) -> Result<String, String> {
+    let domain = input
+        .trim()
+        .trim_start_matches("*.")
+        .trim_start_matches('.')
+        .trim_end_matches('.')
+        .to_ascii_lowercase();
+    let valid = !domain.is_empty()
+        && domain.len() <= 253
+        && domain.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        });
+    if valid {
+        Ok(domain)
+    } else {
+        Err(format!("Geçersiz alan adı: {input}"))
+    }
+}
+
+/// Alan adı listesinden PAC betiği üretir. Geçersiz girdiler atlanır,
+/// tekrarlar birleştirilir; sıralama korunur.
+pub fn generate(domains: &[String], proxy: &ProxyEndpoint) -> String {
+    let mut unique: Vec<String> = Vec::new();
+    for domain in domains.iter().filter_map(|d| normalize_domain(d).ok()) {
+        if !unique.contains(&domain) {
+            unique.push(domain);
+        }
+    }
+    let list = unique
+        .iter()
+        .map(|domain| format!("\n  \"{domain}\""))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!(
+        r#"// Vexar tarafından oluşturuldu. Listedeki alan adları motordan geçer.
+var PROXY = "PROXY {proxy}";
+var DOMAINS = [{list}
+];
+
+function FindProxyForURL(url, host) {{
+  host = host.toLowerCase();
+  for (var i = 0; i < DOMAINS.length; i++) {{
+    if (host === DOMAINS[i] || dnsDomainIs(host, "." + DOMAINS[i])) {{
+      return PROXY;
+    }}
+  }}
+  return "DIRECT";
+}}
+"#
+    )
+}
+
+/// LAN cihazları için betik: her şey paylaşılan Vexar'dan geçer. Bilgisayar
+/// kapalıyken cihazın internetsiz kalmaması için `DIRECT`'e düşülür.
+pub fn generate_shared(proxy: &ProxyEndpoint) -> String {
+    format!(
+        r#"// Vexar tarafından oluşturuldu. Tüm trafik paylaşılan Vexar'dan geçer.
+function FindProxyForURL(url, host) {{
+  if (isPlainHostName(host)) {{
+    return "DIRECT";
+  }}
+  return "PROXY {proxy}; DIRECT";
+}}
+"#
+    )
+}
+
+/// Betiği loopback üzerinden veren HTTP sunucusu. İlk kullanımda başlar ve
+/// uygulama boyunca açık kalır; betik değiştiğinde sunucu yeniden başlamaz.
+#[derive(Default)]
+pub struct PacServer {
+    script: Arc<RwLock<String>>,
+    addr: Mutex<Option<SocketAddr>>,
+    revision: AtomicU64,
+}
+
+impl PacServer {
+    /// Betiği yayınlar ve `AutoConfigURL` olarak yazılacak adresi döndürür.
+    /// Adres her yayında değişir ki sistem önbellekteki eski betiği kullanmasın.
+    pub fn publish(&self, script: String) -> Result<String, String> {
+        *self.script.write().unwrap() = script;
+        let addr = self.ensure_started()?;
+        let revision = self.revision.fetch_add(1, Ordering::SeqCst) + 1;
+        Ok(format!("http://{addr}{PAC_PATH}?v={revision}"))
+    }
+
+    fn ensure_started(&self) -> Result<SocketAddr, String> {
+        let mut addr = self.addr.lock().unwrap();
+        if let Some(addr) = *addr {
+            return Ok(addr);
+        }
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .map_err(|e| format!("PAC sunucusu başlatılamadı: {e}"))?;
+        let local = listener
+            .local_addr()
+            .map_err(|e| format!("PAC sunucusu başlatılamadı: {e}"))?;
+        let script = self.script.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let script = script.clone();
+                std::thread::spawn(move || {
+                    let _ = serve(stream, &script);
+                });
+            }
+        });
+        *addr = Some(local);
+        Ok(local)
+    }
+}
+
+fn serve(mut stream: TcpStream, script: &RwLock<String>) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = stream.read(&mut buf)?;
+        if n == 0 || request.len() + n > MAX_REQUEST_BYTES {
+            break;
+        }
+        request.extend_from_slice(&buf[..n]);
+    }
+
+    let response = respond(&request, &script.read().unwrap()).unwrap_or_else(|| {
+        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+    });
+    stream.write_all(response.as_bytes())
+}
+
+/// İstek betiğin kendisini istiyorsa yanıtı döndürür. Proxy istekleri
+/// (`GET http://...`, `CONNECT`) betik isteği sayılmaz.
+pub fn respond(request: &[u8], script: &str) -> Option<String> {
+    let request = String::from_utf8_lossy(request);
+    let mut parts = request.lines().next().unwrap_or_default().split(' ');
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default();
+    let path = path.split('?').next().unwrap_or_default();
+    if !(method == "GET" || method == "HEAD") || !(path == PAC_PATH || path == WPAD_PATH) {
+        return None;
+    }
+    let body = if method == "HEAD" { "" } else { script };
+    Some(format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: {PAC_CONTENT_TYPE}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{body}",
+        script.len()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn domains(list: &[&str]) -> Vec<String> {
+        list.iter().map(|d| d.to_string()).collect()
+    }
+
+    #[test]
+    fn generates_discord_script() {
+        assert_eq!(
+            generate(&SplitTunnel::default().domains, &ProxyEndpoint::local(8080)),
+            include_str!("testdata/discord.pac")
+        );
+    }
+
+    #[test]
+    fn normalizes_and_deduplicates() {
+        assert_eq!(
+            generate(
+                &domains(&["*.Example.com", "example.com.", "bad domain", ".twitch.tv"]),
+                &ProxyEndpoint::local(9000)
+            ),
+            include_str!("testdata/normalized.pac")
+        );
+    }
+
+    #[test]
+    fn empty_list_sends_everything_direct() {
+        assert_eq!(
+            generate(&[], &ProxyEndpoint::local(8080)),
+            include_str!("testdata/empty.pac")
+        );
+    }
+
+    #[test]
+    fn shared_script_points_at_lan_address() {
+        let script = generate_shared(&ProxyEndpoint::new("192.168.1.10", 8080).unwrap());
+        assert!(script.contains("\"PROXY 192.168.1.10:8080; DIRECT\""));
+
+        let response = respond(b"GET /wpad.dat HTTP/1.1\r\nHost: wpad\r\n\r\n", &script).unwrap();
+        assert!(response.contains(PAC_CONTENT_TYPE));
+        assert!(response.ends_with(&script));
+        assert!(
+            respond(
+                b"GET http://example.com/proxy.pac HTTP/1.1\r\n\r\n",
+                &script
+            )
+            .is_none()
+        );
+        assert!(respond(b"CONNECT example.com:443 HTTP/1.1\r\n\r\n", &script).is_none());
+        assert!(respond(b"POST /proxy.pac HTTP/1.1\r\n\r\n", &script).is_none());
+    }
+
+    #[test]
+    fn validates_domains() {
+        assert_eq!(normalize_domain("*.Discord.com").unwrap(), "discord.com");
+        assert!(normalize_domain("").is_err());
+        assert!(normalize_domain("*").is_err());
+        assert!(normalize_domain("a\"b.com").is_err());
+        assert!(
+            SplitTunnel {
+                domains: domains(&["ok.example", "no/slash"])
+            }
+            .validate()
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn serves_script_on_loopback() {
+        let server = PacServer::default();
+        let url = server
+            .publish("function FindProxyForURL(){}".to_string())
+            .unwrap();
+        let addr = url
+            .strip_prefix("http://")
+            .and_then(|rest| rest.split('/').next())
+            .unwrap();
+        assert!(addr.starts_with("127.0.0.1:"));
+
+        let fetch = |path: &str| {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            write!(stream, "GET {path} HTTP/1.1\r\nHost: {addr}\r\n\r\n").unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+
+        let response = fetch("/proxy.pac?v=1");
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains(PAC_CONTENT_TYPE));
+        assert!(response.ends_with("function FindProxyForURL(){}"));
+        assert!(fetch("/other").starts_with("HTTP/1.1 404"));
+
+        // Yeniden yayın aynı sunucuyu kullanır ama adres değişir
+        let next = server.publish("// yeni".to_string()).unwrap();
+        assert_ne!(next, url);
+        assert!(next.contains(addr));
+        assert!(fetch("/proxy.pac").ends_with("// yeni"));
+    }
+}
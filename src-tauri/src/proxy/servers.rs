@@ -0,0 +1,281 @@
+//! WinINet `ProxyServer` değeri.
+//!
+//! Değer ya tek bir `host:port` (HTTP, HTTPS ve FTP aynı adrese gider) ya da
+//! şemaya göre ayrılmış bir listedir: `http=h:p;https=h:p;socks=h:p`. SOCKS
+//! yalnızca şemalı listede verilebilir. Linux backend'i de masaüstü
+//! ayarlarını bu biçime çevirir; böylece `ProxySnapshot` her platformda
+//! aynı metni taşır.
+
+use std::fmt;
+
+use super::endpoint::ProxyEndpoint;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scheme {
+    Http,
+    Https,
+    Ftp,
+    Socks,
+}
+
+impl Scheme {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Scheme::Http => "http",
+            Scheme::Https => "https",
+            Scheme::Ftp => "ftp",
+            Scheme::Socks => "socks",
+        }
+    }
+
+    fn parse(input: &str) -> Result<Self, String> {
+        match input.trim().to_ascii_lowercase().as_str() {
+            "http" => Ok(Scheme::Http),
+            "https" => Ok(Scheme::Https),
+            "ftp" => Ok(Scheme::Ftp),
+            "socks" => Ok(Scheme::Socks),
+            _ => Err(format!("Bilinmeyen proxy şeması: {input}")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProxyServers {
+    /// `host:port`; SOCKS dışındaki tüm şemalar.
+    All(ProxyEndpoint),
+    /// `şema=host:port` listesi; sıralama korunur.
+    PerScheme(Vec<(Scheme, ProxyEndpoint)>),
+}
+
+impl ProxyServers {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err("Proxy sunucusu boş.".to_string());
+        }
+        if !input.contains('=') {
+            return parse_address(input).map(ProxyServers::All);
+        }
+
+        let mut entries: Vec<(Scheme, ProxyEndpoint)> = Vec::new();
+        for part in input.split(';').map(str::trim).filter(|p| !p.is_empty()) {
+            let (scheme, address) = part
+                .split_once('=')
+                .ok_or_else(|| format!("Geçersiz proxy girdisi: {part} (şema=host:port olmalı)"))?;
+            let scheme = Scheme::parse(scheme)?;
+            if entries.iter().any(|(s, _)| *s == scheme) {
+                return Err(format!(
+                    "{} şeması birden fazla kez verilmiş.",
+                    scheme.as_str()
+                ));
+            }
+            entries.push((scheme, parse_address(address)?));
+        }
+        Ok(ProxyServers::PerScheme(entries))
+    }
+
+    /// Şema başına adreslerden değer oluşturur. HTTP, HTTPS ve FTP aynı
+    /// adresteyse ve SOCKS yoksa tek adrese indirgenir; liste boşsa `None`.
+    pub fn from_schemes(entries: Vec<(Scheme, ProxyEndpoint)>) -> Option<Self> {
+        let first = entries.first()?.1.clone();
+        let get = |scheme| entries.iter().find(|(s, _)| *s == scheme).map(|(_, e)| e);
+        let uniform = get(Scheme::Http) == Some(&first)
+            && get(Scheme::Https) == Some(&first)
+            && get(Scheme::Ftp).is_none_or(|e| *e == first)
+            && get(Scheme::Socks).is_none();
+        Some(if uniform {
+            ProxyServers::All(first)
+        } else {
+            ProxyServers::PerScheme(entries)
+        })
+    }
+
+    pub fn get(&self, scheme: Scheme) -> Option<&ProxyEndpoint> {
+        match self {
+            ProxyServers::All(endpoint) => (scheme != Scheme::Socks).then_some(endpoint),
+            ProxyServers::PerScheme(entries) => entries
+                .iter()
+                .find(|(s, _)| *s == scheme)
+                .map(|(_, endpoint)| endpoint),
+        }
+    }
+
+    /// Web trafiğinin gittiği adres; PAC betiği ve kurtarma kaydı bunu kullanır.
+    pub fn primary(&self) -> Option<&ProxyEndpoint> {
+        self.get(Scheme::Http)
+            .or_else(|| self.get(Scheme::Https))
+            .or_else(|| self.endpoints().next())
+    }
+
+    pub fn endpoints(&self) -> impl Iterator<Item = &ProxyEndpoint> {
+        let entries: Vec<&ProxyEndpoint> = match self {
+            ProxyServers::All(endpoint) => vec![endpoint],
+            ProxyServers::PerScheme(entries) => entries.iter().map(|(_, e)| e).collect(),
+        };
+        entries.into_iter()
+    }
+}
+
+impl fmt::Display for ProxyServers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProxyServers::All(endpoint) => write!(f, "{endpoint}"),
+            ProxyServers::PerScheme(entries) => {
+                for (i, (scheme, endpoint)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(";")?;
+                    }
+                    write!(f, "{}={endpoint}", scheme.as_str())?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Bazı araçların yazdığı `http://host:port` biçimi de kabul edilir.
+fn parse_address(input: &str) -> Result<ProxyEndpoint, String> {
+    let input = input.trim();
+    let address = input.split_once("://").map_or(input, |(_, rest)| rest);
+    ProxyEndpoint::parse(address.trim_end_matches('/'))
+}
+
+/// Bir oturumda `ProxyServer`'a hangi şemaların yazılacağı.
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemeLayout {
+    /// `host:port`: HTTP, HTTPS ve FTP.
+    #[default]
+    Single,
+    /// Yalnızca HTTP ve HTTPS; diğer şemalar doğrudan çıkar.
+    Web,
+    /// HTTP ve HTTPS'e ek olarak SOCKS; yalnızca SOCKS destekleyen uygulamalar da yönlenir.
+    WebSocks,
+}
+
+impl SchemeLayout {
+    pub fn needs_socks(self) -> bool {
+        self == SchemeLayout::WebSocks
+    }
+
+    pub fn servers(
+        self,
+        http: &ProxyEndpoint,
+        socks: Option<&ProxyEndpoint>,
+    ) -> Result<ProxyServers, String> {
+        let web = vec![(Scheme::Http, http.clone()), (Scheme::Https, http.clone())];
+        Ok(match self {
+            SchemeLayout::Single => ProxyServers::All(http.clone()),
+            SchemeLayout::Web => ProxyServers::PerScheme(web),
+            SchemeLayout::WebSocks => {
+                let socks = socks.ok_or("SOCKS dinleyicisi açık değil.")?;
+                let mut entries = web;
+                entries.push((Scheme::Socks, socks.clone()));
+                ProxyServers::PerScheme(entries)
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ep(input: &str) -> ProxyEndpoint {
+        ProxyEndpoint::parse(input).unwrap()
+    }
+
+    #[test]
+    fn round_trips_wininet_strings() {
+        for input in [
+            "127.0.0.1:8080",
+            "[::1]:8080",
+            "http=127.0.0.1:8080;https=127.0.0.1:8080",
+            "http=corp.example:3128;https=corp.example:3129;ftp=ftp.corp.example:21;socks=127.0.0.1:1080",
+        ] {
+            assert_eq!(ProxyServers::parse(input).unwrap().to_string(), input);
+        }
+    }
+
+    #[test]
+    fn normalizes_loose_input() {
+        let servers =
+            ProxyServers::parse(" HTTP=http://Corp.example:3128/ ;;https=corp.example:3128; ")
+                .unwrap();
+        assert_eq!(
+            servers.to_string(),
+            "http=corp.example:3128;https=corp.example:3128"
+        );
+        assert_eq!(servers.get(Scheme::Ftp), None);
+    }
+
+    #[test]
+    fn rejects_malformed_strings() {
+        for input in [
+            "",
+            "http=",
+            "gopher=host:70",
+            "http=a:1;http=b:2",
+            "http=a:1;b:2",
+            "http=host",
+        ] {
+            assert!(ProxyServers::parse(input).is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn single_address_excludes_socks() {
+        let servers = ProxyServers::parse("127.0.0.1:8080").unwrap();
+        assert_eq!(servers.get(Scheme::Ftp), Some(&ep("127.0.0.1:8080")));
+        assert_eq!(servers.get(Scheme::Socks), None);
+
+        let servers = ProxyServers::parse("socks=127.0.0.1:1080").unwrap();
+        assert_eq!(servers.get(Scheme::Http), None);
+        assert_eq!(servers.primary(), Some(&ep("127.0.0.1:1080")));
+    }
+
+    #[test]
+    fn collapses_uniform_schemes() {
+        let vexar = ep("127.0.0.1:8080");
+        assert_eq!(
+            ProxyServers::from_schemes(vec![
+                (Scheme::Http, vexar.clone()),
+                (Scheme::Https, vexar.clone()),
+            ]),
+            Some(ProxyServers::All(vexar.clone()))
+        );
+        assert_eq!(
+            ProxyServers::from_schemes(vec![
+                (Scheme::Http, vexar.clone()),
+                (Scheme::Https, vexar.clone()),
+                (Scheme::Socks, ep("127.0.0.1:1080")),
+            ])
+            .unwrap()
+            .to_string(),
+            "http=127.0.0.1:8080;https=127.0.0.1:8080;socks=127.0.0.1:1080"
+        );
+        assert_eq!(ProxyServers::from_schemes(Vec::new()), None);
+    }
+
+    #[test]
+    fn layouts_build_expected_strings() {
+        let http = ep("127.0.0.1:8080");
+        let socks = ep("127.0.0.1:1080");
+        let build = |layout: SchemeLayout| {
+            layout
+                .servers(&http, Some(&socks))
+                .map(|servers| servers.to_string())
+        };
+        assert_eq!(build(SchemeLayout::Single).unwrap(), "127.0.0.1:8080");
+        assert_eq!(
+            build(SchemeLayout::Web).unwrap(),
+            "http=127.0.0.1:8080;https=127.0.0.1:8080"
+        );
+        assert_eq!(
+            build(SchemeLayout::WebSocks).unwrap(),
+            "http=127.0.0.1:8080;https=127.0.0.1:8080;socks=127.0.0.1:1080"
+        );
+        assert!(SchemeLayout::WebSocks.servers(&http, None).is_err());
+    }
+}
@@ -0,0 +1,317 @@
+//! Windows registry backend'i.
+//!
+//! Ayarlar `HKCU\...\Internet Settings` altında tutulur; okuma ve yazma
+//! uygulamanın geri kalanında olduğu gibi `reg.exe` ile yapılır. Metin
+//! değerleri her yazıldığında `Connections\DefaultConnectionSettings` ikili
+//! değeri de aynı ayarlarla güncellenir.
+
+use std::collections::HashMap;
+
+use super::ProxySnapshot;
+#[cfg(windows)]
+use super::SystemProxyBackend;
+use super::connection_settings::{self, ConnectionSettings};
+
+#[cfg(windows)]
+pub struct RegistryBackend;
+
+#[cfg(windows)]
+impl SystemProxyBackend for RegistryBackend {
+    fn read(&self) -> Result<ProxySnapshot, String> {
+        let output = registry::query(registry::INTERNET_SETTINGS)?;
+        Ok(snapshot_from_values(&parse_reg_query(&output)))
+    }
+
+    /// Görüntüde olmayan değerler silinir; `ProxyEnable` en son yazılır ki
+    /// yarım kalan bir yazma proxy'yi eksik ayarlarla açmasın. İkili değer
+    /// önce hazırlanır: hiç yoksa varsayılandan oluşturulur, okunamıyorsa
+    /// hiçbir değer yazılmadan hata döner.
+    fn apply(&self, snapshot: &ProxySnapshot) -> Result<(), String> {
+        // ✅ İkili değeri okuyan uygulamalar da aynı ayarları görsün
+        let mut settings =
+            connection_settings_from_query(&registry::query(registry::CONNECTIONS)?)?
+                .unwrap_or_default();
+        settings.apply_snapshot(snapshot)?;
+
+        match &snapshot.server {
+            Some(server) => registry::set_sz("ProxyServer", server)?,
+            None => registry::delete("ProxyServer")?,
+        }
+        match &snapshot.bypass {
+            Some(bypass) => registry::set_sz("ProxyOverride", bypass)?,
+            None => registry::delete("ProxyOverride")?,
+        }
+        match &snapshot.auto_config_url {
+            Some(url) => registry::set_sz("AutoConfigURL", url)?,
+            None => registry::delete("AutoConfigURL")?,
+        }
+        match snapshot.auto_detect {
+            Some(detect) => registry::set_dword("AutoDetect", detect as u32)?,
+            None => registry::delete("AutoDetect")?,
+        }
+
+        registry::set_binary(connection_settings::VALUE_NAME, &settings.encode())?;
+
+        registry::set_dword("ProxyEnable", snapshot.enabled as u32)
+    }
+
+    fn notify(&self) {
+        registry::notify_change();
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum RegValue {
+    Dword(u32),
+    Sz(String),
+    Binary(Vec<u8>),
+    Other(String),
+}
+
+/// `reg query` çıktısındaki değerleri ada göre ayrıştırır.
+fn parse_reg_query(output: &str) -> HashMap<String, RegValue> {
+    let mut values = HashMap::new();
+    for line in output.lines() {
+        // Değer satırları dört boşlukla girintilidir: "    Ad    TÜR    veri"
+        let Some(line) = line.strip_prefix("    ") else {
+            continue;
+        };
+        let mut parts = line.splitn(3, "    ");
+        let (Some(name), Some(kind)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        let data = parts
+            .next()
+            .unwrap_or_default()
+            .trim_end_matches(['\r', '\n']);
+        let value = match kind {
+            "REG_DWORD" => match u32::from_str_radix(data.trim_start_matches("0x"), 16) {
+                Ok(n) => RegValue::Dword(n),
+                Err(_) => continue,
+            },
+            "REG_SZ" | "REG_EXPAND_SZ" => RegValue::Sz(data.to_string()),
+            "REG_BINARY" => match decode_hex(data.trim()) {
+                Some(bytes) => RegValue::Binary(bytes),
+                None => continue,
+            },
+            _ => RegValue::Other(data.to_string()),
+        };
+        values.insert(name.to_string(), value);
+    }
+    values
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// `Connections` anahtarının `reg query` çıktısından ikili değeri çözer;
+/// değer yoksa None, çözülemiyorsa hata döner.
+fn connection_settings_from_query(output: &str) -> Result<Option<ConnectionSettings>, String> {
+    match parse_reg_query(output).remove(connection_settings::VALUE_NAME) {
+        None => Ok(None),
+        Some(RegValue::Binary(bytes)) => ConnectionSettings::decode(&bytes).map(Some),
+        Some(_) => Err(format!(
+            "{} ikili değer değil.",
+            connection_settings::VALUE_NAME
+        )),
+    }
+}
+
+fn snapshot_from_values(values: &HashMap<String, RegValue>) -> ProxySnapshot {
+    let sz = |name: &str| match values.get(name) {
+        Some(RegValue::Sz(s)) => Some(s.clone()),
+        _ => None,
+    };
+    ProxySnapshot {
+        enabled: matches!(values.get("ProxyEnable"), Some(RegValue::Dword(n)) if *n != 0),
+        server: sz("ProxyServer"),
+        bypass: sz("ProxyOverride"),
+        auto_config_url: sz("AutoConfigURL"),
+        auto_detect: match values.get("AutoDetect") {
+            Some(RegValue::Dword(n)) => Some(*n != 0),
+            _ => None,
+        },
+    }
+}
+
+#[cfg(windows)]
+mod registry {
+    use std::os::windows::process::CommandExt;
+    use std::process::Command;
+
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+    pub const INTERNET_SETTINGS: &str =
+        "HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Internet Settings";
+    pub const CONNECTIONS: &str =
+        "HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Internet Settings\\Connections";
+
+    pub fn query(key: &str) -> Result<String, String> {
+        let output = Command::new("reg")
+            .args(["query", key])
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+            .map_err(|e| format!("Registry erişim hatası: {e}"))?;
+        if !output.status.success() {
+            return Err("Registry okunamadı.".to_string());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    pub fn set_dword(name: &str, value: u32) -> Result<(), String> {
+        add(INTERNET_SETTINGS, name, "REG_DWORD", &value.to_string())
+    }
+
+    pub fn set_sz(name: &str, value: &str) -> Result<(), String> {
+        add(INTERNET_SETTINGS, name, "REG_SZ", value)
+    }
+
+    /// `Connections` anahtarına ikili değer yazar.
+    pub fn set_binary(name: &str, value: &[u8]) -> Result<(), String> {
+        let hex: String = value.iter().map(|b| format!("{b:02X}")).collect();
+        add(CONNECTIONS, name, "REG_BINARY", &hex)
+    }
+
+    fn add(key: &str, name: &str, kind: &str, data: &str) -> Result<(), String> {
+        let status = Command::new("reg")
+            .args(["add", key, "/v", name, "/t", kind, "/d", data, "/f"])
+            .creation_flags(CREATE_NO_WINDOW)
+            .status()
+            .map_err(|e| format!("{name} ayarlanamadı: {e}"))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(format!("{name} ayarlanamadı."))
+        }
+    }
+
+    /// Değer yoksa da başarılı sayılır.
+    pub fn delete(name: &str) -> Result<(), String> {
+        let status = Command::new("reg")
+            .args(["delete", INTERNET_SETTINGS, "/v", name, "/f"])
+            .creation_flags(CREATE_NO_WINDOW)
+            .status()
+            .map_err(|e| format!("{name} silinemedi: {e}"))?;
+        if status.success() || !value_exists(name) {
+            Ok(())
+        } else {
+            Err(format!("{name} silinemedi."))
+        }
+    }
+
+    fn value_exists(name: &str) -> bool {
+        Command::new("reg")
+            .args(["query", INTERNET_SETTINGS, "/v", name])
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+            .is_ok_and(|output| output.status.success())
+    }
+
+    /// Notify Windows that internet settings have changed
+    /// This forces browsers to immediately pick up the new proxy settings
+    pub fn notify_change() {
+        use std::ptr::null_mut;
+        use winapi::um::wininet::{
+            INTERNET_OPTION_REFRESH, INTERNET_OPTION_SETTINGS_CHANGED, InternetSetOptionW,
+        };
+
+        unsafe {
+            // Notify that settings have changed
+            InternetSetOptionW(null_mut(), INTERNET_OPTION_SETTINGS_CHANGED, null_mut(), 0);
+            // Refresh the settings
+            InternetSetOptionW(null_mut(), INTERNET_OPTION_REFRESH, null_mut(), 0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QUERY_OUTPUT: &str = r"
+HKEY_CURRENT_USER\Software\Microsoft\Windows\CurrentVersion\Internet Settings
+    CertificateRevocation    REG_DWORD    0x1
+    ProxyEnable    REG_DWORD    0x1
+    ProxyServer    REG_SZ    http=corp.example:3128;https=corp.example:3128
+    ProxyOverride    REG_SZ    *.corp.example;<local>
+    AutoConfigURL    REG_SZ    http://wpad.corp.example/proxy.pac
+    AutoDetect    REG_DWORD    0x0
+    ZonesSecurityUpgrade    REG_BINARY    5E2A1F0A
+
+HKEY_CURRENT_USER\Software\Microsoft\Windows\CurrentVersion\Internet Settings\Connections
+";
+
+    #[test]
+    fn parses_reg_query_output() {
+        let values = parse_reg_query(QUERY_OUTPUT);
+        assert_eq!(values.get("ProxyEnable"), Some(&RegValue::Dword(1)));
+        assert_eq!(
+            values.get("ProxyOverride"),
+            Some(&RegValue::Sz("*.corp.example;<local>".to_string()))
+        );
+        assert_eq!(
+            values.get("ZonesSecurityUpgrade"),
+            Some(&RegValue::Binary(vec![0x5E, 0x2A, 0x1F, 0x0A]))
+        );
+    }
+
+    #[test]
+    fn builds_snapshot_from_values() {
+        assert_eq!(
+            snapshot_from_values(&parse_reg_query(QUERY_OUTPUT)),
+            ProxySnapshot {
+                enabled: true,
+                server: Some("http=corp.example:3128;https=corp.example:3128".to_string()),
+                bypass: Some("*.corp.example;<local>".to_string()),
+                auto_config_url: Some("http://wpad.corp.example/proxy.pac".to_string()),
+                auto_detect: Some(false),
+            }
+        );
+    }
+
+    #[test]
+    fn reads_connection_settings_blob() {
+        let blob = include_bytes!("testdata/dcs_corporate.bin");
+        let hex: String = blob.iter().map(|b| format!("{b:02X}")).collect();
+        let output = format!(
+            "\r\nHKEY_CURRENT_USER\\...\\Connections\r\n    DefaultConnectionSettings    REG_BINARY    {hex}\r\n    SavedLegacySettings    REG_BINARY    46000000\r\n"
+        );
+        let settings = connection_settings_from_query(&output).unwrap().unwrap();
+        let snapshot = snapshot_from_values(&parse_reg_query(QUERY_OUTPUT));
+        assert_eq!(settings.proxy_server, b"corp.example:3128");
+        // Metin değerleri farklı sunucu biçimi kullanıyor; yazınca eşitlenmeli
+        assert!(!settings.matches(&snapshot));
+        assert_eq!(
+            connection_settings_from_query("    ProxyEnable    REG_DWORD    0x1"),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn unreadable_blob_is_an_error() {
+        // Varsayılan değer yazılıp sayaç ve WPAD baytları kaybolmasın
+        let truncated = "    DefaultConnectionSettings    REG_BINARY    4600000003000000\r\n";
+        assert!(connection_settings_from_query(truncated).is_err());
+        let wrong_type = "    DefaultConnectionSettings    REG_SZ    46\r\n";
+        assert!(connection_settings_from_query(wrong_type).is_err());
+    }
+
+    #[test]
+    fn missing_values_are_none() {
+        let output = "HKEY_CURRENT_USER\\...\r\n    ProxyEnable    REG_DWORD    0x0\r\n    ProxyServer    REG_SZ    \r\n";
+        assert_eq!(
+            snapshot_from_values(&parse_reg_query(output)),
+            ProxySnapshot {
+                enabled: false,
+                server: Some(String::new()),
+                ..ProxySnapshot::default()
+            }
+        );
+    }
+}
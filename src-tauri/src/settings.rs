@@ -0,0 +1,144 @@
+//! Backend tarafında kalıcı tutulan ayarlar.
+//!
+//! Arayüz tercihleri (dil, tema vb.) localStorage'da kalır; motorun ve
+//! sistem proxy'sinin davranışını belirleyen ayarlar ise uygulama config
+//! dizinindeki `settings.json` dosyasında tutulur ki webview olmadan da
+//! okunabilsin.
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::engine::heartbeat::HeartbeatPolicy;
+use crate::engine::lan::LanSettings;
+use crate::engine::reconnect::ReconnectPolicy;
+use crate::integrations::IntegrationSettings;
+use crate::launcher::LauncherSettings;
+use crate::proxy::bypass::BypassList;
+use crate::proxy::drift::DriftPolicy;
+use crate::proxy::pac::SplitTunnel;
+
+pub const SETTINGS_FILE: &str = "settings.json";
+
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub reconnect: ReconnectPolicy,
+    pub heartbeat: HeartbeatPolicy,
+    /// Sistem proxy'sinden muaf tutulan adresler.
+    pub bypass: BypassList,
+    /// PAC modunda motordan geçirilecek alan adları.
+    pub split_tunnel: SplitTunnel,
+    /// Bağlıyken sistem proxy'sinin başka araçlarca değiştirilmesine tepki.
+    pub drift: DriftPolicy,
+    /// Sistem proxy'sini izlemeyen geliştirici araçları.
+    pub integrations: IntegrationSettings,
+    /// Proxy bayraklarıyla başlatılan uygulamalar.
+    pub launcher: LauncherSettings,
+    /// LAN paylaşımında motora bağlanabilecek istemciler.
+    pub lan: LanSettings,
+}
+
+impl Settings {
+    pub fn validate(&self) -> Result<(), String> {
+        self.reconnect.validate()?;
+        self.heartbeat.validate()?;
+        self.split_tunnel.validate()?;
+        self.drift.validate()?;
+        self.launcher.validate()?;
+        self.lan.validate()
+    }
+}
+
+pub struct SettingsStore {
+    path: PathBuf,
+    current: Mutex<Settings>,
+}
+
+impl SettingsStore {
+    /// Dosya yoksa veya bozuksa varsayılan ayarlarla başlar.
+    pub fn load(path: PathBuf) -> Self {
+        let settings = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<Settings>(&json).ok())
+            .filter(|settings| settings.validate().is_ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            current: Mutex::new(settings),
+        }
+    }
+
+    pub fn get(&self) -> Settings {
+        self.current.lock().unwrap().clone()
+    }
+
+    pub fn replace(&self, settings: Settings) -> Result<Settings, String> {
+        settings.validate()?;
+        let mut current = self.current.lock().unwrap();
+        self.save(&settings)?;
+        *current = settings.clone();
+        Ok(settings)
+    }
+
+    fn save(&self, settings: &Settings) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+        crate::files::write_atomic(&self.path, &json)
+    }
+}
+
+#[tauri::command]
+pub fn get_settings(store: tauri::State<'_, SettingsStore>) -> Settings {
+    store.get()
+}
+
+#[tauri::command]
+pub fn update_settings(
+    store: tauri::State<'_, SettingsStore>,
+    settings: Settings,
+) -> Result<Settings, String> {
+    store.replace(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("vexar-settings-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir.join(SETTINGS_FILE)
+    }
+
+    #[test]
+    fn round_trips_through_disk() {
+        let path = temp_path("roundtrip");
+        let store = SettingsStore::load(path.clone());
+        assert_eq!(store.get(), Settings::default());
+
+        let mut settings = store.get();
+        settings.reconnect.max_attempts = 8;
+        store.replace(settings.clone()).unwrap();
+
+        assert_eq!(SettingsStore::load(path).get(), settings);
+    }
+
+    #[test]
+    fn rejects_invalid_and_keeps_previous() {
+        let store = SettingsStore::load(temp_path("invalid"));
+        let mut settings = store.get();
+        settings.reconnect.jitter = 3.0;
+        assert!(store.replace(settings).is_err());
+        assert_eq!(store.get(), Settings::default());
+    }
+
+    #[test]
+    fn missing_fields_fall_back_to_defaults() {
+        let path = temp_path("partial");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, r#"{"reconnect":{"max_attempts":3}}"#).unwrap();
+        let settings = SettingsStore::load(path).get();
+        assert_eq!(settings.reconnect.max_attempts, 3);
+        assert_eq!(settings.reconnect.base_delay_ms, 1000);
+    }
+}
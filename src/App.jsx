@@ -2,12 +2,13 @@ import Settings from './Settings';
 import { motion, AnimatePresence } from 'framer-motion';
 import { disable, enable, isEnabled } from '@tauri-apps/plugin-autostart';
 import { useState, useRef, useEffect, useMemo } from 'react';
-import { Command, open } from '@tauri-apps/plugin-shell';
+import { open } from '@tauri-apps/plugin-shell';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { getTranslations } from './i18n';
 
 // Re-add missing imports
-import { Power, Shield, Settings as SettingsIcon, FileText, X, Copy, Trash2, WifiOff, Globe, Smartphone, HelpCircle } from 'lucide-react';
+import { Power, Shield, Settings as SettingsIcon, FileText, X, Copy, Trash2, WifiOff, Globe, Smartphone, HelpCircle, RotateCw, UserX } from 'lucide-react';
 import { getCurrentWindow } from '@tauri-apps/api/window';
 import { writeText } from '@tauri-apps/plugin-clipboard-manager';
 import { exit } from '@tauri-apps/plugin-process';
@@ -15,12 +16,22 @@ import { db } from './firebase';
 import { doc, setDoc, collection, serverTimestamp, increment } from "firebase/firestore";
 import './App.css';
 
+const formatBytes = (bytes) => {
+  if (bytes < 1024) return `${bytes} B`;
+  if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`;
+  if (bytes < 1024 * 1024 * 1024) return `${(bytes / 1024 / 1024).toFixed(1)} MB`;
+  return `${(bytes / 1024 / 1024 / 1024).toFixed(2)} GB`;
+};
+
 function App() {
   const [isConnected, setIsConnected] = useState(false);
   const [logs, setLogs] = useState([]);
   const [currentPort, setCurrentPort] = useState(8080);
   const [lanIp, setLanIp] = useState('127.0.0.1'); // ✅ LAN IP State
+  const [pacUrl, setPacUrl] = useState(null);
   const [showConnectionModal, setShowConnectionModal] = useState(false); // ✅ Modal State
+  const [lanAuth, setLanAuth] = useState(null);
+  const [lanClients, setLanClients] = useState([]);
   const [isProcessing, setIsProcessing] = useState(false);
   const [showLogs, setShowLogs] = useState(false);
   const [showSettings, setShowSettings] = useState(false);
@@ -90,14 +101,17 @@ function App() {
   // ✅ i18n: Reactive translations (config'den sonra olmalı!)
   const t = useMemo(() => getTranslations(config.language || 'tr'), [config.language]);
 
-  const childProcess = useRef(null);
   const logsEndRef = useRef(null);
-  const isRetrying = useRef(false);
-  
-  // ✅ Auto-reconnect mekanizması
+
+  // ✅ Motor durumu Rust tarafında tutuluyor (engine://state)
+  const engineState = useRef('idle');
+  const enginePort = useRef(8080);
   const retryCount = useRef(0);
-  const retryTimer = useRef(null);
-  const userIntentDisconnect = useRef(false);
+  const retryMax = useRef(5);
+  // ✅ Duraklatma: bitiş zamanı (ms) ve tooltip geri sayımı
+  const pauseUntil = useRef(0);
+  const pauseTimer = useRef(null);
+  const engineHandlers = useRef({});
   const lastTrackTime = useRef(0); // Telemetri zamanlayıcısı
 
   // Constants
@@ -110,6 +124,8 @@ function App() {
     opendns: '208.67.222.222'
   };
 
+  const INTEGRATION_KEYS = ['devGit', 'devNpm', 'devPip', 'devShellEnv', 'firefoxProxy'];
+
 
   const updateConfig = async (key, value) => {
     setConfig(prev => {
@@ -118,6 +134,16 @@ function App() {
       return newConfig;
     });
 
+    // Yeniden bağlanma politikası Rust tarafında (settings.json) tutuluyor
+    if (key === 'autoReconnect') {
+       syncReconnectSetting(value);
+    }
+
+    // Geliştirici araçlarının proxy ayarları da Rust tarafında uygulanıyor
+    if (INTEGRATION_KEYS.includes(key)) {
+       syncIntegrationSettings({ ...configRef.current, [key]: value });
+    }
+
     // Analytics Ayarı Senkronizasyonu
     if (key === 'analytics') {
        try {
@@ -135,6 +161,33 @@ function App() {
     }
   };
 
+  const syncReconnectSetting = async (enabled) => {
+    try {
+      const settings = await invoke('get_settings');
+      if (settings.reconnect.enabled === enabled) return;
+      settings.reconnect.enabled = enabled;
+      await invoke('update_settings', { settings });
+    } catch (e) {
+      console.error('Reconnect setting sync failed:', e);
+    }
+  };
+
+  const syncIntegrationSettings = async (cfg) => {
+    try {
+      await invoke('update_integrations', {
+        integrations: {
+          git: !!cfg.devGit,
+          npm: !!cfg.devNpm,
+          pip: !!cfg.devPip,
+          shell_env: !!cfg.devShellEnv,
+          firefox: !!cfg.firefoxProxy
+        }
+      });
+    } catch (e) {
+      console.error('Integration setting sync failed:', e);
+    }
+  };
+
   const trackConnectionSuccess = async () => {
       // Sadece analytics açıksa gönder
       if (!config.analytics) return;
@@ -179,6 +232,45 @@ function App() {
 
   const [copyStatus, setCopyStatus] = useState('idle'); // idle, success, error
 
+  // LAN kimlik bilgileri Rust tarafında (settings.json); modal açılınca okunur
+  const openConnectionModal = async () => {
+    setShowConnectionModal(true);
+    try {
+      setLanAuth(await invoke('get_lan_auth'));
+      setLanClients(await invoke('list_lan_clients'));
+    } catch (e) {
+      console.error('LAN auth load failed:', e);
+    }
+  };
+
+  const kickLanClient = async (address) => {
+    try {
+      setLanClients(await invoke('disconnect_lan_client', { address }));
+      addLog(t.logLanClientKicked(address), 'warn');
+    } catch (e) {
+      addLog(`${t.logLanClientFailed}: ${e}`, 'error');
+    }
+  };
+
+  // Boş bırakılırsa sınır kaldırılır
+  const setLanClientLimit = async (address, value) => {
+    const limit = value.trim() === '' ? null : Number(value);
+    try {
+      setLanClients(await invoke('set_lan_client_limit', { address, limit }));
+    } catch (e) {
+      addLog(`${t.logLanClientFailed}: ${e}`, 'error');
+    }
+  };
+
+  const rotateLanCredentials = async () => {
+    try {
+      setLanAuth(await invoke('rotate_lan_credentials'));
+      addLog(t.logLanCredentialsRotated, 'success');
+    } catch (e) {
+      addLog(`${t.logLanCredentialsRotateFailed}: ${e}`, 'error');
+    }
+  };
+
   const copyLogs = async () => {
     if (logs.length === 0) return;
     
@@ -218,14 +310,8 @@ function App() {
     }
   };
 
-  // ✅ Exponential backoff hesaplama
-  const getRetryDelay = (attempt) => {
-    const delays = [0, 3000, 6000, 12000, 20000]; // 0s, 3s, 6s, 12s, 20s
-    return delays[Math.min(attempt, delays.length - 1)];
-  };
-
   // ✅ Tray tooltip güncelle
-  const updateTrayTooltip = async (status) => {
+  const updateTrayTooltip = async (status, address = `127.0.0.1:${enginePort.current}`) => {
     try {
       let tooltip = '';
       switch (status) {
@@ -233,13 +319,22 @@ function App() {
           const dnsName = DNS_MAP[config.selectedDns] 
             ? Object.keys(DNS_MAP).find(key => DNS_MAP[key] === DNS_MAP[config.selectedDns])?.toUpperCase()
             : 'SYSTEM';
-          tooltip = `🟢 Vexar - ${t.statusConnected}\n127.0.0.1:${currentPort}\nDNS: ${dnsName}`;
+          tooltip = `🟢 Vexar - ${t.statusConnected}\n${address}\nDNS: ${dnsName}`;
           break;
         case 'disconnected':
           tooltip = `⚪ Vexar - ${t.statusInactive}`;
           break;
+        case 'degraded':
+          tooltip = `🟠 Vexar - ${t.statusDegraded}`;
+          break;
+        case 'paused': {
+          const left = Math.max(0, Math.round((pauseUntil.current - Date.now()) / 1000));
+          const mmss = `${Math.floor(left / 60)}:${String(left % 60).padStart(2, '0')}`;
+          tooltip = `⏸️ Vexar - ${t.statusPaused}\n${t.pauseRemaining(mmss)}`;
+          break;
+        }
         case 'retrying':
-          tooltip = `🟡 Vexar - ${t.btnConnecting}\n${retryCount.current}/5...`;
+          tooltip = `🟡 Vexar - ${t.btnConnecting}\n${retryCount.current}/${retryMax.current}...`;
           break;
         case 'connecting':
           tooltip = `🔵 Vexar - ${t.btnConnecting}`;
@@ -253,366 +348,309 @@ function App() {
     }
   };
 
-  // ✅ Otomatik yeniden bağlanma
-  const attemptReconnect = () => {
-    // Timer varsa temizle
-    if (retryTimer.current) {
-      clearTimeout(retryTimer.current);
-      retryTimer.current = null;
+  // ✅ Motor ayarları (argv Rust tarafında doğrulanıp oluşturuluyor - EngineConfig)
+  const buildEngineConfig = () => ({
+    listen_addr: configRef.current.lanSharing ? '0.0.0.0' : '127.0.0.1',
+    dns_addr: DNS_MAP[configRef.current.selectedDns] || null,
+    window_size: Number(configRef.current.dpiMethod || '1'),
+    enable_doh: true,
+    timeout_ms: 5000,
+    // Bölünmüş tünel: yalnızca listedeki alan adları motordan geçer (PAC)
+    proxy_mode: configRef.current.splitTunnel ? 'pac' : 'global',
+    // SOCKS köprüsü açıksa sistem proxy'sine socks= girdisi de yazılır
+    socks: !!configRef.current.socksProxy,
+    scheme_layout: configRef.current.socksProxy ? 'web_socks' : 'single'
+  });
+
+  const buildRemoteConfig = () => ({
+    address: (configRef.current.remoteAddress || '').trim(),
+    proxy_mode: configRef.current.splitTunnel ? 'pac' : 'global'
+  });
+
+  // Optimized regex pattern - compiled once
+  const SKIP_PATTERN = /\[(?:PROXY|DNS|HTTPS|CACHE)\]|method:\s*CONNECT|cache (?:miss|hit)|resolving|routing|resolution took|new conn|client sent hello|shouldExploit|useSystemDns|fragmentation|conn established|writing chunked|caching \d+ records|[a-f0-9]{8}-[a-f0-9]{8}|d88|Y88|88P|level=|ctrl \+ c|listen_addr|dns_addr|github\.com|spoofdpi/i;
+
+  // engine://log satırlarını kullanıcı dostu loglara çevir
+  const handleEngineLog = ({ line, stream }) => {
+    const trimmedLine = line.trim();
+    const lowerLine = line.toLowerCase();
+
+    if (trimmedLine.length === 0) return;
+    if (/^(DBG|INF|WRN|ERR)\s+\d{4}-/.test(trimmedLine)) return;
+    if (line.includes('888')) return;
+
+    if (SKIP_PATTERN.test(line)) return;
+
+    // Optimized alpha check
+    const alphaCount = line.replace(/[^a-zA-ZğüşıöçĞÜŞİÖÇ]/g, '').length;
+    if (alphaCount < 5 && trimmedLine.length > 3) return;
+
+    let friendlyMsg = null;
+
+    if (lowerLine.includes('listening on') || lowerLine.includes('created a listener')) {
+      friendlyMsg = `✓ SpoofDPI Motoru başlatıldı (Port: ${enginePort.current})`;
+    } else if (lowerLine.includes('server started')) {
+      friendlyMsg = "✓ Vexar motoru aktif";
+    } else if (lowerLine.includes('bind') || lowerLine.includes('usage') || lowerLine.includes('yuva adresi')) {
+      friendlyMsg = `⚠ Port ${enginePort.current} dolu, başka port deneniyor...`;
+    } else if (lowerLine.includes('initializing')) {
+      friendlyMsg = `⏳ Motor başlatılıyor...`;
     }
 
-    const currentAttempt = retryCount.current;
-    const maxAttempts = 5;
-
-    if (currentAttempt >= maxAttempts) {
-      // Maksimum deneme aşıldı
-      addLog(`❌ ${t.logMaxRetries}`, 'error');
-      addLog('', 'info');
-      addLog(`📋 ${t.logPossibleReasons}`, 'warn');
-      addLog(`  • ${t.logReasonInternet}`, 'info');
-      addLog(`  • ${t.logReasonFirewall}`, 'info');
-      addLog(`  • ${t.logReasonPorts}`, 'info');
-      addLog('', 'info');
-      addLog(`💡 ${t.logSolutions}`, 'warn');
-      addLog(`  • ${t.logSolInternet}`, 'info');
-      addLog(`  • ${t.logSolFirewall}`, 'info');
-      addLog(`  • ${t.logSolAdmin}`, 'info');
-      addLog(`  • ${t.logSolLogs}`, 'info');
-      
-      retryCount.current = 0;
-      setIsProcessing(false);
-      return;
+    if (friendlyMsg) {
+      addLog(friendlyMsg, stream === 'stderr' ? 'warn' : 'success');
     }
+  };
 
-    const delay = getRetryDelay(currentAttempt);
-    retryCount.current++;
+  const logRetryHelp = (tr) => {
+    addLog(`❌ ${tr.logMaxRetries}`, 'error');
+    addLog('', 'info');
+    addLog(`📋 ${tr.logPossibleReasons}`, 'warn');
+    addLog(`  • ${tr.logReasonInternet}`, 'info');
+    addLog(`  • ${tr.logReasonFirewall}`, 'info');
+    addLog(`  • ${tr.logReasonPorts}`, 'info');
+    addLog('', 'info');
+    addLog(`💡 ${tr.logSolutions}`, 'warn');
+    addLog(`  • ${tr.logSolInternet}`, 'info');
+    addLog(`  • ${tr.logSolFirewall}`, 'info');
+    addLog(`  • ${tr.logSolAdmin}`, 'info');
+    addLog(`  • ${tr.logSolLogs}`, 'info');
+  };
 
-    if (delay === 0) {
-      addLog(`🔄 ${t.logReconnecting(currentAttempt + 1)}`, 'warn');
-      startEngine(8080);
-    } else {
-      addLog(`⏳ ${t.logReconnectWait(delay / 1000, currentAttempt + 1)}`, 'warn');
-      updateTrayTooltip('retrying');
-      retryTimer.current = setTimeout(() => {
-        addLog(`🔄 ${t.logReconnectNow}`, 'info');
-        startEngine(8080);
-      }, delay);
+  // ✅ Rust'tan gelen motor durumunu arayüze yansıt
+  const applyEngineStatus = (status) => {
+    const tr = getTranslations(configRef.current.language || 'tr');
+    const kind = status.state.kind;
+    const prev = engineState.current;
+    engineState.current = kind;
+
+    if (kind !== 'paused' && pauseTimer.current) {
+      clearInterval(pauseTimer.current);
+      pauseTimer.current = null;
     }
-  };
 
-  // Wait for port to be ready
-  const waitForPort = async (port, maxAttempts = 10) => {
-    for (let i = 0; i < maxAttempts; i++) {
-      try {
-        await fetch(`http://127.0.0.1:${port}`, {
-          method: 'HEAD',
-          signal: AbortSignal.timeout(500)
-        });
-        return true;
-      } catch {
-        await new Promise(r => setTimeout(r, 150)); // ✅ 300ms -> 150ms (Daha sık kontrol)
+    if (status.port) {
+      enginePort.current = status.port;
+      setCurrentPort(status.port);
+    }
+    if (status.lan_ip) setLanIp(status.lan_ip);
+    // Sistem proxy'sinin gösterdiği adres: uzak Vexar veya yerel motor
+    const address = status.remote || `127.0.0.1:${status.port}`;
+    // Paylaşım kapalıyken veya motor dururken adres yok
+    setPacUrl(status.pac_url || null);
+
+    switch (kind) {
+      case 'starting':
+        setIsProcessing(true);
+        if (prev !== 'starting') {
+          addLog(status.remote
+            ? tr.logRemoteConnecting(status.remote)
+            : tr.logEngineStarting(status.port), 'info');
+        }
+        updateTrayTooltip('connecting');
+        break;
+      case 'ready':
+        setIsConnected(true);
+        setIsProcessing(false);
+        if (prev === 'degraded') {
+          updateTrayTooltip('connected', address);
+        } else if (prev === 'paused') {
+          addLog(`▶️ ${tr.logProxyResumed}`, 'success');
+          updateTrayTooltip('connected', address);
+        } else if (prev !== 'ready') {
+          retryCount.current = 0;
+          addLog(tr.logProxySet(address), 'success');
+          addLog(tr.logConnected, 'success');
+          updateTrayTooltip('connected', address);
+          trackConnectionSuccess(); // Telemetri gönder
+        }
+        break;
+      case 'degraded':
+        // Motor çalışıyor ama yanıt vermiyor: proxy kapalı, bağlantı butonu durdurmaya devam eder
+        setIsConnected(true);
+        setIsProcessing(false);
+        updateTrayTooltip('degraded');
+        break;
+      case 'paused':
+        // Motor çalışıyor, proxy geçici olarak kapalı; kalan süre tepside sayılır
+        setIsConnected(true);
+        setIsProcessing(false);
+        pauseUntil.current = status.state.resume_at_ms;
+        if (prev !== 'paused') {
+          const minutes = Math.max(1, Math.round((pauseUntil.current - Date.now()) / 60000));
+          addLog(`⏸️ ${tr.logProxyPaused(minutes)}`, 'warn');
+        }
+        updateTrayTooltip('paused');
+        if (!pauseTimer.current) {
+          pauseTimer.current = setInterval(() => updateTrayTooltip('paused'), 1000);
+        }
+        break;
+      case 'reconnecting': {
+        const { attempt, max_attempts, delay_ms } = status.state;
+        retryCount.current = attempt;
+        retryMax.current = max_attempts;
+        setIsConnected(false);
+        setIsProcessing(true);
+        if (prev === 'ready' || prev === 'degraded') {
+          addLog(`🔄 ${tr.logAutoReconnect}`, 'info');
+        }
+        if (delay_ms > 0) {
+          addLog(`⏳ ${tr.logReconnectWait(delay_ms / 1000, attempt, max_attempts)}`, 'warn');
+        } else {
+          addLog(`🔄 ${tr.logReconnecting(attempt, max_attempts)}`, 'warn');
+        }
+        updateTrayTooltip('retrying');
+        break;
       }
+      case 'failed':
+        setIsConnected(false);
+        setIsProcessing(false);
+        addLog(`⚠️ ${status.state.reason}`, 'error');
+        if (retryCount.current > 0) {
+          logRetryHelp(tr);
+          retryCount.current = 0;
+        }
+        updateTrayTooltip('disconnected');
+        break;
+      case 'stopping':
+        setIsProcessing(true);
+        break;
+      default:
+        setIsConnected(false);
+        setIsProcessing(false);
+        if (prev !== 'idle') {
+          addLog(tr.logProxyCleared, 'success');
+        }
+        updateTrayTooltip('disconnected');
     }
-    return false;
   };
 
-  const startEngine = async (ignoredPort, portRetryCount = 0) => {
-    updateTrayTooltip('connecting'); 
-    
-    // Max 20 retries
-    if (portRetryCount >= 20) {
-      addLog(t.logNoPort, 'error');
-      setIsProcessing(false);
-      return;
+  // ✅ Hazırlık kontrolü (CONNECT probe) sonucu
+  const handleReadiness = (report) => {
+    if (report.ready) {
+      addLog(`✓ Motor doğrulandı: ${report.target} (${report.elapsed_ms} ms)`, 'success');
     }
+  };
 
-    // ✅ Rust'tan Smart Configuration al (Port & IP)
-    let configData;
-    let port;
-    let bindAddr;
-    
-    try {
-        configData = await invoke('get_sidecar_config', { 
-            allowLanSharing: configRef.current.lanSharing || false 
-        });
-        port = configData.port;
-        bindAddr = configData.bind_address;
-        setLanIp(configData.lan_ip); // IP'yi state'e kaydet
-    } catch (e) {
-        addLog(t.logConfigError(e), 'error');
-        setIsProcessing(false);
-        return;
-    }
-    
-    if (childProcess.current) return;
-    await clearProxy(true);
+  // ✅ Çökme döngüsü: motor kısa sürede çok kez kapandı, yeniden deneme durduruldu
+  const handleBreaker = (trip) => {
+    addLog(`⛔ ${t.logBreakerTripped(trip.crashes, trip.window_secs)}`, 'error');
+  };
 
-    const dnsIP = DNS_MAP[config.selectedDns];
-    
-    addLog(t.logEngineStarting(port), 'info');
-    
-    // DNS bilgisi
-    if (dnsIP) {
-      addLog(t.logDnsUsed(config.selectedDns.toUpperCase(), dnsIP), 'info');
+  // ✅ Heartbeat: motor kilitlenince proxy kapatılır, toparlanınca geri açılır
+  const handleHeartbeat = (report) => {
+    if (report.healthy) {
+      addLog(`✓ ${t.logHeartbeatRecovered}`, 'success');
     } else {
-      addLog(t.logDnsDefault, 'info');
+      addLog(`⚠️ ${t.logHeartbeatFailOpen(report.failures)}`, 'warn');
+      if (report.reason) addLog(report.reason, 'warn');
     }
-    
-    isRetrying.current = false;
+  };
 
-    try {
-      // Base arguments
-      const args = [
-          '-listen-port', port.toString(),
-          '-listen-addr', bindAddr // ✅ Flag updated to match binary
-      ];
-      
-      // ✅ Sadece DNS seçiliyse ekle
-      if (dnsIP) {
-        args.push('-dns-addr', dnsIP);
-      }
-      
-      // Diğer parametreler
-      args.push(
-        '-window-size', configRef.current.dpiMethod || '1', 
-        '-enable-doh',            
-        '-timeout', '5000'        
-      );
-      
-      const command = Command.sidecar('binaries/vexar-proxy', args);
+  // Başka bir araç (VPN, kurumsal ajan) sistem proxy'sini değiştirdi
+  const handleDrift = ({ action, changes }) => {
+    const fields = changes.map(c => c.field).join(', ');
+    addLog(`⚠️ ${t.logProxyDrift(fields)}`, 'warn');
+    if (action === 'reassert') addLog(t.logProxyDriftReasserted, 'info');
+    else if (action === 'disconnect') addLog(t.logProxyDriftDisconnected, 'warn');
+  };
 
-      
-      let connectionConfirmed = false;
-      let isReady = false;
+  // Sistem proxy'si açık kalır; yalnızca araç dosyası yazılamadı
+  const handleIntegrationsFailed = (reason) => {
+    addLog(`⚠️ ${t.logIntegrationsFailed}: ${reason}`, 'warn');
+  };
 
-      // Optimized regex pattern - compiled once
-      const SKIP_PATTERN = /\[(?:PROXY|DNS|HTTPS|CACHE)\]|method:\s*CONNECT|cache (?:miss|hit)|resolving|routing|resolution took|new conn|client sent hello|shouldExploit|useSystemDns|fragmentation|conn established|writing chunked|caching \d+ records|[a-f0-9]{8}-[a-f0-9]{8}|d88|Y88|88P|level=|ctrl \+ c|listen_addr|dns_addr|github\.com|spoofdpi/i;
+  // Tepsi menüsünden proxy ile başlatılan uygulamalar
+  const handleLaunched = (report) => {
+    if (report.already_running) addLog(`⚠️ ${t.launcherAlreadyRunning(report.name)}`, 'warn');
+    else addLog(t.launcherStarted(report.name), 'success');
+  };
 
-      const handleOutput = async (line, type) => {
-        const trimmedLine = line.trim();
-        const lowerLine = line.toLowerCase();
-        
-        if (trimmedLine.length === 0) return;
-        if (/^(DBG|INF|WRN|ERR)\s+\d{4}-/.test(trimmedLine)) return;
-        if (line.includes('888')) return;
+  const handleLaunchFailed = (reason) => {
+    addLog(`${t.logLaunchFailed}: ${reason}`, 'error');
+  };
 
-        if (SKIP_PATTERN.test(line)) return;
+  const handleLanRejected = ({ address }) => {
+    addLog(t.logLanRejected(address), 'warn');
+  };
 
-        // Optimized alpha check
-        const alphaCount = line.replace(/[^a-zA-ZğüşıöçĞÜŞİÖÇ]/g, '').length;
-        if (alphaCount < 5 && trimmedLine.length > 3) return;
-        
-        let friendlyMsg = null;
-        
-        if (lowerLine.includes('listening on') || lowerLine.includes('created a listener')) {
-          isReady = true;
-          friendlyMsg = `✓ SpoofDPI Motoru başlatıldı (Port: ${port})`;
-        } else if (lowerLine.includes('server started')) {
-          isReady = true;
-          friendlyMsg = "✓ Vexar motoru aktif";
-        } else if (lowerLine.includes('bind') || lowerLine.includes('usage') || lowerLine.includes('yuva adresi')) {
-          friendlyMsg = `⚠ Port ${port} dolu, başka port deneniyor...`;
-        } else if (lowerLine.includes('initializing')) {
-          friendlyMsg = `⏳ Motor başlatılıyor...`;
-        }
-        
-        if (friendlyMsg) {
-          addLog(friendlyMsg, type === 'warn' ? 'warn' : 'success');
-        }
-        
-        // Wait for port to be actually ready
-        if (!connectionConfirmed && isReady) {
-          connectionConfirmed = true;
-          
-          const portReady = await waitForPort(port);
-          if (!portReady) {
-            addLog(`Port ${port} açılamadı, yeniden deneniyor...`, 'warn');
-            return;
-          }
-          
-          setCurrentPort(port);
-          try {
-            await invoke('set_system_proxy', { port });
-            addLog(t.logProxySet(port), 'success');
-          } catch (err) {
-            addLog(`Proxy ayarlanamadı: ${err}`, 'error');
-            return;
-          }
-          
-          // ✅ Başarılı bağlantı - retry mekanizmasını sıfırla
-          retryCount.current = 0;
-          userIntentDisconnect.current = false;
-          
-          setIsConnected(true);
-          setIsProcessing(false);
-          addLog(t.logConnected, 'success');
-          updateTrayTooltip('connected'); 
-          trackConnectionSuccess(); // Telemetri gönder
-        }
+  const handleLanClients = (clients) => {
+    setLanClients(clients);
+  };
 
-        const isPortError = lowerLine.includes('bind') || 
-                            lowerLine.includes('usage') || 
-                            lowerLine.includes('listener') || 
-                            lowerLine.includes('kullanıma izin veriliyor'); 
-
-        if (isPortError && (lowerLine.includes('error') || lowerLine.includes('fail') || lowerLine.includes('ftl')) && !isRetrying.current) {
-          isRetrying.current = true;
-          
-          if (childProcess.current) {
-             childProcess.current.kill().catch(() => {});
-             childProcess.current = null;
-          }
-          
-          setTimeout(() => {
-            // Smart Retry: Port increment yerine Rust'ın yeni port bulmasına güveniyoruz
-            // Ama yine de recursion için count artırıyoruz
-            startEngine(0, portRetryCount + 1); 
-          }, 1000); 
-        }
-      };
+  engineHandlers.current = {
+    applyEngineStatus,
+    handleEngineLog,
+    handleReadiness,
+    handleBreaker,
+    handleHeartbeat,
+    handleDrift,
+    handleIntegrationsFailed,
+    handleLaunched,
+    handleLaunchFailed,
+    handleLanRejected,
+    handleLanClients
+  };
 
-      command.on('close', data => {
-        if (!isRetrying.current) {
-          const wasConnected = isConnected;
-          const isUnexpectedClose = data.code !== 0 && data.code !== null;
-          
-          // ✅ ÖNCE user intent kontrol et
-          if (userIntentDisconnect.current) {
-            // Kullanıcı kasıtlı kapattı - normal mesaj göster
-            addLog('Vexar motoru kapatıldı.', 'info');
-            setIsConnected(false);
-            setIsProcessing(false);
-            childProcess.current = null;
-            clearProxy(true).catch(console.error);
-            
-            // Reset flags
-            retryCount.current = 0;
-            userIntentDisconnect.current = false;
-            return; // Erken çık, retry yapma
-          }
-          
-          // Kullanıcı kasıtlı kapatmadı - beklenmedik kapanma
-          if (isUnexpectedClose) {
-              addLog(`⚠️ ${t.logEngineStopped(data.code)}`, 'warn');
-          } else {
-              addLog('Vexar motoru kapatıldı.', 'info');
-          }
-          
-          // ✅ childProcess null yapılmadan önce backup al
-          const hadActiveProcess = childProcess.current !== null;
-          
-          setIsConnected(false);
-          setIsProcessing(false);
-          childProcess.current = null;
-          clearProxy(true).catch(console.error);
-          updateTrayTooltip('disconnected'); // ✅ Bağlantı koptu (geçici)
-          
-          // ✅ Otomatik yeniden bağlanma kontrol
-          const autoReconnectEnabled = configRef.current.autoReconnect !== false; // undefined veya true ise açık
-          
-          const shouldReconnect = 
-            autoReconnectEnabled &&               // Ayarda açık mı?
-            !userIntentDisconnect.current &&      // Kullanıcı kasıtlı kapatmadı mı?
-            hadActiveProcess;                     // Process çalışıyor muydu?
-          
-          if (shouldReconnect) {
-            addLog(`🔄 ${t.logAutoReconnect}`, 'info');
-            setIsProcessing(true);
-            attemptReconnect();
-          }
-        }
-      });
+  const startEngine = async () => {
+    const dnsIP = DNS_MAP[configRef.current.selectedDns];
 
-      command.stderr.on('data', line => handleOutput(line, 'warn'));
-      command.stdout.on('data', line => handleOutput(line, 'info'));
-      
-      const child = await command.spawn();
-      childProcess.current = child;
-
-      // Failsafe timeout
-      setTimeout(async () => {
-        if (childProcess.current && !connectionConfirmed && !isRetrying.current) {
-             connectionConfirmed = true;
-             setCurrentPort(port);
-
-             try {
-                await invoke('set_system_proxy', { port: port });
-                addLog(t.logProxySet(port), 'success');
-             } catch (err) {
-                addLog(`Proxy ayarlanamadı: ${err}`, 'error');
-             }
-
-             // ✅ Başarılı bağlantı - retry mekanizmasını sıfırla
-             retryCount.current = 0;
-             userIntentDisconnect.current = false;
-
-             setIsConnected(true);
-             setIsProcessing(false);
-             addLog(t.logConnected, 'info');
-             trackConnectionSuccess(); // Telemetri gönder
-             updateTrayTooltip('connected'); // ✅ Auto-connect başarılı
-        }
-      }, 2000); // ✅ 3000ms -> 2000ms (Fail-safe timeout azaltıldı)
+    // DNS bilgisi (uzak Vexar kendi DNS ayarını kullanır)
+    if (!configRef.current.remoteMode) {
+      if (dnsIP) {
+        addLog(t.logDnsUsed(configRef.current.selectedDns.toUpperCase(), dnsIP), 'info');
+      } else {
+        addLog(t.logDnsDefault, 'info');
+      }
+    }
 
+    try {
+      // İstemci modu: yerel motor yok, sistem proxy'si ağdaki başka bir Vexar'a yönlenir
+      const status = configRef.current.remoteMode
+        ? await invoke('connect_remote', { config: buildRemoteConfig() })
+        : await invoke('start_engine', { config: buildEngineConfig() });
+      applyEngineStatus(status);
     } catch (e) {
       addLog(t.logEngineStartError(e), 'error');
       setIsConnected(false);
       setIsProcessing(false);
-      clearProxy();
+    }
+  };
+
+  const stopEngine = async () => {
+    try {
+      await invoke('stop_engine');
+    } catch (e) {
+      addLog(`Servis durdurma hatası: ${e}`, 'error');
     }
   };
 
   const toggleConnection = async () => {
     if (isProcessing) return;
 
+    setIsProcessing(true);
     if (isConnected) {
-      // ✅ Kullanıcı kasıtlı olarak bağlantıyı kesiyor
-      userIntentDisconnect.current = true;
-      
-      // Retry timer varsa iptal et
-      if (retryTimer.current) {
-        clearTimeout(retryTimer.current);
-        retryTimer.current = null;
-      }
-      
-      setIsProcessing(true);
-      if (childProcess.current) {
-        try {
-          addLog(t.logDisconnected, 'warn');
-          await childProcess.current.kill();
-        } catch (e) {
-          addLog(`Servis durdurma hatası: ${e}`, 'error');
-        }
-        childProcess.current = null;
-      }
-      setIsConnected(false);
-      await clearProxy(); 
+      addLog(t.logDisconnected, 'warn');
+      await stopEngine();
       addLog('Servis Durduruldu', 'success');
-      setIsProcessing(false);
-      updateTrayTooltip('disconnected'); // ✅ Manuel durdurma
     } else {
       // ✅ Kullanıcı manuel bağlanıyor - retry counter sıfırla
       retryCount.current = 0;
-      userIntentDisconnect.current = false;
-      
-      setIsProcessing(true);
-      startEngine(8080);
+      await startEngine();
     }
   };
 
   useEffect(() => {
     logsEndRef.current?.scrollIntoView({ behavior: 'smooth' });
   }, [logs]);
-
   // ✅ LAN Sharing Değişince Restart (Side-Effect)
   useEffect(() => {
       if (config.lanSharing !== configRef.current.lanSharing) {
            if (isConnected) {
                addLog(t.logLanRestart, 'warn');
-               childProcess.current?.kill().catch(() => {});
-               childProcess.current = null;
-               setIsConnected(false);
-               setTimeout(() => startEngine(0), 1500); // 1.5s bekle (Portun boşa çıkması için)
+               stopEngine().finally(() => {
+                   setTimeout(() => startEngine(), 1500); // 1.5s bekle (Portun boşa çıkması için)
+               });
            }
       }
   }, [config.lanSharing]);
@@ -624,13 +662,46 @@ function App() {
   }, [config]);
 
   useEffect(() => {
-    // Initial cleanup on mount
+    // ✅ Motor Rust tarafında yaşıyor: webview yeniden yüklense de durumu oradan al
+    const unlisteners = [];
+    listen('engine://state', event => engineHandlers.current.applyEngineStatus(event.payload))
+      .then(fn => unlisteners.push(fn));
+    listen('engine://log', event => engineHandlers.current.handleEngineLog(event.payload))
+      .then(fn => unlisteners.push(fn));
+    listen('engine://ready', event => engineHandlers.current.handleReadiness(event.payload))
+      .then(fn => unlisteners.push(fn));
+    listen('engine://breaker', event => engineHandlers.current.handleBreaker(event.payload))
+      .then(fn => unlisteners.push(fn));
+    listen('engine://heartbeat', event => engineHandlers.current.handleHeartbeat(event.payload))
+      .then(fn => unlisteners.push(fn));
+    listen('engine://drift', event => engineHandlers.current.handleDrift(event.payload))
+      .then(fn => unlisteners.push(fn));
+    listen('integrations://failed', event => engineHandlers.current.handleIntegrationsFailed(event.payload))
+      .then(fn => unlisteners.push(fn));
+    listen('launcher://launched', event => engineHandlers.current.handleLaunched(event.payload))
+      .then(fn => unlisteners.push(fn));
+    listen('launcher://failed', event => engineHandlers.current.handleLaunchFailed(event.payload))
+      .then(fn => unlisteners.push(fn));
+    listen('lan://rejected', event => engineHandlers.current.handleLanRejected(event.payload))
+      .then(fn => unlisteners.push(fn));
+    listen('lan://clients', event => engineHandlers.current.handleLanClients(event.payload))
+      .then(fn => unlisteners.push(fn));
+    syncReconnectSetting(configRef.current.autoReconnect !== false);
+    syncIntegrationSettings(configRef.current);
+    // ✅ Sürüm yükseltmesi: izin listesinden önce paylaşım açıksa yerel alt ağ bir kez eklenir
+    if (!configRef.current.lanAclSeeded) {
+      (configRef.current.lanSharing ? invoke('seed_lan_acl') : Promise.resolve())
+        .then(() => updateConfig('lanAclSeeded', true))
+        .catch(e => console.error('LAN ACL migration failed:', e));
+    }
+
     (async () => {
       try {
-        await clearProxy(true);
-        updateTrayTooltip('disconnected');
+        // Çökme sonrası kalan proxy açılışta Rust tarafında (proxy kaydı) geri yükleniyor
+        const status = await invoke('engine_status');
+        engineHandlers.current.applyEngineStatus(status);
       } catch (e) {
-        console.error('Initial proxy cleanup failed:', e);
+        console.error('Initial engine sync failed:', e);
       }
     })();
     
@@ -649,17 +720,8 @@ function App() {
           return;
         }
 
-        // ✅ Timer'ı temizle
-        if (retryTimer.current) {
-          clearTimeout(retryTimer.current);
-          retryTimer.current = null;
-        }
-
         try {
-          if (childProcess.current) {
-            await childProcess.current.kill().catch(() => {}); 
-          }
-          await clearProxy(true);
+          await invoke('stop_engine');
         } catch (e) {
           console.error('Cleanup failed:', e);
         }
@@ -673,49 +735,16 @@ function App() {
 
     return () => {
       if (unlistenFn) unlistenFn();
-      
-      // ✅ Retry timer'ı temizle
-      if (retryTimer.current) {
-        clearTimeout(retryTimer.current);
-        retryTimer.current = null;
-      }
-      
-      // Cleanup on unmount
-      const cleanup = async () => {
-        if (childProcess.current) {
-          try {
-            await childProcess.current.kill();
-            childProcess.current = null;
-          } catch (e) {
-            console.error('Process kill failed:', e);
-          }
-        }
-        try {
-          await invoke('clear_system_proxy');
-        } catch (e) {
-          console.error('Proxy cleanup failed:', e);
-        }
-      };
-      
-      cleanup();
+      unlisteners.forEach(fn => fn());
     };
   }, []);
 
  const handleExit = async () => {
     addLog('Kapatma başlatılıyor...', 'warn');
     
-    // ✅ Timer'ı temizle
-    if (retryTimer.current) {
-      clearTimeout(retryTimer.current);
-      retryTimer.current = null;
-    }
-    
     try {
-      if (childProcess.current) {
-        await childProcess.current.kill().catch(() => {}); 
-        addLog('İşlem sonlandırıldı', 'success');
-      }
-      await clearProxy(true);
+      await invoke('stop_engine');
+      addLog('İşlem sonlandırıldı', 'success');
       
       await getCurrentWindow().close().catch(() => {});
       await exit(0);
@@ -793,12 +822,14 @@ function App() {
     const shouldAutoConnect = configRef.current.autoConnect;
     let isMounted = true;
     
-    if (shouldAutoConnect && !childProcess.current) {
-      const timeoutId = setTimeout(() => {
-        if (!childProcess.current && isMounted) {
-          setIsProcessing(true);
-          startEngine(8080);
-        }
+    if (shouldAutoConnect) {
+      const timeoutId = setTimeout(async () => {
+        if (!isMounted) return;
+        // Webview yeniden yüklendiyse motor zaten çalışıyor olabilir
+        const status = await invoke('engine_status').catch(() => null);
+        if (status && !['idle', 'failed'].includes(status.state.kind)) return;
+        setIsProcessing(true);
+        startEngine();
       }, 300); // ✅ 1000ms -> 300ms (Uygulama açılışında daha hızlı bağlan)
       
       return () => {
@@ -1070,13 +1101,13 @@ function App() {
       <div className="action-area">
         {/* LAN Connect Button */}
         <AnimatePresence>
-            {config.lanSharing && isConnected && (
+            {config.lanSharing && !config.remoteMode && isConnected && (
                 <motion.button 
                     initial={{ opacity: 0, y: 10, height: 0 }}
                     animate={{ opacity: 1, y: 0, height: 'auto', marginBottom: '1rem' }}
                     exit={{ opacity: 0, y: 10, height: 0, marginBottom: 0 }}
                     className="lan-connect-pill-btn"
-                    onClick={() => setShowConnectionModal(true)}
+                    onClick={openConnectionModal}
                 >
                     <Smartphone size={16} />
                     <span>{t.btnConnectDevices}</span>
@@ -1192,10 +1223,24 @@ function App() {
                     
                     <div className="modal-body">
                         <p className="modal-desc">
-                            <span dangerouslySetInnerHTML={{ __html: t.modalDesc }} />
+                            <span dangerouslySetInnerHTML={{ __html: pacUrl ? t.modalPacDesc : t.modalDesc }} />
                         </p>
                         
-                        <div className="info-row">
+                        {pacUrl ? (
+                          <>
+                            <div className="info-row">
+                              <div className="info-group">
+                                <label>{t.modalPacUrl}</label>
+                                <div className="code-box" onClick={() => writeText(pacUrl)}>
+                                  <span>{pacUrl}</span>
+                                  <Copy size={16} />
+                                </div>
+                              </div>
+                            </div>
+                            <p className="modal-desc">{t.modalManual(lanIp, currentPort)}</p>
+                          </>
+                        ) : (
+                          <div className="info-row">
                             <div className="info-group">
                                 <label>{t.modalHost}</label>
                                 <div className="code-box" onClick={() => writeText(lanIp)}>
@@ -1210,6 +1255,65 @@ function App() {
                                     <Copy size={16} />
                                 </div>
                             </div>
+                          </div>
+                        )}
+
+                        {lanAuth?.enabled && lanAuth.credentials && (
+                          <div className="info-row">
+                            <div className="info-group">
+                              <label>{t.modalUsername}</label>
+                              <div className="code-box" onClick={() => writeText(lanAuth.credentials.username)}>
+                                <span>{lanAuth.credentials.username}</span>
+                                <Copy size={16} />
+                              </div>
+                            </div>
+                            <div className="info-group">
+                              <label>{t.modalPassword}</label>
+                              <div className="code-box" onClick={() => writeText(lanAuth.credentials.password)}>
+                                <span>{lanAuth.credentials.password}</span>
+                                <Copy size={16} />
+                              </div>
+                            </div>
+                            <button className="close-btn" title={t.modalRotate} onClick={rotateLanCredentials}>
+                              <RotateCw size={18} />
+                            </button>
+                          </div>
+                        )}
+
+                        <div className="info-row">
+                          <div className="info-group">
+                            <label>{t.modalClients}</label>
+                            {lanClients.length === 0 && <p className="modal-desc">{t.modalNoClients}</p>}
+                            {lanClients.map((client) => (
+                              <div key={client.address} className="lan-client">
+                                <div className="lan-client-info">
+                                  <span>{client.address}</span>
+                                  <small>
+                                    {t.modalClientTunnels(client.active_tunnels, client.tunnel_limit)}
+                                    {` · ↑ ${formatBytes(client.bytes_up)} ↓ ${formatBytes(client.bytes_down)}`}
+                                    {client.blocked ? ` · ${t.modalClientBlocked}` : ''}
+                                  </small>
+                                </div>
+                                <input
+                                  key={`${client.address}-${client.tunnel_limit ?? ''}`}
+                                  className="lan-client-limit"
+                                  type="number"
+                                  min="1"
+                                  defaultValue={client.tunnel_limit ?? ''}
+                                  placeholder={t.modalClientLimit}
+                                  title={t.modalClientLimit}
+                                  onBlur={(e) => {
+                                    if (e.target.value !== String(client.tunnel_limit ?? '')) {
+                                      setLanClientLimit(client.address, e.target.value);
+                                    }
+                                  }}
+                                />
+                                <button className="close-btn" title={t.modalClientKick} onClick={() => kickLanClient(client.address)}>
+                                  <UserX size={16} />
+                                </button>
+                              </div>
+                            ))}
+                          </div>
                         </div>
 
                         <button className="tutorial-btn" onClick={() => open('https://vexar-official-website.vercel.app/proxy')}> 
@@ -2,7 +2,8 @@
 import { motion, AnimatePresence } from 'framer-motion';
 import { 
   ChevronLeft, Globe, Power, Zap, RotateCw, Activity, 
-  Shield, Youtube, Coffee, AlertTriangle, Check, Wrench, Languages
+  Shield, Youtube, Coffee, AlertTriangle, Check, Wrench, Languages, Split, Server, Search, Network,
+  GitBranch, Package, Terminal, Flame, Rocket, Plus, Trash2
 } from 'lucide-react';
 import { enable, disable, isEnabled } from '@tauri-apps/plugin-autostart';
 import { open, Command } from '@tauri-apps/plugin-shell';
@@ -22,12 +23,30 @@ const Toggle = ({ checked, onChange }) => (
   </div>
 );
 
+// Hız sınırı alanları KB/s; boş alan sınırsız demektir
+const toKb = (bytes) => (bytes == null ? '' : Math.round(bytes / 1024));
+const fromKb = (value) => {
+  const kb = Number(value);
+  return String(value).trim() === '' || !Number.isFinite(kb) ? null : Math.max(0, Math.round(kb * 1024));
+};
+
 const Settings = ({ onBack, config, updateConfig }) => {
   const [latencies, setLatencies] = useState({});
   const [isChecking, setIsChecking] = useState(false);
   const [autostartEnabled, setAutostartEnabled] = useState(false);
   const [sortedProviders, setSortedProviders] = useState([]);
   const [fixStatus, setFixStatus] = useState('idle');
+  const [remoteEngines, setRemoteEngines] = useState(null);
+  const [isSearching, setIsSearching] = useState(false);
+  const [launchApps, setLaunchApps] = useState([]);
+  const [newApp, setNewApp] = useState({ name: '', path: '', args: '', restart: false });
+  const [launchStatus, setLaunchStatus] = useState(null);
+  const [lanAcl, setLanAcl] = useState([]);
+  const [newLanEntry, setNewLanEntry] = useState('');
+  const [lanAclError, setLanAclError] = useState(null);
+  const [lanAuthEnabled, setLanAuthEnabled] = useState(false);
+  const [lanShaping, setLanShaping] = useState({ global: {}, clients: {}, shape_loopback: false });
+  const [newRateClient, setNewRateClient] = useState({ address: '', upload: '', download: '' });
 
   const lang = config.language || 'tr';
   const t = getTranslations(lang);
@@ -48,6 +67,8 @@ const Settings = ({ onBack, config, updateConfig }) => {
 
   useEffect(() => {
     checkAutostart();
+    loadLaunchApps();
+    loadLanAcl();
   }, []);
 
   useEffect(() => {
@@ -56,6 +77,138 @@ const Settings = ({ onBack, config, updateConfig }) => {
     }
   }, [config.dnsMode]);
 
+  // LAN paylaşımı açık Vexar'ları UDP yayınıyla bul (Rust: discover_remote_engines)
+  const searchRemoteEngines = async () => {
+    setIsSearching(true);
+    try {
+      const found = await invoke('discover_remote_engines', { timeoutMs: 1500 });
+      setRemoteEngines(found);
+      if (found.length === 1 && !config.remoteAddress) {
+        updateConfig('remoteAddress', found[0].address);
+      }
+    } catch (e) {
+      console.error('Remote discovery failed:', e);
+      setRemoteEngines([]);
+    }
+    setIsSearching(false);
+  };
+
+  // Başlatıcı listesi Rust tarafında (settings.json) tutuluyor; tepsi menüsü de oradan okur
+  const loadLaunchApps = async () => {
+    try {
+      const settings = await invoke('get_settings');
+      setLaunchApps(settings.launcher.apps);
+    } catch (e) {
+      console.error('Launcher load failed:', e);
+    }
+  };
+
+  const saveLaunchApps = async (apps) => {
+    try {
+      setLaunchApps(await invoke('update_launcher', { apps }));
+      setLaunchStatus(null);
+      return true;
+    } catch (e) {
+      setLaunchStatus({ ok: false, text: String(e) });
+      return false;
+    }
+  };
+
+  const addLaunchApp = async () => {
+    const app = {
+      id: `app-${Date.now().toString(36)}`,
+      name: newApp.name.trim(),
+      path: newApp.path.trim(),
+      args: newApp.args.split(/\s+/).filter(Boolean),
+      restart_running: newApp.restart
+    };
+    if (await saveLaunchApps([...launchApps, app])) {
+      setNewApp({ name: '', path: '', args: '', restart: false });
+    }
+  };
+
+  const launchApp = async (app) => {
+    try {
+      const report = await invoke('launch_app', { id: app.id });
+      setLaunchStatus({
+        ok: true,
+        text: report.already_running ? t.launcherAlreadyRunning(app.name) : t.launcherStarted(app.name)
+      });
+    } catch (e) {
+      setLaunchStatus({ ok: false, text: String(e) });
+    }
+  };
+
+  // LAN izin listesi Rust tarafında; değişiklik çalışan ağ geçidine hemen uygulanır
+  const loadLanAcl = async () => {
+    try {
+      setLanAcl(await invoke('list_lan_acl'));
+      setLanAuthEnabled((await invoke('get_lan_auth')).enabled);
+      setLanShaping(await invoke('get_lan_shaping'));
+    } catch (e) {
+      console.error('LAN ACL load failed:', e);
+    }
+  };
+
+  const addLanEntry = async () => {
+    try {
+      setLanAcl(await invoke('add_lan_acl', { entry: newLanEntry.trim() }));
+      setNewLanEntry('');
+      setLanAclError(null);
+    } catch (e) {
+      setLanAclError(String(e));
+    }
+  };
+
+  // İlk açılışta kullanıcı adı/parola Rust tarafında üretilir; bağlantı penceresinde gösterilir
+  const toggleLanAuth = async (enabled) => {
+    try {
+      setLanAuthEnabled((await invoke('set_lan_auth', { enabled })).enabled);
+      setLanAclError(null);
+    } catch (e) {
+      setLanAclError(String(e));
+    }
+  };
+
+  // Hız sınırları Rust tarafında bayt/saniye; arayüzde KB/s gösterilir
+  const saveLanShaping = async (shaping) => {
+    try {
+      setLanShaping(await invoke('update_lan_shaping', { shaping }));
+      setLanAclError(null);
+      return true;
+    } catch (e) {
+      setLanAclError(String(e));
+      return false;
+    }
+  };
+
+  const setGlobalRate = (direction, value) => {
+    if (value === String(toKb(lanShaping.global[direction]))) return;
+    saveLanShaping({ ...lanShaping, global: { ...lanShaping.global, [direction]: fromKb(value) } });
+  };
+
+  const addRateClient = async () => {
+    const limit = { upload: fromKb(newRateClient.upload), download: fromKb(newRateClient.download) };
+    const clients = { ...lanShaping.clients, [newRateClient.address.trim()]: limit };
+    if (await saveLanShaping({ ...lanShaping, clients })) {
+      setNewRateClient({ address: '', upload: '', download: '' });
+    }
+  };
+
+  const removeRateClient = (address) => {
+    const { [address]: _, ...clients } = lanShaping.clients;
+    saveLanShaping({ ...lanShaping, clients });
+  };
+
+  const removeLanEntry = async (entry) => {
+    try {
+      setLanAcl(await invoke('remove_lan_acl', { entry }));
+      setLanAclError(null);
+    } catch (e) {
+      setLanAclError(String(e));
+    }
+  };
+
   const checkAutostart = async () => {
     try {
       const active = await isEnabled();
@@ -262,6 +415,221 @@ const Settings = ({ onBack, config, updateConfig }) => {
               </div>
               <Toggle checked={config.lanSharing || false} onChange={(v) => updateConfig('lanSharing', v)} />
             </div>
+
+            {config.lanSharing && (
+              <div className="v2-dns-list">
+                <div className="v2-item">
+                  <div className="v2-item-text">
+                    <h3>{t.lanAuth}</h3>
+                    <p>{t.lanAuthDesc}</p>
+                  </div>
+                  <Toggle checked={lanAuthEnabled} onChange={toggleLanAuth} />
+                </div>
+                <div className="v2-remote-hint">{t.lanAclDesc}</div>
+                {lanAcl.map((entry) => (
+                  <div key={entry} className="v2-dns-item">
+                    <div className="v2-dns-info">
+                      <span className="v2-dns-name">{entry}</span>
+                    </div>
+                    <button className="v2-refresh-btn" title={t.lanAclRemove} onClick={() => removeLanEntry(entry)}>
+                      <Trash2 size={16} />
+                    </button>
+                  </div>
+                ))}
+                <div className="v2-remote-row">
+                  <input
+                    className="v2-input"
+                    value={newLanEntry}
+                    placeholder={t.lanAclPlaceholder}
+                    spellCheck={false}
+                    onChange={(e) => setNewLanEntry(e.target.value)}
+                    onKeyDown={(e) => e.key === 'Enter' && newLanEntry.trim() && addLanEntry()}
+                  />
+                  <button
+                    className="v2-refresh-btn"
+                    title={t.lanAclAdd}
+                    onClick={addLanEntry}
+                    disabled={!newLanEntry.trim()}
+                  >
+                    <Plus size={16} />
+                  </button>
+                </div>
+                <div className="v2-item">
+                  <div className="v2-item-text">
+                    <h3>{t.lanShaping}</h3>
+                    <p>{t.lanShapingDesc}</p>
+                  </div>
+                </div>
+                <div className="v2-remote-row">
+                  <input
+                    key={`up-${lanShaping.global.upload ?? ''}`}
+                    className="v2-input"
+                    type="number"
+                    min="1"
+                    defaultValue={toKb(lanShaping.global.upload)}
+                    placeholder={t.lanShapingUpload}
+                    title={t.lanShapingUpload}
+                    onBlur={(e) => setGlobalRate('upload', e.target.value)}
+                  />
+                  <input
+                    key={`down-${lanShaping.global.download ?? ''}`}
+                    className="v2-input"
+                    type="number"
+                    min="1"
+                    defaultValue={toKb(lanShaping.global.download)}
+                    placeholder={t.lanShapingDownload}
+                    title={t.lanShapingDownload}
+                    onBlur={(e) => setGlobalRate('download', e.target.value)}
+                  />
+                </div>
+                {Object.entries(lanShaping.clients).map(([address, limit]) => (
+                  <div key={address} className="v2-dns-item">
+                    <div className="v2-dns-info">
+                      <span className="v2-dns-name">{address}</span>
+                      <span className="v2-dns-desc">{t.lanShapingRate(toKb(limit.upload), toKb(limit.download))}</span>
+                    </div>
+                    <button className="v2-refresh-btn" title={t.lanAclRemove} onClick={() => removeRateClient(address)}>
+                      <Trash2 size={16} />
+                    </button>
+                  </div>
+                ))}
+                <div className="v2-remote-row">
+                  <input
+                    className="v2-input"
+                    value={newRateClient.address}
+                    placeholder={t.lanShapingClient}
+                    spellCheck={false}
+                    onChange={(e) => setNewRateClient({ ...newRateClient, address: e.target.value })}
+                  />
+                  <input
+                    className="v2-input"
+                    type="number"
+                    min="1"
+                    value={newRateClient.upload}
+                    placeholder={t.lanShapingUpload}
+                    onChange={(e) => setNewRateClient({ ...newRateClient, upload: e.target.value })}
+                  />
+                  <input
+                    className="v2-input"
+                    type="number"
+                    min="1"
+                    value={newRateClient.download}
+                    placeholder={t.lanShapingDownload}
+                    onChange={(e) => setNewRateClient({ ...newRateClient, download: e.target.value })}
+                  />
+                  <button
+                    className="v2-refresh-btn"
+                    title={t.lanShapingAdd}
+                    onClick={addRateClient}
+                    disabled={!newRateClient.address.trim() || (!newRateClient.upload && !newRateClient.download)}
+                  >
+                    <Plus size={16} />
+                  </button>
+                </div>
+                <div className="v2-item">
+                  <div className="v2-item-text">
+                    <h3>{t.lanShapingLoopback}</h3>
+                    <p>{t.lanShapingLoopbackDesc}</p>
+                  </div>
+                  <Toggle
+                    checked={lanShaping.shape_loopback}
+                    onChange={(v) => saveLanShaping({ ...lanShaping, shape_loopback: v })}
+                  />
+                </div>
+                {lanAclError && (
+                  <div className="v2-remote-hint" style={{ color: '#fca5a5' }}>{lanAclError}</div>
+                )}
+              </div>
+            )}
+
+            <div className="v2-divider" />
+
+            <div className="v2-item">
+              <div className="v2-icon blue"><Split size={20} /></div>
+              <div className="v2-item-text">
+                <h3>{t.splitTunnel}</h3>
+                <p>{t.splitTunnelDesc}</p>
+              </div>
+              <Toggle checked={config.splitTunnel || false} onChange={(v) => updateConfig('splitTunnel', v)} />
+            </div>
+
+            <div className="v2-divider" />
+
+            <div className="v2-item">
+              <div className="v2-icon yellow"><Network size={20} /></div>
+              <div className="v2-item-text">
+                <h3>{t.socksProxy}</h3>
+                <p>{t.socksProxyDesc}</p>
+              </div>
+              <Toggle checked={config.socksProxy || false} onChange={(v) => updateConfig('socksProxy', v)} />
+            </div>
+
+            <div className="v2-divider" />
+
+            <div className="v2-item">
+              <div className="v2-icon orange"><Flame size={20} /></div>
+              <div className="v2-item-text">
+                <h3>{t.firefoxProxy}</h3>
+                <p>{t.firefoxProxyDesc}</p>
+              </div>
+              <Toggle checked={config.firefoxProxy || false} onChange={(v) => updateConfig('firefoxProxy', v)} />
+            </div>
+
+            <div className="v2-divider" />
+
+            <div className="v2-item">
+              <div className="v2-icon green"><Server size={20} /></div>
+              <div className="v2-item-text">
+                <h3>{t.remoteEngine}</h3>
+                <p>{t.remoteEngineDesc}</p>
+              </div>
+              <Toggle
+                checked={config.remoteMode || false}
+                onChange={(v) => {
+                  updateConfig('remoteMode', v);
+                  if (v && remoteEngines === null) searchRemoteEngines();
+                }}
+              />
+            </div>
+
+            {config.remoteMode && (
+              <div className="v2-dns-list">
+                <div className="v2-remote-row">
+                  <input
+                    className="v2-input"
+                    value={config.remoteAddress || ''}
+                    placeholder="192.168.1.20:8080"
+                    spellCheck={false}
+                    onChange={(e) => updateConfig('remoteAddress', e.target.value)}
+                  />
+                  <button className="v2-refresh-btn" title={t.remoteSearch} onClick={searchRemoteEngines} disabled={isSearching}>
+                    {isSearching ? <RotateCw size={16} className="spin" /> : <Search size={16} />}
+                  </button>
+                </div>
+                {isSearching && <div className="v2-remote-hint">{t.remoteSearching}</div>}
+                {!isSearching && remoteEngines?.length === 0 && (
+                  <div className="v2-remote-hint">{t.remoteNoneFound}</div>
+                )}
+                {remoteEngines?.map((engine) => {
+                  const isSelected = config.remoteAddress === engine.address;
+                  return (
+                    <div
+                      key={engine.address}
+                      className={`v2-dns-item ${isSelected ? 'selected' : ''}`}
+                      onClick={() => updateConfig('remoteAddress', engine.address)}
+                    >
+                      <div className={`v2-radio ${isSelected ? 'on' : ''}`}>
+                        {isSelected && <div className="v2-radio-dot" />}
+                      </div>
+                      <div className="v2-dns-info">
+                        <span className="v2-dns-name">{engine.name}</span>
+                        <span className="v2-dns-desc">{engine.address}</span>
+                      </div>
+                    </div>
+                  );
+                })}
+              </div>
+            )}
           </div>
         </div>
 
@@ -291,6 +659,136 @@ const Settings = ({ onBack, config, updateConfig }) => {
           </div>
         </div>
 
+        {/* ========== GELİŞTİRİCİ ARAÇLARI ========== */}
+        <div className="v2-section">
+          <div className="v2-section-title">{t.sectionDevTools}</div>
+          <div className="v2-card">
+            <div className="v2-item">
+              <div className="v2-icon orange"><GitBranch size={20} /></div>
+              <div className="v2-item-text">
+                <h3>{t.devGit}</h3>
+                <p>{t.devGitDesc}</p>
+              </div>
+              <Toggle checked={config.devGit || false} onChange={(v) => updateConfig('devGit', v)} />
+            </div>
+
+            <div className="v2-divider" />
+
+            <div className="v2-item">
+              <div className="v2-icon yellow"><Package size={20} /></div>
+              <div className="v2-item-text">
+                <h3>{t.devNpm}</h3>
+                <p>{t.devNpmDesc}</p>
+              </div>
+              <Toggle checked={config.devNpm || false} onChange={(v) => updateConfig('devNpm', v)} />
+            </div>
+
+            <div className="v2-divider" />
+
+            <div className="v2-item">
+              <div className="v2-icon blue"><Package size={20} /></div>
+              <div className="v2-item-text">
+                <h3>{t.devPip}</h3>
+                <p>{t.devPipDesc}</p>
+              </div>
+              <Toggle checked={config.devPip || false} onChange={(v) => updateConfig('devPip', v)} />
+            </div>
+
+            <div className="v2-divider" />
+
+            <div className="v2-item">
+              <div className="v2-icon green"><Terminal size={20} /></div>
+              <div className="v2-item-text">
+                <h3>{t.devShellEnv}</h3>
+                <p>{t.devShellEnvDesc}</p>
+              </div>
+              <Toggle checked={config.devShellEnv || false} onChange={(v) => updateConfig('devShellEnv', v)} />
+            </div>
+          </div>
+        </div>
+
+        {/* ========== PROXY İLE BAŞLAT ========== */}
+        <div className="v2-section">
+          <div className="v2-section-title">{t.sectionLauncher}</div>
+          <div className="v2-card">
+            <div className="v2-item">
+              <div className="v2-icon purple"><Rocket size={20} /></div>
+              <div className="v2-item-text">
+                <h3>{t.launcher}</h3>
+                <p>{t.launcherDesc}</p>
+              </div>
+            </div>
+
+            <div className="v2-dns-list">
+              {launchApps.map((app) => (
+                <div key={app.id} className="v2-dns-item" onClick={() => launchApp(app)}>
+                  <div className="v2-dns-info">
+                    <span className="v2-dns-name">{app.name}</span>
+                    <span className="v2-dns-desc">
+                      {app.path}{app.restart_running ? ` · ${t.launcherRestart}` : ''}
+                    </span>
+                  </div>
+                  <button
+                    className="v2-refresh-btn"
+                    title={t.launcherRemove}
+                    onClick={(e) => {
+                      e.stopPropagation();
+                      saveLaunchApps(launchApps.filter(a => a.id !== app.id));
+                    }}
+                  >
+                    <Trash2 size={16} />
+                  </button>
+                </div>
+              ))}
+
+              <div className="v2-remote-row">
+                <input
+                  className="v2-input"
+                  value={newApp.name}
+                  placeholder={t.launcherName}
+                  spellCheck={false}
+                  onChange={(e) => setNewApp({ ...newApp, name: e.target.value })}
+                />
+                <input
+                  className="v2-input"
+                  value={newApp.args}
+                  placeholder={t.launcherArgs}
+                  spellCheck={false}
+                  onChange={(e) => setNewApp({ ...newApp, args: e.target.value })}
+                />
+              </div>
+              <div className="v2-remote-row">
+                <input
+                  className="v2-input"
+                  value={newApp.path}
+                  placeholder={t.launcherPath}
+                  spellCheck={false}
+                  onChange={(e) => setNewApp({ ...newApp, path: e.target.value })}
+                />
+                <button
+                  className="v2-refresh-btn"
+                  title={t.launcherAdd}
+                  onClick={addLaunchApp}
+                  disabled={!newApp.name.trim() || !newApp.path.trim()}
+                >
+                  <Plus size={16} />
+                </button>
+              </div>
+              <div className="v2-item">
+                <div className="v2-item-text">
+                  <p>{t.launcherRestartDesc}</p>
+                </div>
+                <Toggle checked={newApp.restart} onChange={(v) => setNewApp({ ...newApp, restart: v })} />
+              </div>
+              {launchStatus && (
+                <div className="v2-remote-hint" style={{ color: launchStatus.ok ? '' : '#fca5a5' }}>
+                  {launchStatus.text}
+                </div>
+              )}
+            </div>
+          </div>
+        </div>
+
         {/* ========== 4. DNS LİSTESİ ========== */}
         <div className="v2-section">
           <div className="v2-section-header-row">
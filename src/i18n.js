@@ -8,6 +8,9 @@ const translations = {
 
     // ===== APP.JSX - Main Status =====
     statusConnected: 'GÜVENLİ',
+    statusPaused: 'Duraklatıldı',
+    pauseRemaining: (time) => `Kalan süre: ${time}`,
+    statusDegraded: 'Motor yanıt vermiyor, proxy geçici olarak kapalı',
     statusConnecting: 'BAĞLANIYOR...',
     statusDisconnecting: 'KESİLİYOR...',
     statusReady2: 'HAZIR',
@@ -40,6 +43,18 @@ const translations = {
     modalDesc: 'Cihazınızın Wi-Fi ayarlarında <strong>Proxy</strong> kısmını <strong>Manuel</strong> yapın ve bilgileri girin.',
     modalHost: 'Sunucu (Host)',
     modalPort: 'Port',
+    modalPacDesc: 'Cihazınızın Wi-Fi ayarlarında <strong>Proxy</strong> kısmını <strong>Otomatik</strong> yapın ve aşağıdaki adresi girin.',
+    modalPacUrl: 'Yapılandırma Adresi (PAC)',
+    modalManual: (host, port) => `Otomatik proxy desteklemeyen cihazlarda Manuel seçip ${host} ve ${port} portunu girin.`,
+    modalUsername: 'Kullanıcı Adı',
+    modalPassword: 'Parola',
+    modalRotate: 'Parolayı yenile',
+    modalClients: 'Bağlı Cihazlar',
+    modalNoClients: 'Henüz bağlanan cihaz yok.',
+    modalClientTunnels: (active, limit) => `${active}${limit ? `/${limit}` : ''} bağlantı`,
+    modalClientLimit: 'Sınır',
+    modalClientKick: 'Bağlantıyı kes',
+    modalClientBlocked: 'engellendi',
     modalTutorial: 'Nasıl Yapılır? (Rehber)',
 
     // ===== APP.JSX - Admin Modal =====
@@ -55,17 +70,26 @@ const translations = {
 
     // ===== APP.JSX - Log Messages =====
     logEngineStarting: (port) => `Vexar Motoru başlatılıyor (Port: ${port})...`,
+    logRemoteConnecting: (addr) => `Uzak Vexar kontrol ediliyor: ${addr}...`,
     logDnsUsed: (name, ip) => `Kullanılan DNS: ${name} (${ip})`,
     logDnsDefault: 'DNS: Sistem Varsayılanı',
     logConnected: 'Bağlantı başarılı! Trafik şifreleniyor.',
     logDisconnected: 'Bağlantı kesildi.',
-    logProxySet: (port) => `Sistem Proxy ayarlandı: 127.0.0.1:${port}`,
+    logProxySet: (addr) => `Sistem Proxy ayarlandı: ${addr}`,
     logProxyCleared: 'Sistem Proxy Temizlendi',
     logEngineStopped: (code) => `Vexar motoru beklenmedik şekilde durduruldu (Kod: ${code})`,
     logEngineStartError: (err) => `Motor başlatılamadı: ${err}`,
     logAutoReconnect: 'Otomatik yeniden bağlanma aktif...',
-    logReconnecting: (n) => `Yeniden bağlanılıyor... (Deneme ${n}/5)`,
-    logReconnectWait: (sec, n) => `${sec} saniye sonra yeniden denenecek... (Deneme ${n}/5)`,
+    logReconnecting: (n, max) => `Yeniden bağlanılıyor... (Deneme ${n}/${max})`,
+    logReconnectWait: (sec, n, max) => `${sec} saniye sonra yeniden denenecek... (Deneme ${n}/${max})`,
+    logBreakerTripped: (n, sec) => `Motor ${sec} saniye içinde ${n} kez çöktü, yeniden deneme durduruldu.`,
+    logHeartbeatFailOpen: (n) => `Motor art arda ${n} kontrolde yanıt vermedi, internetin kesilmemesi için sistem proxy'si kapatıldı.`,
+    logHeartbeatRecovered: 'Motor yeniden yanıt veriyor, sistem proxy\'si tekrar açıldı.',
+    logProxyDrift: (fields) => `Sistem proxy ayarları başka bir uygulama tarafından değiştirildi (${fields}).`,
+    logProxyDriftReasserted: 'Vexar ayarları yeniden uygulandı.',
+    logProxyPaused: (min) => `Proxy ${min} dakika duraklatıldı, motor çalışmaya devam ediyor.`,
+    logProxyResumed: 'Proxy yeniden uygulandı.',
+    logProxyDriftDisconnected: 'Proxy başka bir uygulamaya geçtiği için bağlantı kesildi.',
     logReconnectNow: 'Yeniden bağlanılıyor...',
     logMaxRetries: 'Bağlantı kurulamadı. Maksimum deneme sayısına ulaşıldı.',
     logPossibleReasons: 'Olası sebepler:',
@@ -99,9 +123,63 @@ const translations = {
     sectionNetwork: 'AĞ AYARLARI',
     lanSharing: 'Yerel Ağ Paylaşımı',
     lanSharingDesc: 'Diğer cihazlardan (Tel, Konsol) bağlanmaya izin ver',
+    lanAclDesc: 'Yalnızca bu adreslerden gelen bağlantılar kabul edilir. Bu bilgisayar her zaman bağlanabilir.',
+    lanAclPlaceholder: '192.168.1.0/24 veya 192.168.1.20',
+    lanAclAdd: 'İzin ver',
+    lanAclRemove: 'Kaldır',
+    lanAuth: 'Parola İste',
+    lanAuthDesc: 'Diğer cihazlar kullanıcı adı ve parola ile bağlanır (HTTP ve SOCKS5)',
+    lanShaping: 'Hız Sınırı',
+    lanShapingDesc: 'Tüm cihazların toplam hızı ve cihaz başına sınırlar (KB/s). Boş bırakılan alan sınırsızdır.',
+    lanShapingUpload: 'Yükleme KB/s',
+    lanShapingDownload: 'İndirme KB/s',
+    lanShapingClient: 'Cihaz IP adresi',
+    lanShapingAdd: 'Sınır ekle',
+    lanShapingRate: (up, down) => `↑ ${up || '∞'} KB/s · ↓ ${down || '∞'} KB/s`,
+    lanShapingLoopback: 'Bu Bilgisayarı da Sınırla',
+    lanShapingLoopbackDesc: 'Kapalıyken bu bilgisayarın kendi trafiği sınırlardan muaftır',
+    splitTunnel: 'Bölünmüş Tünel',
+    splitTunnelDesc: 'Yalnızca listedeki siteler (Discord vb.) Vexar üzerinden geçsin',
+    socksProxy: 'SOCKS Proxy',
+    socksProxyDesc: 'Yalnızca SOCKS destekleyen uygulamalar da Vexar üzerinden geçsin (1080)',
+    firefoxProxy: 'Firefox',
+    firefoxProxyDesc: 'Kendi proxy ayarını kullanan Firefox profillerini de yönlendir (yeniden başlatınca etkili olur)',
+    remoteEngine: 'Uzak Vexar Kullan',
+    remoteEngineDesc: 'Bu bilgisayarda motor çalıştırma, ağdaki başka bir Vexar\'a bağlan',
+    remoteSearch: 'Ağda Ara',
+    remoteSearching: 'Ağda Vexar aranıyor...',
+    remoteNoneFound: 'Paylaşım açık bir Vexar bulunamadı.',
 
     // Section: Automation
     sectionAutomation: 'OTOMASYON',
+    sectionDevTools: 'GELİŞTİRİCİ ARAÇLARI',
+    sectionLauncher: 'PROXY İLE BAŞLAT',
+    launcher: 'Uygulamalar',
+    launcherDesc: 'Discord gibi uygulamaları doğrudan Vexar\'a bağlı başlat (tepsi menüsünden de)',
+    launcherName: 'Ad (Discord)',
+    launcherPath: 'Uygulama yolu (C:\\...\\Discord.exe)',
+    launcherArgs: 'Ek argümanlar',
+    launcherAdd: 'Ekle',
+    launcherRemove: 'Kaldır',
+    launcherRestart: 'açıksa yeniden başlat',
+    launcherRestartDesc: 'Açık olan kopyayı kapatıp proxy ile yeniden başlat',
+    launcherStarted: (name) => `${name} Vexar üzerinden başlatıldı`,
+    launcherAlreadyRunning: (name) => `${name} zaten açıktı; proxy için önce kapatın`,
+    logLaunchFailed: 'Uygulama başlatılamadı',
+    logLanRejected: (address) => `LAN bağlantısı reddedildi: ${address} izin listesinde değil`,
+    logLanCredentialsRotated: 'LAN parolası yenilendi; diğer cihazlarda güncelleyin',
+    logLanCredentialsRotateFailed: 'LAN parolası yenilenemedi',
+    logLanClientKicked: (address) => `${address} bağlantısı kesildi`,
+    logLanClientFailed: 'Cihaz ayarı uygulanamadı',
+    devGit: 'Git',
+    devGitDesc: 'Bağlanınca ~/.gitconfig içine http.proxy yazılır',
+    devNpm: 'npm',
+    devNpmDesc: 'Bağlanınca ~/.npmrc içine proxy ayarları yazılır',
+    devPip: 'pip',
+    devPipDesc: 'Bağlanınca pip yapılandırmasına proxy yazılır',
+    devShellEnv: 'Terminal Ortamı',
+    devShellEnvDesc: 'HTTP_PROXY/NO_PROXY içeren dev-proxy.env dosyası oluşturulur',
+    logIntegrationsFailed: 'Uygulama proxy ayarı uygulanamadı',
     autoConnect: 'Otomatik Bağlan',
     autoConnectDesc: 'Uygulama açılır açılmaz bağlan',
     autoReconnect: 'Otomatik Yeniden Bağlan',
@@ -165,6 +243,9 @@ const translations = {
 
     // ===== APP.JSX - Main Status =====
     statusConnected: 'SECURE',
+    statusPaused: 'Paused',
+    pauseRemaining: (time) => `Time left: ${time}`,
+    statusDegraded: 'Engine not responding, proxy temporarily off',
     statusConnecting: 'CONNECTING...',
     statusDisconnecting: 'DISCONNECTING...',
     statusReady2: 'READY',
@@ -197,6 +278,18 @@ const translations = {
     modalDesc: 'Go to your device\'s Wi-Fi settings, set <strong>Proxy</strong> to <strong>Manual</strong> and enter the details below.',
     modalHost: 'Server (Host)',
     modalPort: 'Port',
+    modalPacDesc: 'Go to your device\'s Wi-Fi settings, set <strong>Proxy</strong> to <strong>Automatic</strong> and enter the address below.',
+    modalPacUrl: 'Configuration URL (PAC)',
+    modalManual: (host, port) => `On devices without automatic proxy support, choose Manual and enter ${host} with port ${port}.`,
+    modalUsername: 'Username',
+    modalPassword: 'Password',
+    modalRotate: 'Rotate password',
+    modalClients: 'Connected Devices',
+    modalNoClients: 'No devices have connected yet.',
+    modalClientTunnels: (active, limit) => `${active}${limit ? `/${limit}` : ''} connections`,
+    modalClientLimit: 'Limit',
+    modalClientKick: 'Disconnect',
+    modalClientBlocked: 'blocked',
     modalTutorial: 'How To? (Guide)',
 
     // ===== APP.JSX - Admin Modal =====
@@ -212,17 +305,26 @@ const translations = {
 
     // ===== APP.JSX - Log Messages =====
     logEngineStarting: (port) => `Vexar Engine starting (Port: ${port})...`,
+    logRemoteConnecting: (addr) => `Checking remote Vexar: ${addr}...`,
     logDnsUsed: (name, ip) => `DNS: ${name} (${ip})`,
     logDnsDefault: 'DNS: System Default',
     logConnected: 'Connection successful! Traffic is encrypted.',
     logDisconnected: 'Disconnected.',
-    logProxySet: (port) => `System Proxy set: 127.0.0.1:${port}`,
+    logProxySet: (addr) => `System Proxy set: ${addr}`,
     logProxyCleared: 'System Proxy Cleared',
     logEngineStopped: (code) => `Vexar engine stopped unexpectedly (Code: ${code})`,
     logEngineStartError: (err) => `Engine failed to start: ${err}`,
     logAutoReconnect: 'Auto-reconnect enabled...',
-    logReconnecting: (n) => `Reconnecting... (Attempt ${n}/5)`,
-    logReconnectWait: (sec, n) => `Retrying in ${sec} seconds... (Attempt ${n}/5)`,
+    logReconnecting: (n, max) => `Reconnecting... (Attempt ${n}/${max})`,
+    logReconnectWait: (sec, n, max) => `Retrying in ${sec} seconds... (Attempt ${n}/${max})`,
+    logBreakerTripped: (n, sec) => `Engine crashed ${n} times in ${sec} seconds, stopped retrying.`,
+    logHeartbeatFailOpen: (n) => `Engine missed ${n} health checks in a row, system proxy disabled to keep internet working.`,
+    logHeartbeatRecovered: 'Engine is responding again, system proxy re-enabled.',
+    logProxyDrift: (fields) => `System proxy settings were changed by another application (${fields}).`,
+    logProxyDriftReasserted: 'Vexar settings were re-applied.',
+    logProxyPaused: (min) => `Proxy paused for ${min} minutes, engine keeps running.`,
+    logProxyResumed: 'Proxy re-applied.',
+    logProxyDriftDisconnected: 'Disconnected because another application took over the proxy.',
     logReconnectNow: 'Reconnecting...',
     logMaxRetries: 'Connection failed. Maximum attempts reached.',
     logPossibleReasons: 'Possible reasons:',
@@ -256,9 +358,63 @@ const translations = {
     sectionNetwork: 'NETWORK',
     lanSharing: 'LAN Sharing',
     lanSharingDesc: 'Allow connections from other devices (Phone, Console)',
+    lanAclDesc: 'Only connections from these addresses are accepted. This computer can always connect.',
+    lanAclPlaceholder: '192.168.1.0/24 or 192.168.1.20',
+    lanAclAdd: 'Allow',
+    lanAclRemove: 'Remove',
+    lanAuth: 'Require Password',
+    lanAuthDesc: 'Other devices connect with a username and password (HTTP and SOCKS5)',
+    lanShaping: 'Bandwidth Limits',
+    lanShapingDesc: 'Total speed for all devices and per-device limits (KB/s). Empty fields are unlimited.',
+    lanShapingUpload: 'Upload KB/s',
+    lanShapingDownload: 'Download KB/s',
+    lanShapingClient: 'Device IP address',
+    lanShapingAdd: 'Add limit',
+    lanShapingRate: (up, down) => `↑ ${up || '∞'} KB/s · ↓ ${down || '∞'} KB/s`,
+    lanShapingLoopback: 'Limit This Computer Too',
+    lanShapingLoopbackDesc: 'When off, this computer\'s own traffic is exempt from limits',
+    splitTunnel: 'Split Tunneling',
+    splitTunnelDesc: 'Route only listed sites (Discord etc.) through Vexar',
+    socksProxy: 'SOCKS Proxy',
+    socksProxyDesc: 'Also route apps that only support SOCKS through Vexar (1080)',
+    firefoxProxy: 'Firefox',
+    firefoxProxyDesc: 'Also route Firefox profiles that use their own proxy settings (applies after restart)',
+    remoteEngine: 'Use Remote Vexar',
+    remoteEngineDesc: 'Run no engine here; connect to another Vexar on the network',
+    remoteSearch: 'Search Network',
+    remoteSearching: 'Searching for Vexar on the network...',
+    remoteNoneFound: 'No Vexar with LAN sharing found.',
 
     // Section: Automation
     sectionAutomation: 'AUTOMATION',
+    sectionDevTools: 'DEVELOPER TOOLS',
+    sectionLauncher: 'LAUNCH THROUGH PROXY',
+    launcher: 'Apps',
+    launcherDesc: 'Start apps like Discord pinned to Vexar (also from the tray menu)',
+    launcherName: 'Name (Discord)',
+    launcherPath: 'App path (C:\\...\\Discord.exe)',
+    launcherArgs: 'Extra arguments',
+    launcherAdd: 'Add',
+    launcherRemove: 'Remove',
+    launcherRestart: 'restart if running',
+    launcherRestartDesc: 'Close a running copy and restart it through the proxy',
+    launcherStarted: (name) => `${name} started through Vexar`,
+    launcherAlreadyRunning: (name) => `${name} was already running; close it first to use the proxy`,
+    logLaunchFailed: 'Could not launch app',
+    logLanRejected: (address) => `LAN connection rejected: ${address} is not on the allow list`,
+    logLanCredentialsRotated: 'LAN password rotated; update it on your other devices',
+    logLanCredentialsRotateFailed: 'Could not rotate LAN password',
+    logLanClientKicked: (address) => `Disconnected ${address}`,
+    logLanClientFailed: 'Could not update device settings',
+    devGit: 'Git',
+    devGitDesc: 'Writes http.proxy to ~/.gitconfig while connected',
+    devNpm: 'npm',
+    devNpmDesc: 'Writes proxy settings to ~/.npmrc while connected',
+    devPip: 'pip',
+    devPipDesc: 'Writes the proxy to the pip configuration while connected',
+    devShellEnv: 'Terminal Environment',
+    devShellEnvDesc: 'Creates a dev-proxy.env file with HTTP_PROXY/NO_PROXY',
+    logIntegrationsFailed: 'Could not apply app proxy settings',
     autoConnect: 'Auto Connect',
     autoConnectDesc: 'Connect as soon as the app opens',
     autoReconnect: 'Auto Reconnect',